@@ -2,15 +2,19 @@
 use std::io::{self, Read as _};
 
 use ambient_auction_api::{
-    instruction::SubmitJobOutputArgs, Auction, Bid, BundleEscrowV2, JobRequest,
-    JobVerificationState, RequestBundle, VerificationState,
+    instruction::SubmitJobOutputArgs, AccountLayoutVersion, Auction, Bid, BundleEscrowV2,
+    BundleRefundPageV2, CidCodec, JobRequest, JobVerificationState, NodePenaltyRecord,
+    NodeRegistration, NodeReputationV2, RequestBundle, RequestTier, VerificationState,
 };
 use base64::Engine as _;
 use clap::{Parser, Subcommand, ValueEnum};
 use serde::Serialize;
 
-fn display_job_request(buffer: Vec<u8>) -> Result<(), String> {
+fn display_job_request(buffer: Vec<u8>, input_codec: InputCodec) -> Result<(), String> {
     eprintln!("Expected len: {}", JobRequest::LEN);
+    let job_request = bytemuck::try_pod_read_unaligned::<JobRequest>(&buffer).map_err(|e| {
+        format!("To decode JobRequest from account bytes. Is it the right account type? {e}")
+    })?;
     let JobRequest {
         bundle,
         max_output_tokens,
@@ -20,9 +24,7 @@ fn display_job_request(buffer: Vec<u8>) -> Result<(), String> {
         status,
         verification,
         ..
-    } = bytemuck::try_pod_read_unaligned::<JobRequest>(&buffer).map_err(|e| {
-        format!("To decode JobRequest from account bytes. Is it the right account type? {e}")
-    })?;
+    } = job_request;
 
     let VerificationState {
         merkle_root,
@@ -32,7 +34,17 @@ fn display_job_request(buffer: Vec<u8>) -> Result<(), String> {
         output_hash,
         ..
     } = verification;
-    let input_hash_b64 = base64::prelude::BASE64_STANDARD.encode(input_hash);
+    let input_cid = job_request
+        .input_cid_with_codec(input_codec.into())
+        .map_or_else(
+            || {
+                format!(
+                    "{} (encrypted)",
+                    base64::prelude::BASE64_STANDARD.encode(input_hash)
+                )
+            },
+            |cid| cid.to_string(),
+        );
     let output_hash_b64 = base64::prelude::BASE64_STANDARD.encode(output_hash);
     let merkle_root_b64 = base64::prelude::BASE64_STANDARD.encode(merkle_root);
     let bundle_b58 = bs58::encode(bundle).into_string();
@@ -48,7 +60,7 @@ fn display_job_request(buffer: Vec<u8>) -> Result<(), String> {
         .join(", ");
     eprintln!(
         "bundle: {bundle_b58}
-input hash: {input_hash_b64}
+input CID: {input_cid}
 max output tokens: {max_output_tokens}
 output token count: {output_token_count}
 input token count: {input_token_count}
@@ -92,18 +104,126 @@ encryption node public key: {encryption_node_publickey:?}"
 
 fn display_bundle_escrow_v2(buffer: Vec<u8>) -> Result<(), String> {
     eprintln!(
-        "Expected len: {} (V1) or {} (V2)",
+        "Expected len: {} (V1), {} (V2/V3), {} (V4), {} (V5) or {} (V6)",
         BundleEscrowV2::LEN_V1,
-        BundleEscrowV2::LEN_V2
+        BundleEscrowV2::LEN_V2,
+        BundleEscrowV2::LEN_V4,
+        BundleEscrowV2::LEN_V5,
+        BundleEscrowV2::LEN_V6
     );
-    let data = BundleEscrowV2::read(&buffer).ok_or_else(|| {
+    let account = BundleEscrowV2::from_bytes(&buffer).ok_or_else(|| {
         "To decode BundleEscrowV2 from account bytes. Is it the right versioned account type?"
             .to_string()
     })?;
+    if let Some(token) = account.token_denomination() {
+        eprintln!(
+            "Token mint: {}, vault: {}, decimals: {}",
+            bs58::encode(token.mint).into_string(),
+            bs58::encode(token.vault).into_string(),
+            token.decimals
+        );
+    }
+    if let Some(protocol_fee_lamports) = account.protocol_fee_lamports() {
+        eprintln!("Protocol fee lamports: {protocol_fee_lamports}");
+    }
+    if let Some(runner_ups) = account.runner_ups() {
+        for (rank, runner_up) in runner_ups.runner_ups[..usize::from(runner_ups.runner_up_count)]
+            .iter()
+            .enumerate()
+        {
+            eprintln!(
+                "Runner-up {rank}: {} at {} per output token",
+                bs58::encode(runner_up.node_pubkey).into_string(),
+                runner_up.clearing_price_per_output_token
+            );
+        }
+        eprintln!("Promoted runner-ups: {}", runner_ups.promoted_count);
+    }
+    if account.layout().version == AccountLayoutVersion::V6 {
+        let verifier_set = account.verifier_set();
+        for (index, verifier) in verifier_set.selected_verifiers[..verifier_set.len()]
+            .iter()
+            .enumerate()
+        {
+            eprintln!(
+                "Verifier {index}: {}, reward remaining: {}, in quorum: {}",
+                bs58::encode(verifier).into_string(),
+                verifier_set.verifier_reward_remaining[index],
+                verifier_set.in_quorum(index)
+            );
+        }
+    }
+    println!(
+        "{}",
+        serde_json::to_string_pretty(account.as_raw()).unwrap()
+    );
+    Ok(())
+}
+
+fn display_bundle_refund_page_v2(buffer: Vec<u8>) -> Result<(), String> {
+    eprintln!("Expected len: {}", BundleRefundPageV2::LEN);
+    let data = BundleRefundPageV2::read(&buffer).ok_or_else(|| {
+        "To decode BundleRefundPageV2 from account bytes. Is it the right versioned account type?"
+            .to_string()
+    })?;
     println!("{}", serde_json::to_string_pretty(&data).unwrap());
     Ok(())
 }
 
+fn display_node_penalty_record(buffer: Vec<u8>) -> Result<(), String> {
+    eprintln!("Expected len: {}", NodePenaltyRecord::LEN);
+    let data = NodePenaltyRecord::read(&buffer).ok_or_else(|| {
+        "To decode NodePenaltyRecord from account bytes. Is it the right versioned account type?"
+            .to_string()
+    })?;
+    eprintln!(
+        "Outstanding penalty lamports: {}",
+        data.outstanding_penalty_lamports()
+    );
+    println!("{}", serde_json::to_string_pretty(&data).unwrap());
+    Ok(())
+}
+
+fn display_node_reputation_v2(buffer: Vec<u8>) -> Result<(), String> {
+    eprintln!("Expected len: {}", NodeReputationV2::LEN);
+    let data = NodeReputationV2::read(&buffer).ok_or_else(|| {
+        "To decode NodeReputationV2 from account bytes. Is it the right versioned account type?"
+            .to_string()
+    })?;
+    eprintln!("Reliability score bps: {}", data.reliability_score_bps());
+    println!("{}", serde_json::to_string_pretty(&data).unwrap());
+    Ok(())
+}
+
+fn display_node_registration(buffer: Vec<u8>) -> Result<(), String> {
+    eprintln!("Expected len: {}", NodeRegistration::LEN);
+    let data = NodeRegistration::read(&buffer).ok_or_else(|| {
+        "To decode NodeRegistration from account bytes. Is it the right versioned account type?"
+            .to_string()
+    })?;
+    let info = data.info;
+    let supported_tiers = RequestTier::ALL
+        .into_iter()
+        .filter(|tier| info.supports_tier(*tier))
+        .map(|tier| format!("{tier:?}"))
+        .collect::<Vec<_>>();
+    // The hostname buffer is too long for serde's array impls, so the JSON is built from the
+    // decoded fields instead of the raw struct.
+    let json = serde_json::json!({
+        "node_authority": bs58::encode(data.node_authority).into_string(),
+        "endpoint": info.socket_addr().to_string(),
+        "hostname": info.hostname(),
+        "encryption_public_key": info
+            .encryption_public_key()
+            .map(|key| bs58::encode(key).into_string()),
+        "supported_tiers": supported_tiers,
+        "registered_slot": data.registered_slot,
+        "heartbeat_slot": data.heartbeat_slot,
+    });
+    println!("{}", serde_json::to_string_pretty(&json).unwrap());
+    Ok(())
+}
+
 fn display_generic<T: bytemuck::Pod + Serialize>(buffer: Vec<u8>) -> Result<(), String> {
     let data = bytemuck::try_pod_read_unaligned::<T>(&buffer)
         .map_err(|e| format!("To decode from transaction bytes. Is it the right data type? {e}"))?;
@@ -129,10 +249,32 @@ enum Decoder {
     Hex,
 }
 
+/// Codec the job input was stored under, which the account does not record
+#[derive(ValueEnum, Clone, Copy, Default, Debug)]
+enum InputCodec {
+    #[default]
+    Raw,
+    /// Printed as a CIDv0
+    DagPb,
+}
+
+impl From<InputCodec> for CidCodec {
+    fn from(codec: InputCodec) -> Self {
+        match codec {
+            InputCodec::Raw => CidCodec::Raw,
+            InputCodec::DagPb => CidCodec::DagPb,
+        }
+    }
+}
+
 #[derive(Subcommand)]
 enum Commands {
     /// Decode a `JobRequest` struct from raw data
-    JobRequest,
+    JobRequest {
+        /// Codec of the input CID
+        #[arg(long, value_enum, default_value_t = Default::default())]
+        input_codec: InputCodec,
+    },
     /// Decode a `Auction` struct from raw data
     Auction,
     /// Decode the transaction input data from a submit_job_output transaction
@@ -140,6 +282,10 @@ enum Commands {
     Bid,
     Bundle,
     BundleEscrowV2,
+    BundleRefundPageV2,
+    NodePenaltyRecord,
+    NodeReputationV2,
+    NodeRegistration,
 }
 
 fn parse_string(buf: Vec<u8>) -> Result<String, String> {
@@ -182,11 +328,15 @@ fn main() -> Result<(), String> {
     eprintln!("Data len: {}", buffer.len());
 
     match cli.command {
-        Commands::JobRequest => display_job_request(buffer),
+        Commands::JobRequest { input_codec } => display_job_request(buffer, input_codec),
         Commands::Auction => display_generic::<Auction>(buffer),
         Commands::SubmitJobOutput => display_submit_job_output(buffer[1..].to_vec()),
         Commands::Bid => display_generic::<Bid>(buffer),
         Commands::Bundle => display_generic::<RequestBundle>(buffer),
         Commands::BundleEscrowV2 => display_bundle_escrow_v2(buffer),
+        Commands::BundleRefundPageV2 => display_bundle_refund_page_v2(buffer),
+        Commands::NodePenaltyRecord => display_node_penalty_record(buffer),
+        Commands::NodeReputationV2 => display_node_reputation_v2(buffer),
+        Commands::NodeRegistration => display_node_registration(buffer),
     }
 }
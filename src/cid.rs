@@ -0,0 +1,223 @@
+//! Conversion between IPFS CIDs and the 32-byte sha2-256 digest stored on chain, for example
+//! in `JobRequest::input_hash`.
+//!
+//! A CIDv0 is the base58btc multihash `0x12 0x20 || digest` of a dag-pb node. A CIDv1 is
+//! `multibase(varint version || varint codec || multihash)`; the base32 (`b`), base58btc
+//! (`z`) and base16 (`f`) multibases are accepted. Only the raw and dag-pb codecs and sha2-256
+//! multihashes are supported, since only the digest is stored. CIDv1s are displayed in
+//! base32, the IPFS default.
+
+use crate::error::AuctionError;
+use crate::MAX_IPFS_CID_LENGTH;
+use std::fmt;
+use std::str::FromStr;
+
+const SHA2_256_MULTIHASH_CODE: u64 = 0x12;
+const SHA2_256_DIGEST_LEN: u64 = 32;
+const CIDV0_LEN: usize = 46;
+const BASE32_ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+const BASE16_ALPHABET: &[u8; 16] = b"0123456789abcdef";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CidVersion {
+    V0,
+    V1,
+}
+
+/// Multicodec of the content a CID addresses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u64)]
+pub enum CidCodec {
+    /// Plain bytes, the codec of a data account payload
+    Raw = 0x55,
+    /// A UnixFS node, the codec of every CIDv0
+    DagPb = 0x70,
+}
+
+impl TryFrom<u64> for CidCodec {
+    type Error = AuctionError;
+
+    fn try_from(code: u64) -> Result<Self, Self::Error> {
+        match code {
+            0x55 => Ok(Self::Raw),
+            0x70 => Ok(Self::DagPb),
+            _ => Err(AuctionError::UnsupportedCid),
+        }
+    }
+}
+
+/// A CID with a sha2-256 multihash.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cid {
+    version: CidVersion,
+    codec: CidCodec,
+    digest: [u8; 32],
+}
+
+impl Cid {
+    pub const fn v0(digest: [u8; 32]) -> Self {
+        Self {
+            version: CidVersion::V0,
+            codec: CidCodec::DagPb,
+            digest,
+        }
+    }
+
+    pub const fn v1(codec: CidCodec, digest: [u8; 32]) -> Self {
+        Self {
+            version: CidVersion::V1,
+            codec,
+            digest,
+        }
+    }
+
+    /// Parses a CID string. Fails with `InvalidCid` for malformed or overlong input and with
+    /// `UnsupportedCid` for another codec, hash function or CID version.
+    pub fn parse(cid: &str) -> Result<Self, AuctionError> {
+        if cid.len() > MAX_IPFS_CID_LENGTH {
+            return Err(AuctionError::InvalidCid);
+        }
+        if cid.len() == CIDV0_LEN && cid.starts_with("Qm") {
+            let bytes = bs58::decode(cid)
+                .into_vec()
+                .map_err(|_| AuctionError::InvalidCid)?;
+            let mut bytes = bytes.as_slice();
+            return Ok(Self::v0(read_multihash(&mut bytes)?));
+        }
+
+        let mut chars = cid.chars();
+        let bytes = match chars.next() {
+            Some('b') => base32_decode(chars.as_str())?,
+            Some('z') => bs58::decode(chars.as_str())
+                .into_vec()
+                .map_err(|_| AuctionError::InvalidCid)?,
+            Some('f') => base16_decode(chars.as_str())?,
+            _ => return Err(AuctionError::InvalidCid),
+        };
+        let mut bytes = bytes.as_slice();
+        if read_varint(&mut bytes)? != 1 {
+            return Err(AuctionError::UnsupportedCid);
+        }
+        let codec = CidCodec::try_from(read_varint(&mut bytes)?)?;
+        Ok(Self::v1(codec, read_multihash(&mut bytes)?))
+    }
+
+    pub fn version(&self) -> CidVersion {
+        self.version
+    }
+
+    pub fn codec(&self) -> CidCodec {
+        self.codec
+    }
+
+    /// The sha2-256 digest, as stored on chain.
+    pub fn digest(&self) -> [u8; 32] {
+        self.digest
+    }
+
+    /// The binary CID, without multibase prefix.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let multihash = [SHA2_256_MULTIHASH_CODE as u8, SHA2_256_DIGEST_LEN as u8];
+        match self.version {
+            CidVersion::V0 => [&multihash[..], &self.digest].concat(),
+            CidVersion::V1 => [&[1, self.codec as u8][..], &multihash, &self.digest].concat(),
+        }
+    }
+}
+
+impl fmt::Display for Cid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.version {
+            CidVersion::V0 => f.write_str(&bs58::encode(self.to_bytes()).into_string()),
+            CidVersion::V1 => write!(f, "b{}", base32_encode(&self.to_bytes())),
+        }
+    }
+}
+
+impl FromStr for Cid {
+    type Err = AuctionError;
+
+    fn from_str(cid: &str) -> Result<Self, Self::Err> {
+        Self::parse(cid)
+    }
+}
+
+/// Reads a sha2-256 multihash that must end the CID and returns its digest.
+fn read_multihash(bytes: &mut &[u8]) -> Result<[u8; 32], AuctionError> {
+    if read_varint(bytes)? != SHA2_256_MULTIHASH_CODE {
+        return Err(AuctionError::UnsupportedCid);
+    }
+    if read_varint(bytes)? != SHA2_256_DIGEST_LEN {
+        return Err(AuctionError::InvalidCid);
+    }
+    (*bytes).try_into().map_err(|_| AuctionError::InvalidCid)
+}
+
+/// Reads an unsigned LEB128 varint of at most 9 bytes, as multiformats allows.
+fn read_varint(bytes: &mut &[u8]) -> Result<u64, AuctionError> {
+    let mut value = 0;
+    for (index, byte) in bytes.iter().take(9).enumerate() {
+        value |= u64::from(byte & 0x7f) << (7 * index);
+        if byte & 0x80 == 0 {
+            *bytes = &bytes[index + 1..];
+            return Ok(value);
+        }
+    }
+    Err(AuctionError::InvalidCid)
+}
+
+fn base32_encode(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(5) * 8);
+    let (mut buffer, mut bits) = (0u16, 0);
+    for byte in bytes {
+        buffer = (buffer << 8) | u16::from(*byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            encoded.push(BASE32_ALPHABET[usize::from((buffer >> bits) & 0x1f)] as char);
+        }
+    }
+    if bits > 0 {
+        encoded.push(BASE32_ALPHABET[usize::from((buffer << (5 - bits)) & 0x1f)] as char);
+    }
+    encoded
+}
+
+fn base32_decode(encoded: &str) -> Result<Vec<u8>, AuctionError> {
+    let mut bytes = Vec::with_capacity(encoded.len() * 5 / 8);
+    let (mut buffer, mut bits) = (0u16, 0);
+    for char in encoded.bytes() {
+        let value = BASE32_ALPHABET
+            .iter()
+            .position(|candidate| *candidate == char)
+            .ok_or(AuctionError::InvalidCid)?;
+        buffer = (buffer << 5) | value as u16;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+    // Unpadded base32 leaves fewer than 5 zero bits.
+    if bits >= 5 || buffer & ((1 << bits) - 1) != 0 {
+        return Err(AuctionError::InvalidCid);
+    }
+    Ok(bytes)
+}
+
+fn base16_decode(encoded: &str) -> Result<Vec<u8>, AuctionError> {
+    let digit = |char: &u8| {
+        BASE16_ALPHABET
+            .iter()
+            .position(|candidate| candidate == char)
+            .ok_or(AuctionError::InvalidCid)
+    };
+    let encoded = encoded.as_bytes();
+    if !encoded.len().is_multiple_of(2) {
+        return Err(AuctionError::InvalidCid);
+    }
+    encoded
+        .chunks(2)
+        .map(|pair| Ok((digit(&pair[0])? << 4 | digit(&pair[1])?) as u8))
+        .collect()
+}
@@ -15,6 +15,13 @@ pub const CONFIG_SEED: &[u8] = b"global_config";
 pub const CONFIG_POLICY_V2_SEED: &[u8] = b"policy_v2";
 pub const BUNDLE_ESCROW_V2_SEED: &[u8] = b"bundle_escrow_v2";
 pub const BUNDLE_VERIFIER_PAGE_V2_SEED: &[u8] = b"bundle_verifier_page_v2";
+pub const BUNDLE_REFUND_PAGE_V2_SEED: &[u8] = b"bundle_refund_page_v2";
+pub const NODE_PENALTY_RECORD_SEED: &[u8] = b"node_penalty_record";
+pub const NODE_REPUTATION_V2_SEED: &[u8] = b"node_reputation_v2";
+pub const NODE_REGISTRATION_SEED: &[u8] = b"node_registration";
+pub const TIER_REGISTRY_V2_SEED: &[u8] = b"tier_registry_v2";
+pub const CONFIG_POLICY_V2_PROPOSAL_SEED: &[u8] = b"policy_v2_proposal";
+pub const CONFIG_POLICY_V2_SCHEDULE_SEED: &[u8] = b"policy_v2_schedule";
 /// The minimum number of bundle-auction pairs
 /// Eg. if set to 2 means two bundle-auction pairs have to be submitted ie 4 accounts
 #[cfg(not(feature = "global-config"))]
@@ -27,3 +34,6 @@ pub const ID: [u8; PUBKEY_BYTES] =
 pub const MAX_VERIFIERS_PER_AUCTION: usize = 3;
 /// Compatibility alias for one release cycle. Prefer `MAX_VERIFIERS_PER_AUCTION`.
 pub const VERIFIERS_PER_AUCTION: usize = MAX_VERIFIERS_PER_AUCTION;
+/// Verifier capacity of V6 `BundleEscrowV2` and `BundleVerifierPageV2` accounts. Legacy
+/// auctions, job requests and earlier V2 layouts stay at `MAX_VERIFIERS_PER_AUCTION`.
+pub const MAX_EXTENDED_VERIFIERS_PER_AUCTION: usize = 16;
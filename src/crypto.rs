@@ -0,0 +1,148 @@
+//! Key agreement and IV checks for the 32-byte commitments stored next to a 16-byte IV:
+//! `input_hash` in `JobRequest`, and `output_hash` and `merkle_root` in `VerificationState`
+//! and `SubmitJobOutputArgs`. An all-zero IV marks a plaintext commitment.
+//!
+//! Both sides compute an X25519 shared secret from their own secret key and the peer's public
+//! key, for example the ambient key and the client key.
+//!
+//! The `unverified-commitment-cipher` feature adds [`CommitmentKey`] and the commitment
+//! encryption helpers. Their key derivation (HKDF-SHA256 with no salt and the info string
+//! below) and cipher (AES-256-CTR with a big-endian 128-bit counter) are a draft: the node
+//! parameters have not been confirmed and no vectors produced by node code exist yet. The
+//! vectors in `tests/crypto.rs` were only cross-checked against a Python implementation of
+//! the same draft steps, so ciphertexts from these helpers must not be assumed to match what
+//! the nodes produce.
+//!
+//! `output_hash` and `merkle_root` are encrypted under the same key. Each 32-byte commitment
+//! takes the two counter blocks `iv` and `iv + 1`, and CTR mode leaks the XOR of plaintexts
+//! encrypted under a shared counter block, so the two IVs must be at least 2 apart; see
+//! [`check_commitment_ivs`].
+
+use crate::error::AuctionError;
+use crate::is_encrypted;
+#[cfg(feature = "unverified-commitment-cipher")]
+use crate::JobRequest;
+#[cfg(feature = "unverified-commitment-cipher")]
+use aes::cipher::{KeyIvInit, StreamCipher};
+#[cfg(feature = "unverified-commitment-cipher")]
+use hkdf::Hkdf;
+#[cfg(feature = "unverified-commitment-cipher")]
+use sha2::Sha256;
+
+#[cfg(feature = "unverified-commitment-cipher")]
+type Aes256Ctr = ctr::Ctr128BE<aes::Aes256>;
+
+#[cfg(feature = "unverified-commitment-cipher")]
+const COMMITMENT_KEY_INFO: &[u8] = b"ambient.commitment.aes256ctr.v1";
+
+/// The X25519 public key of `secret_key`.
+pub fn x25519_public_key(secret_key: &[u8; 32]) -> [u8; 32] {
+    x25519_dalek::x25519(*secret_key, x25519_dalek::X25519_BASEPOINT_BYTES)
+}
+
+/// The symmetric key shared by the holders of two X25519 key pairs, derived with the draft
+/// parameters described in the module docs.
+#[cfg(feature = "unverified-commitment-cipher")]
+#[derive(Clone, PartialEq, Eq)]
+pub struct CommitmentKey([u8; 32]);
+
+#[cfg(feature = "unverified-commitment-cipher")]
+impl CommitmentKey {
+    /// Agrees on a key with the holder of `peer_public_key`. Fails with `InvalidEncryptionKey`
+    /// for a low-order public key, which would make the shared secret all zeroes.
+    pub fn derive(secret_key: &[u8; 32], peer_public_key: &[u8; 32]) -> Result<Self, AuctionError> {
+        let shared_secret = x25519_dalek::x25519(*secret_key, *peer_public_key);
+        if shared_secret == [0; 32] {
+            return Err(AuctionError::InvalidEncryptionKey);
+        }
+        Ok(Self::from_shared_secret(&shared_secret))
+    }
+
+    /// Expands an X25519 shared secret computed elsewhere.
+    pub fn from_shared_secret(shared_secret: &[u8; 32]) -> Self {
+        let mut key = [0; 32];
+        Hkdf::<Sha256>::new(None, shared_secret)
+            .expand(COMMITMENT_KEY_INFO, &mut key)
+            .expect("32 bytes is a valid HKDF-SHA256 output length");
+        Self(key)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    fn apply_keystream(&self, iv: &[u8; 16], commitment: &[u8; 32]) -> [u8; 32] {
+        let mut output = *commitment;
+        Aes256Ctr::new(&self.0.into(), iv.into()).apply_keystream(&mut output);
+        output
+    }
+}
+
+#[cfg(feature = "unverified-commitment-cipher")]
+impl std::fmt::Debug for CommitmentKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("CommitmentKey(..)")
+    }
+}
+
+/// Encrypts `commitment` under `iv`. Fails with `InvalidEncryptionIv` for an all-zero IV,
+/// which would mark the result as plaintext.
+#[cfg(feature = "unverified-commitment-cipher")]
+pub fn encrypt_commitment(
+    key: &CommitmentKey,
+    iv: &[u8; 16],
+    commitment: &[u8; 32],
+) -> Result<[u8; 32], AuctionError> {
+    if !is_encrypted(iv) {
+        return Err(AuctionError::InvalidEncryptionIv);
+    }
+    Ok(key.apply_keystream(iv, commitment))
+}
+
+/// Checks the IVs of an `output_hash` and a `merkle_root` encrypted under the same key. Fails
+/// with `InvalidEncryptionIv` if both are encrypted and, read as big-endian counters, less
+/// than 2 apart in either direction, wrapping around `u128::MAX`, so that their counter blocks
+/// overlap.
+pub fn check_commitment_ivs(
+    output_hash_iv: &[u8; 16],
+    merkle_root_iv: &[u8; 16],
+) -> Result<(), AuctionError> {
+    if !is_encrypted(output_hash_iv) || !is_encrypted(merkle_root_iv) {
+        return Ok(());
+    }
+    let output_hash_counter = u128::from_be_bytes(*output_hash_iv);
+    let merkle_root_counter = u128::from_be_bytes(*merkle_root_iv);
+    let distance = output_hash_counter
+        .wrapping_sub(merkle_root_counter)
+        .min(merkle_root_counter.wrapping_sub(output_hash_counter));
+    if distance < 2 {
+        return Err(AuctionError::InvalidEncryptionIv);
+    }
+    Ok(())
+}
+
+/// Decrypts a stored commitment. A commitment stored with an all-zero IV is plaintext and
+/// is returned unchanged.
+#[cfg(feature = "unverified-commitment-cipher")]
+pub fn decrypt_commitment(key: &CommitmentKey, iv: &[u8; 16], stored: &[u8; 32]) -> [u8; 32] {
+    if !is_encrypted(iv) {
+        return *stored;
+    }
+    key.apply_keystream(iv, stored)
+}
+
+/// `job_request` with its `input_hash` decrypted and its IV cleared, ready for
+/// [`verify_job_input`](crate::verify_job_input).
+#[cfg(feature = "unverified-commitment-cipher")]
+pub fn decrypt_job_input_hash(key: &CommitmentKey, job_request: &JobRequest) -> JobRequest {
+    JobRequest {
+        input_hash: decrypt_commitment(
+            key,
+            &job_request.input_hash_iv,
+            &job_request.input_hash.inner(),
+        )
+        .into(),
+        input_hash_iv: [0; 16],
+        ..*job_request
+    }
+}
@@ -0,0 +1,716 @@
+//! Client-side helpers for filling data accounts through `AppendData`.
+//!
+//! A data account starts with its [`Metadata`] and holds the payload right after it, so
+//! payload byte `i` lives at account offset `Metadata::LEN + i`.
+//!
+//! A payload too large for one account is split over V1 data accounts, filled through
+//! `AppendDataV1`. Each starts with a [`MetadataV1`], whose header marks the layout and whose
+//! `next_data_account` links to the account holding the next part of the payload. Only the
+//! head account records `decompressed_len`, which covers the whole payload.
+//!
+//! A payload with `decompressed_len` set is a zstd frame. Compressing and decompressing it
+//! needs the `compression` feature.
+//!
+//! The content hash of a job input is the sha256 of its decompressed payload, the digest a
+//! raw-codec CIDv1 carries, and is what `JobRequest::input_hash` commits to.
+
+use crate::error::AuctionError;
+use crate::{
+    metadata_payload_offset, parse_metadata_layout, AccountLayoutVersion, AppendDataArgs,
+    AppendDataV1Args, JobRequest, Metadata, MetadataV1, ParsedAccountLayout, Pubkey, PUBKEY_BYTES,
+};
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+use std::num::NonZeroU64;
+
+const ZSTD_FRAME_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+const LZ4_FRAME_MAGIC: [u8; 4] = [0x04, 0x22, 0x4d, 0x18];
+
+/// zstd level used by [`DataAccountWriter::compressed`].
+#[cfg(feature = "compression")]
+pub const DATA_ACCOUNT_ZSTD_LEVEL: i32 = 19;
+
+fn padded_seed(seed: &[u8]) -> Result<[u8; PUBKEY_BYTES], AuctionError> {
+    let mut padded = [0; PUBKEY_BYTES];
+    padded
+        .get_mut(..seed.len())
+        .ok_or(AuctionError::InvalidDataUpload)?
+        .copy_from_slice(seed);
+    Ok(padded)
+}
+
+fn data_account_metadata(
+    authority: Pubkey,
+    seed: &[u8],
+    decompressed_len: Option<NonZeroU64>,
+    payload_len: usize,
+) -> Result<Metadata, AuctionError> {
+    Ok(Metadata {
+        authority,
+        decompressed_len,
+        job_request_key: None.into(),
+        seed: padded_seed(seed)?,
+        seed_len: seed.len() as u64,
+        payload_len: payload_len as u64,
+    })
+}
+
+/// Largest part of a linked payload one account can hold under the 10 MiB account size limit.
+pub const MAX_DATA_ACCOUNT_SEGMENT_LEN: usize = 10 * 1024 * 1024 - MetadataV1::LEN;
+
+/// Largest `AppendData` chunk that fits a 1232-byte transaction signed by the data authority
+/// alone, next to the instruction tag, [`AppendDataArgs`] and a compute budget instruction.
+pub const APPEND_DATA_MAX_CHUNK_LEN: usize = 880;
+
+/// [`APPEND_DATA_MAX_CHUNK_LEN`] for `AppendDataV1`, less the `next_data_account` its
+/// [`AppendDataV1Args`] add.
+pub const APPEND_DATA_V1_MAX_CHUNK_LEN: usize = APPEND_DATA_MAX_CHUNK_LEN - PUBKEY_BYTES;
+
+/// The `AppendData` instructions that upload one payload, in order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UploadPlan<'a> {
+    payload: &'a [u8],
+    seed: [u8; PUBKEY_BYTES],
+    seed_len: u64,
+    decompressed_data_length: Option<NonZeroU64>,
+    max_chunk_len: usize,
+    version: AccountLayoutVersion,
+    next_data_account: Option<Pubkey>,
+}
+
+impl<'a> UploadPlan<'a> {
+    /// Plans the upload of `payload` into the account created with `seed`, in chunks of
+    /// [`APPEND_DATA_MAX_CHUNK_LEN`]. `compression` is the decompressed length of a compressed
+    /// `payload`, or `None` for a plain one.
+    pub fn new(
+        payload: &'a [u8],
+        seed: &[u8],
+        compression: Option<NonZeroU64>,
+    ) -> Result<Self, AuctionError> {
+        Self::new_with_chunk_len(payload, seed, compression, APPEND_DATA_MAX_CHUNK_LEN)
+    }
+
+    /// [`Self::new`] with a custom chunk length, for transactions that carry more accounts or
+    /// instructions.
+    pub fn new_with_chunk_len(
+        payload: &'a [u8],
+        seed: &[u8],
+        compression: Option<NonZeroU64>,
+        max_chunk_len: usize,
+    ) -> Result<Self, AuctionError> {
+        if payload.is_empty() || max_chunk_len == 0 {
+            return Err(AuctionError::InvalidDataUpload);
+        }
+
+        Ok(Self {
+            payload,
+            seed: padded_seed(seed)?,
+            seed_len: seed.len() as u64,
+            decompressed_data_length: compression,
+            max_chunk_len,
+            version: AccountLayoutVersion::LegacyV0,
+            next_data_account: None,
+        })
+    }
+
+    fn payload_offset(&self) -> usize {
+        match self.version {
+            AccountLayoutVersion::LegacyV0 => Metadata::LEN,
+            _ => MetadataV1::LEN,
+        }
+    }
+
+    /// Size to create the data account with: the metadata followed by the whole payload.
+    pub fn data_account_len(&self) -> usize {
+        self.payload_offset() + self.payload.len()
+    }
+
+    /// Every instruction of the upload, starting from an empty account.
+    pub fn instructions(&self) -> Vec<(AppendDataArgs, &'a [u8])> {
+        self.instructions_from(0)
+    }
+
+    /// The instructions still missing from a partially filled account, read from its current
+    /// `account_data`.
+    ///
+    /// Fails with `InvalidMetadata` if the account belongs to another upload: a different
+    /// layout, seed, compression or link, or stored bytes that differ from the payload.
+    pub fn resume(
+        &self,
+        account_data: &[u8],
+    ) -> Result<Vec<(AppendDataArgs, &'a [u8])>, AuctionError> {
+        let reader = DataAccountReader::from_account_data(account_data)?;
+        let metadata = reader.metadata();
+        let uploaded = usize::try_from(metadata.payload_len)
+            .ok()
+            .filter(|uploaded| *uploaded <= self.payload.len())
+            .ok_or(AuctionError::InvalidMetadata)?;
+        if uploaded != 0
+            && (reader.layout().version != self.version
+                || metadata.seed != self.seed
+                || metadata.seed_len != self.seed_len
+                || metadata.decompressed_len != self.decompressed_data_length
+                || reader.next_data_account() != self.next_data_account
+                || reader.stored_payload() != &self.payload[..uploaded])
+        {
+            return Err(AuctionError::InvalidMetadata);
+        }
+        Ok(self.instructions_from(uploaded))
+    }
+
+    fn instructions_from(&self, uploaded: usize) -> Vec<(AppendDataArgs, &'a [u8])> {
+        let payload_offset = self.payload_offset();
+        self.payload[uploaded..]
+            .chunks(self.max_chunk_len)
+            .scan(uploaded, move |position, chunk| {
+                let offset = payload_offset + *position;
+                *position += chunk.len();
+                Some((
+                    AppendDataArgs {
+                        offset: offset as u64,
+                        seed: self.seed,
+                        seed_len: self.seed_len,
+                        decompressed_data_length: self.decompressed_data_length,
+                    },
+                    chunk,
+                ))
+            })
+            .collect()
+    }
+}
+
+/// The `AppendDataV1` instructions that upload one account of a linked payload, in order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LinkedUploadPlan<'a> {
+    plan: UploadPlan<'a>,
+}
+
+impl<'a> LinkedUploadPlan<'a> {
+    /// Size to create the data account with: the [`MetadataV1`] followed by its part of the
+    /// payload.
+    pub fn data_account_len(&self) -> usize {
+        self.plan.data_account_len()
+    }
+
+    pub fn next_data_account(&self) -> Option<Pubkey> {
+        self.plan.next_data_account
+    }
+
+    /// Every instruction of the upload, starting from an empty account.
+    pub fn instructions(&self) -> Vec<(AppendDataV1Args, &'a [u8])> {
+        self.with_link(self.plan.instructions())
+    }
+
+    /// [`UploadPlan::resume`] for a V1 account, which must also link to the same account.
+    pub fn resume(
+        &self,
+        account_data: &[u8],
+    ) -> Result<Vec<(AppendDataV1Args, &'a [u8])>, AuctionError> {
+        Ok(self.with_link(self.plan.resume(account_data)?))
+    }
+
+    fn with_link(
+        &self,
+        instructions: Vec<(AppendDataArgs, &'a [u8])>,
+    ) -> Vec<(AppendDataV1Args, &'a [u8])> {
+        instructions
+            .into_iter()
+            .map(|(args, chunk)| {
+                (
+                    AppendDataV1Args::new(&args, self.plan.next_data_account),
+                    chunk,
+                )
+            })
+            .collect()
+    }
+}
+
+/// A parsed data account: its metadata and the payload bytes it holds.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DataAccountReader<'a> {
+    layout: ParsedAccountLayout,
+    metadata: Metadata,
+    next_data_account: Option<Pubkey>,
+    stored_payload: &'a [u8],
+}
+
+impl<'a> DataAccountReader<'a> {
+    /// Parses `account_data` of the account created with `seed`. Fails with `InvalidMetadata`
+    /// if the stored seed differs or `payload_len` runs past the account.
+    pub fn new(account_data: &'a [u8], seed: &[u8]) -> Result<Self, AuctionError> {
+        let reader = Self::from_account_data(account_data)?;
+        let seed_len = usize::try_from(reader.metadata.seed_len)
+            .ok()
+            .filter(|seed_len| *seed_len <= PUBKEY_BYTES)
+            .ok_or(AuctionError::InvalidMetadata)?;
+        if reader.metadata.seed[..seed_len] != *seed {
+            return Err(AuctionError::InvalidMetadata);
+        }
+        Ok(reader)
+    }
+
+    /// [`Self::new`] without the seed check, for accounts already looked up by address.
+    pub fn from_account_data(account_data: &'a [u8]) -> Result<Self, AuctionError> {
+        let layout = parse_metadata_layout(account_data).ok_or(AuctionError::InvalidMetadata)?;
+        let payload_offset =
+            metadata_payload_offset(layout.version).ok_or(AuctionError::InvalidMetadata)?;
+        let (metadata, next_data_account) = if layout.is_legacy() {
+            let metadata: Metadata = bytemuck::pod_read_unaligned(&account_data[..payload_offset]);
+            (metadata, None)
+        } else {
+            let metadata: MetadataV1 =
+                bytemuck::pod_read_unaligned(&account_data[..payload_offset]);
+            (metadata.metadata(), metadata.next_data_account.get())
+        };
+        let stored_payload = usize::try_from(metadata.payload_len)
+            .ok()
+            .and_then(|payload_len| payload_len.checked_add(payload_offset))
+            .and_then(|payload_end| account_data.get(payload_offset..payload_end))
+            .ok_or(AuctionError::InvalidMetadata)?;
+
+        Ok(Self {
+            layout,
+            metadata,
+            next_data_account,
+            stored_payload,
+        })
+    }
+
+    pub fn layout(&self) -> ParsedAccountLayout {
+        self.layout
+    }
+
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    /// The account holding the rest of the payload, if this one is not the last.
+    pub fn next_data_account(&self) -> Option<Pubkey> {
+        self.next_data_account
+    }
+
+    pub fn is_compressed(&self) -> bool {
+        self.metadata.decompressed_len.is_some()
+    }
+
+    /// The payload bytes stored in this account, compressed or not.
+    pub fn stored_payload(&self) -> &'a [u8] {
+        self.stored_payload
+    }
+
+    /// The payload, decompressed if `decompressed_len` is set.
+    ///
+    /// Fails with `InvalidCompressedPayload` if the decompressed length differs from
+    /// `decompressed_len`, and with `CompressionNotSupported` for an lz4 frame or a build
+    /// without the `compression` feature. A linked account only holds part of the payload, so
+    /// it fails with `InvalidDataAccountChain`; read it with [`DataAccountChainReader`].
+    pub fn payload(&self) -> Result<Cow<'a, [u8]>, AuctionError> {
+        if self.next_data_account.is_some() {
+            return Err(AuctionError::InvalidDataAccountChain);
+        }
+        decode_payload(
+            Cow::Borrowed(self.stored_payload),
+            self.metadata.decompressed_len,
+        )
+    }
+}
+
+fn decode_payload(
+    stored_payload: Cow<'_, [u8]>,
+    decompressed_len: Option<NonZeroU64>,
+) -> Result<Cow<'_, [u8]>, AuctionError> {
+    let Some(decompressed_len) = decompressed_len else {
+        return Ok(stored_payload);
+    };
+    match stored_payload.get(..4) {
+        Some(magic) if magic == ZSTD_FRAME_MAGIC => {
+            decompress_zstd(&stored_payload, decompressed_len).map(Cow::Owned)
+        }
+        Some(magic) if magic == LZ4_FRAME_MAGIC => Err(AuctionError::CompressionNotSupported),
+        _ => Err(AuctionError::InvalidCompressedPayload),
+    }
+}
+
+/// Reads a payload split over linked data accounts, one account at a time from the head.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataAccountChainReader {
+    head_metadata: Metadata,
+    accounts: Vec<Pubkey>,
+    next_data_account: Option<Pubkey>,
+    stored_payload: Vec<u8>,
+}
+
+impl DataAccountChainReader {
+    /// Starts from the head account at `head`, usually a job request's `input_data_account`.
+    pub fn new(head: Pubkey, head_data: &[u8]) -> Result<Self, AuctionError> {
+        let reader = DataAccountReader::from_account_data(head_data)?;
+        let mut chain = Self {
+            head_metadata: reader.metadata,
+            accounts: vec![head],
+            next_data_account: None,
+            stored_payload: Vec::new(),
+        };
+        chain.append(&reader)?;
+        Ok(chain)
+    }
+
+    /// The account to read next, `None` once the chain is complete.
+    pub fn next_data_account(&self) -> Option<Pubkey> {
+        self.next_data_account
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.next_data_account.is_none()
+    }
+
+    /// The accounts read so far, head first.
+    pub fn accounts(&self) -> &[Pubkey] {
+        &self.accounts
+    }
+
+    pub fn head_metadata(&self) -> &Metadata {
+        &self.head_metadata
+    }
+
+    pub fn authority(&self) -> Pubkey {
+        self.head_metadata.authority
+    }
+
+    /// Appends the account at `key`, which must be [`Self::next_data_account`], and returns the
+    /// account after it.
+    ///
+    /// Fails with `DataAccountAuthorityMismatch` if its authority differs from the head's, with
+    /// `DataAccountChainCycle` if it links back to an account already read, and with
+    /// `InvalidDataAccountChain` if it is not the expected account or records its own
+    /// `decompressed_len`.
+    pub fn push(
+        &mut self,
+        key: Pubkey,
+        account_data: &[u8],
+    ) -> Result<Option<Pubkey>, AuctionError> {
+        if self.next_data_account != Some(key) {
+            return Err(AuctionError::InvalidDataAccountChain);
+        }
+        let reader = DataAccountReader::from_account_data(account_data)?;
+        if reader.metadata.authority != self.head_metadata.authority {
+            return Err(AuctionError::DataAccountAuthorityMismatch);
+        }
+        if reader.metadata.decompressed_len.is_some() {
+            return Err(AuctionError::InvalidDataAccountChain);
+        }
+        self.accounts.push(key);
+        self.append(&reader)?;
+        Ok(self.next_data_account)
+    }
+
+    /// The bytes stored across the accounts read so far.
+    pub fn stored_payload(&self) -> &[u8] {
+        &self.stored_payload
+    }
+
+    /// The whole payload, decompressed like [`DataAccountReader::payload`]. Fails with
+    /// `InvalidDataAccountChain` until every account has been read.
+    pub fn payload(&self) -> Result<Cow<'_, [u8]>, AuctionError> {
+        if !self.is_complete() {
+            return Err(AuctionError::InvalidDataAccountChain);
+        }
+        decode_payload(
+            Cow::Borrowed(&self.stored_payload),
+            self.head_metadata.decompressed_len,
+        )
+    }
+
+    fn append(&mut self, reader: &DataAccountReader<'_>) -> Result<(), AuctionError> {
+        if let Some(next) = reader.next_data_account {
+            if self.accounts.contains(&next) {
+                return Err(AuctionError::DataAccountChainCycle);
+            }
+        }
+        self.stored_payload.extend_from_slice(reader.stored_payload);
+        self.next_data_account = reader.next_data_account;
+        Ok(())
+    }
+}
+
+/// Reads the whole payload linked from `head`, getting each account's data from `fetch`.
+/// Fails with `InvalidDataAccountChain` if `fetch` cannot find an account.
+pub fn read_data_account_chain<F, D>(head: Pubkey, fetch: F) -> Result<Vec<u8>, AuctionError>
+where
+    F: FnMut(&Pubkey) -> Option<D>,
+    D: AsRef<[u8]>,
+{
+    read_complete_chain(head, fetch)?
+        .payload()
+        .map(Cow::into_owned)
+}
+
+fn read_complete_chain<F, D>(
+    head: Pubkey,
+    mut fetch: F,
+) -> Result<DataAccountChainReader, AuctionError>
+where
+    F: FnMut(&Pubkey) -> Option<D>,
+    D: AsRef<[u8]>,
+{
+    let head_data = fetch(&head).ok_or(AuctionError::InvalidDataAccountChain)?;
+    let mut chain = DataAccountChainReader::new(head, head_data.as_ref())?;
+    while let Some(next) = chain.next_data_account() {
+        let account_data = fetch(&next).ok_or(AuctionError::InvalidDataAccountChain)?;
+        chain.push(next, account_data.as_ref())?;
+    }
+    Ok(chain)
+}
+
+/// The content hash of a decompressed data account payload.
+pub fn data_account_content_hash(payload: &[u8]) -> [u8; 32] {
+    Sha256::digest(payload).into()
+}
+
+/// Checks that the data account in `data_account_bytes` holds the input of the job request
+/// at `job_request_key`: its `job_request_key` points back at the request, its authority is
+/// the requester, and the content hash of its payload is the request's `input_hash`.
+///
+/// Fails with `InvalidJobInputAccount` for an account of another request or requester, with
+/// `JobInputHashMismatch` for a tampered payload, and with `JobInputHashEncrypted` if the
+/// request's `input_hash` is encrypted and has to be decrypted first.
+pub fn verify_job_input(
+    job_request_key: Pubkey,
+    job_request: &JobRequest,
+    data_account_bytes: &[u8],
+) -> Result<(), AuctionError> {
+    let reader = DataAccountReader::from_account_data(data_account_bytes)?;
+    check_job_input(
+        job_request_key,
+        job_request,
+        reader.metadata(),
+        &reader.payload()?,
+    )
+}
+
+/// [`verify_job_input`] for an input read from the job request's `input_data_account` and
+/// the accounts linked from it, getting each account's data from `fetch`.
+pub fn verify_job_input_chain<F, D>(
+    job_request_key: Pubkey,
+    job_request: &JobRequest,
+    fetch: F,
+) -> Result<(), AuctionError>
+where
+    F: FnMut(&Pubkey) -> Option<D>,
+    D: AsRef<[u8]>,
+{
+    let head = job_request
+        .input_data_account
+        .get()
+        .ok_or(AuctionError::InvalidJobInputAccount)?;
+    let chain = read_complete_chain(head, fetch)?;
+    check_job_input(
+        job_request_key,
+        job_request,
+        chain.head_metadata(),
+        &chain.payload()?,
+    )
+}
+
+fn check_job_input(
+    job_request_key: Pubkey,
+    job_request: &JobRequest,
+    metadata: &Metadata,
+    payload: &[u8],
+) -> Result<(), AuctionError> {
+    if job_request.is_input_hash_encrypted() {
+        return Err(AuctionError::JobInputHashEncrypted);
+    }
+    if metadata.job_request_key.get() != Some(job_request_key)
+        || metadata.authority != job_request.authority
+    {
+        return Err(AuctionError::InvalidJobInputAccount);
+    }
+    if job_request.input_hash != data_account_content_hash(payload) {
+        return Err(AuctionError::JobInputHashMismatch);
+    }
+    Ok(())
+}
+
+#[cfg(feature = "compression")]
+fn decompress_zstd(frame: &[u8], decompressed_len: NonZeroU64) -> Result<Vec<u8>, AuctionError> {
+    let expected_len = usize::try_from(decompressed_len.get())
+        .map_err(|_| AuctionError::InvalidCompressedPayload)?;
+    // One spare byte so an oversized frame fails the length check instead of the decoder.
+    let payload = zstd::bulk::decompress(frame, expected_len + 1)
+        .map_err(|_| AuctionError::InvalidCompressedPayload)?;
+    if payload.len() != expected_len {
+        return Err(AuctionError::InvalidCompressedPayload);
+    }
+    Ok(payload)
+}
+
+#[cfg(not(feature = "compression"))]
+fn decompress_zstd(_frame: &[u8], _decompressed_len: NonZeroU64) -> Result<Vec<u8>, AuctionError> {
+    Err(AuctionError::CompressionNotSupported)
+}
+
+/// A payload ready to be stored in a data account, with the `Metadata` and `AppendData`
+/// fields that describe it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DataAccountWriter {
+    stored_payload: Vec<u8>,
+    decompressed_len: Option<NonZeroU64>,
+}
+
+impl DataAccountWriter {
+    /// Stores `payload` as is.
+    pub fn uncompressed(payload: &[u8]) -> Self {
+        Self {
+            stored_payload: payload.to_vec(),
+            decompressed_len: None,
+        }
+    }
+
+    /// Stores `payload` as a zstd frame. Fails with `InvalidDataUpload` for an empty payload,
+    /// which has no decompressed length to record.
+    #[cfg(feature = "compression")]
+    pub fn compressed(payload: &[u8]) -> Result<Self, AuctionError> {
+        let decompressed_len =
+            NonZeroU64::new(payload.len() as u64).ok_or(AuctionError::InvalidDataUpload)?;
+        let stored_payload = zstd::bulk::compress(payload, DATA_ACCOUNT_ZSTD_LEVEL)
+            .map_err(|_| AuctionError::InvalidDataUpload)?;
+        Ok(Self {
+            stored_payload,
+            decompressed_len: Some(decompressed_len),
+        })
+    }
+
+    pub fn stored_payload(&self) -> &[u8] {
+        &self.stored_payload
+    }
+
+    pub fn decompressed_len(&self) -> Option<NonZeroU64> {
+        self.decompressed_len
+    }
+
+    /// The `AppendData` instructions that upload the stored payload.
+    pub fn upload_plan(&self, seed: &[u8]) -> Result<UploadPlan<'_>, AuctionError> {
+        UploadPlan::new(&self.stored_payload, seed, self.decompressed_len)
+    }
+
+    /// The metadata of a data account holding the whole payload.
+    pub fn metadata(&self, authority: Pubkey, seed: &[u8]) -> Result<Metadata, AuctionError> {
+        data_account_metadata(
+            authority,
+            seed,
+            self.decompressed_len,
+            self.stored_payload.len(),
+        )
+    }
+
+    /// The bytes of a data account holding the whole payload.
+    pub fn account_data(&self, authority: Pubkey, seed: &[u8]) -> Result<Vec<u8>, AuctionError> {
+        let metadata = self.metadata(authority, seed)?;
+        Ok([bytemuck::bytes_of(&metadata), &self.stored_payload].concat())
+    }
+
+    /// Number of linked accounts needed to hold the stored payload in parts of at most
+    /// `max_segment_len` bytes.
+    pub fn linked_account_count(&self, max_segment_len: usize) -> usize {
+        self.stored_payload.len().div_ceil(max_segment_len.max(1))
+    }
+
+    /// Splits the stored payload over the `(address, seed)` accounts, head first, in parts of
+    /// at most `max_segment_len` bytes.
+    ///
+    /// Fails with `InvalidDataUpload` unless there are exactly
+    /// [`Self::linked_account_count`] accounts, and with `DataAccountChainCycle` if an address
+    /// repeats.
+    pub fn linked_accounts(
+        &self,
+        accounts: &[(Pubkey, &[u8])],
+        max_segment_len: usize,
+    ) -> Result<Vec<LinkedDataAccount>, AuctionError> {
+        if max_segment_len == 0
+            || max_segment_len > MAX_DATA_ACCOUNT_SEGMENT_LEN
+            || accounts.len() != self.linked_account_count(max_segment_len)
+        {
+            return Err(AuctionError::InvalidDataUpload);
+        }
+        for (index, (key, _)) in accounts.iter().enumerate() {
+            if accounts[..index].iter().any(|(other, _)| other == key) {
+                return Err(AuctionError::DataAccountChainCycle);
+            }
+        }
+
+        accounts
+            .iter()
+            .zip(self.stored_payload.chunks(max_segment_len))
+            .enumerate()
+            .map(|(index, ((key, seed), segment))| {
+                Ok(LinkedDataAccount {
+                    key: *key,
+                    seed: padded_seed(seed)?,
+                    seed_len: seed.len(),
+                    decompressed_len: self.decompressed_len.filter(|_| index == 0),
+                    next_data_account: accounts.get(index + 1).map(|(next, _)| *next),
+                    segment: segment.to_vec(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// One V1 account of a linked payload: its [`MetadataV1`] fields and its part of the payload.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LinkedDataAccount {
+    key: Pubkey,
+    seed: [u8; PUBKEY_BYTES],
+    seed_len: usize,
+    decompressed_len: Option<NonZeroU64>,
+    next_data_account: Option<Pubkey>,
+    segment: Vec<u8>,
+}
+
+impl LinkedDataAccount {
+    pub fn key(&self) -> Pubkey {
+        self.key
+    }
+
+    pub fn seed(&self) -> &[u8] {
+        &self.seed[..self.seed_len]
+    }
+
+    pub fn next_data_account(&self) -> Option<Pubkey> {
+        self.next_data_account
+    }
+
+    /// This account's part of the payload.
+    pub fn segment(&self) -> &[u8] {
+        &self.segment
+    }
+
+    /// The `AppendDataV1` instructions that upload [`Self::segment`].
+    pub fn upload_plan(&self) -> Result<LinkedUploadPlan<'_>, AuctionError> {
+        let mut plan = UploadPlan::new_with_chunk_len(
+            &self.segment,
+            self.seed(),
+            self.decompressed_len,
+            APPEND_DATA_V1_MAX_CHUNK_LEN,
+        )?;
+        plan.version = AccountLayoutVersion::V1;
+        plan.next_data_account = self.next_data_account;
+        Ok(LinkedUploadPlan { plan })
+    }
+
+    pub fn metadata(&self, authority: Pubkey) -> Result<MetadataV1, AuctionError> {
+        let metadata = data_account_metadata(
+            authority,
+            self.seed(),
+            self.decompressed_len,
+            self.segment.len(),
+        )?;
+        Ok(MetadataV1::new(&metadata, self.next_data_account))
+    }
+
+    /// The bytes of the account once uploaded.
+    pub fn account_data(&self, authority: Pubkey) -> Result<Vec<u8>, AuctionError> {
+        let metadata = self.metadata(authority)?;
+        Ok([bytemuck::bytes_of(&metadata), &self.segment].concat())
+    }
+}
@@ -157,6 +157,113 @@ pub enum AuctionError {
     InvalidPostedResultV2 = 74,
     /// Invalid page-backed verification summary was provided
     InvalidVerifierPagesSummary = 75,
+    /// Invalid tier registry v2 account data was found
+    InvalidTierRegistryV2Data = 76,
+    /// The tier id is not configured in the tier registry
+    TierNotFound = 77,
+    /// The tier registry has no free slots left
+    TierRegistryFull = 78,
+    /// Invalid arguments were provided for a config policy v2 patch
+    InvalidConfigPolicyV2Patch = 79,
+    /// The default pubkey was given as a config policy authority
+    InvalidConfigPolicyAuthority = 80,
+    /// The authority already holds a slot of this kind
+    DuplicateConfigPolicyAuthority = 81,
+    /// All config policy authority slots of this kind are taken
+    ConfigPolicyAuthoritySlotsFull = 82,
+    /// The authority does not hold a slot of this kind
+    ConfigPolicyAuthorityNotFound = 83,
+    /// The change would leave the config policy without an admin authority
+    LastConfigPolicyAdmin = 84,
+    /// Invalid config policy v2 proposal account data was found
+    InvalidConfigPolicyV2ProposalData = 85,
+    /// The config policy v2 requires multiple admin approvals for this update
+    ConfigPolicyV2ProposalRequired = 86,
+    /// The admin approval threshold is zero or exceeds the number of admin authorities
+    InvalidAdminApprovalThreshold = 87,
+    /// The config policy v2 proposal was already executed or canceled
+    ConfigPolicyV2ProposalNotPending = 88,
+    /// The config policy v2 proposal has expired
+    ConfigPolicyV2ProposalExpired = 89,
+    /// The admin already approved the config policy v2 proposal
+    ConfigPolicyV2ProposalAlreadyApproved = 90,
+    /// The config policy v2 proposal does not have enough admin approvals
+    ConfigPolicyV2ProposalThresholdNotMet = 91,
+    /// Invalid config policy v2 schedule account data was found
+    InvalidConfigPolicyV2ScheduleData = 92,
+    /// The config policy v2 schedule has no free slots left
+    ConfigPolicyV2ScheduleFull = 93,
+    /// The activation slot is sooner than the minimum activation delay allows
+    ActivationSlotTooEarly = 94,
+    /// No scheduled config policy v2 patch has the given schedule id
+    ScheduledConfigPolicyV2PatchNotFound = 95,
+    /// The instruction belongs to a paused instruction group
+    ProtocolPaused = 96,
+    /// The protocol fee exceeds 10_000 bps or charges a fee without a recipient
+    InvalidProtocolFee = 97,
+    /// The protocol fee recipient account does not match the config policy
+    InvalidProtocolFeeRecipient = 98,
+    /// The token program, vault or token accounts are missing or do not match the escrow
+    InvalidTokenEscrowAccounts = 99,
+    /// Bundle refund page v2 input is invalid
+    InvalidRefundPageV2Input = 100,
+    /// The requester refund was already claimed
+    RequesterRefundAlreadyClaimed = 101,
+    /// Runner-ups are unranked, undercut the winner or exceed the escrow
+    InvalidRunnerUpsV2 = 102,
+    /// The escrow has no runner-up left to promote
+    NoRunnerUpAvailable = 103,
+    /// Node penalty exceeds 10 000 bps
+    InvalidNodePenalty = 104,
+    /// The node can not be penalised for this escrow
+    NodeNotPenalizable = 105,
+    /// The node was already penalised for this escrow
+    NodePenaltyAlreadyApplied = 106,
+    /// The node penalty record belongs to another node or mint
+    InvalidNodePenaltyRecord = 107,
+    /// The node did not win or verify the settled escrow
+    InvalidNodeReputationV2 = 108,
+    /// Verifier candidates are duplicated or their stakes overflow
+    InvalidVerifierCandidates = 109,
+    /// The committed verifiers are not the ones the selection seed picks
+    VerifierSelectionMismatch = 110,
+    /// Verifier token ranges are malformed or exceed the posted output tokens
+    InvalidTokenRanges = 111,
+    /// The Merkle proof does not lead to the committed output root
+    InvalidMerkleProof = 112,
+    /// An all-zero IV marks a plaintext commitment and cannot encrypt one, and two commitments
+    /// under the same key cannot share an IV
+    InvalidEncryptionIv = 113,
+    /// The peer public key is a low-order point and yields no shared secret
+    InvalidEncryptionKey = 114,
+    /// The upload payload, data account seed or chunk length is invalid
+    InvalidDataUpload = 115,
+    /// The compressed payload is malformed or does not match `decompressed_len`
+    InvalidCompressedPayload = 116,
+    /// The payload uses a codec this build cannot decompress
+    CompressionNotSupported = 117,
+    /// A data account links back to an account already in its chain
+    DataAccountChainCycle = 118,
+    /// A linked data account has a different authority than the head account
+    DataAccountAuthorityMismatch = 119,
+    /// Linked data accounts are missing, out of order or inconsistent
+    InvalidDataAccountChain = 120,
+    /// The input data account does not belong to the job request or its requester
+    InvalidJobInputAccount = 121,
+    /// The input data account payload does not match the job request's `input_hash`
+    JobInputHashMismatch = 122,
+    /// The job request's `input_hash` is encrypted and has to be decrypted first
+    JobInputHashEncrypted = 123,
+    /// The CID string is malformed or too long
+    InvalidCid = 124,
+    /// The CID uses a version, codec or hash function that cannot be stored as a sha2-256 digest
+    UnsupportedCid = 125,
+    /// Node registration endpoint, tiers or heartbeat slot is invalid
+    InvalidNodeRegistration = 126,
+    /// Config policy v2 patch changes values escrows in flight depend on and must be scheduled
+    ConfigPolicyV2PatchMustBeScheduled = 127,
+    /// A config policy schedule must delay activation by at least one slot
+    InvalidActivationDelay = 128,
 }
 
 impl Display for AuctionError {
@@ -248,6 +355,59 @@ impl AuctionError {
             Self::InvalidVerificationVerdict => "InvalidVerificationVerdict",
             Self::InvalidPostedResultV2 => "InvalidPostedResultV2",
             Self::InvalidVerifierPagesSummary => "InvalidVerifierPagesSummary",
+            Self::InvalidTierRegistryV2Data => "InvalidTierRegistryV2Data",
+            Self::TierNotFound => "TierNotFound",
+            Self::TierRegistryFull => "TierRegistryFull",
+            Self::InvalidConfigPolicyV2Patch => "InvalidConfigPolicyV2Patch",
+            Self::InvalidConfigPolicyAuthority => "InvalidConfigPolicyAuthority",
+            Self::DuplicateConfigPolicyAuthority => "DuplicateConfigPolicyAuthority",
+            Self::ConfigPolicyAuthoritySlotsFull => "ConfigPolicyAuthoritySlotsFull",
+            Self::ConfigPolicyAuthorityNotFound => "ConfigPolicyAuthorityNotFound",
+            Self::LastConfigPolicyAdmin => "LastConfigPolicyAdmin",
+            Self::InvalidConfigPolicyV2ProposalData => "InvalidConfigPolicyV2ProposalData",
+            Self::ConfigPolicyV2ProposalRequired => "ConfigPolicyV2ProposalRequired",
+            Self::InvalidAdminApprovalThreshold => "InvalidAdminApprovalThreshold",
+            Self::ConfigPolicyV2ProposalNotPending => "ConfigPolicyV2ProposalNotPending",
+            Self::ConfigPolicyV2ProposalExpired => "ConfigPolicyV2ProposalExpired",
+            Self::ConfigPolicyV2ProposalAlreadyApproved => "ConfigPolicyV2ProposalAlreadyApproved",
+            Self::ConfigPolicyV2ProposalThresholdNotMet => "ConfigPolicyV2ProposalThresholdNotMet",
+            Self::InvalidConfigPolicyV2ScheduleData => "InvalidConfigPolicyV2ScheduleData",
+            Self::ConfigPolicyV2ScheduleFull => "ConfigPolicyV2ScheduleFull",
+            Self::ActivationSlotTooEarly => "ActivationSlotTooEarly",
+            Self::ScheduledConfigPolicyV2PatchNotFound => "ScheduledConfigPolicyV2PatchNotFound",
+            Self::ProtocolPaused => "ProtocolPaused",
+            Self::InvalidProtocolFee => "InvalidProtocolFee",
+            Self::InvalidProtocolFeeRecipient => "InvalidProtocolFeeRecipient",
+            Self::InvalidTokenEscrowAccounts => "InvalidTokenEscrowAccounts",
+            Self::InvalidRefundPageV2Input => "InvalidRefundPageV2Input",
+            Self::RequesterRefundAlreadyClaimed => "RequesterRefundAlreadyClaimed",
+            Self::InvalidRunnerUpsV2 => "InvalidRunnerUpsV2",
+            Self::NoRunnerUpAvailable => "NoRunnerUpAvailable",
+            Self::InvalidNodePenalty => "InvalidNodePenalty",
+            Self::NodeNotPenalizable => "NodeNotPenalizable",
+            Self::NodePenaltyAlreadyApplied => "NodePenaltyAlreadyApplied",
+            Self::InvalidNodePenaltyRecord => "InvalidNodePenaltyRecord",
+            Self::InvalidNodeReputationV2 => "InvalidNodeReputationV2",
+            Self::InvalidVerifierCandidates => "InvalidVerifierCandidates",
+            Self::VerifierSelectionMismatch => "VerifierSelectionMismatch",
+            Self::InvalidTokenRanges => "InvalidTokenRanges",
+            Self::InvalidMerkleProof => "InvalidMerkleProof",
+            Self::InvalidEncryptionIv => "InvalidEncryptionIv",
+            Self::InvalidEncryptionKey => "InvalidEncryptionKey",
+            Self::InvalidDataUpload => "InvalidDataUpload",
+            Self::InvalidCompressedPayload => "InvalidCompressedPayload",
+            Self::CompressionNotSupported => "CompressionNotSupported",
+            Self::DataAccountChainCycle => "DataAccountChainCycle",
+            Self::DataAccountAuthorityMismatch => "DataAccountAuthorityMismatch",
+            Self::InvalidDataAccountChain => "InvalidDataAccountChain",
+            Self::InvalidJobInputAccount => "InvalidJobInputAccount",
+            Self::JobInputHashMismatch => "JobInputHashMismatch",
+            Self::JobInputHashEncrypted => "JobInputHashEncrypted",
+            Self::InvalidCid => "InvalidCid",
+            Self::UnsupportedCid => "UnsupportedCid",
+            Self::InvalidNodeRegistration => "InvalidNodeRegistration",
+            Self::ConfigPolicyV2PatchMustBeScheduled => "ConfigPolicyV2PatchMustBeScheduled",
+            Self::InvalidActivationDelay => "InvalidActivationDelay",
         }
     }
 
@@ -345,6 +505,87 @@ impl AuctionError {
             Self::InvalidVerifierPagesSummary => {
                 "Page-backed verification summary is invalid"
             }
+            Self::InvalidTierRegistryV2Data => "Tier registry v2 account data is invalid",
+            Self::TierNotFound => "Tier is not configured in the tier registry",
+            Self::TierRegistryFull => "Tier registry has no free slots",
+            Self::InvalidConfigPolicyV2Patch => "Config policy v2 patch arguments are invalid",
+            Self::InvalidConfigPolicyAuthority => "Config policy authority key is invalid",
+            Self::DuplicateConfigPolicyAuthority => "Config policy authority is already present",
+            Self::ConfigPolicyAuthoritySlotsFull => "Config policy authority slots are full",
+            Self::ConfigPolicyAuthorityNotFound => "Config policy authority was not found",
+            Self::LastConfigPolicyAdmin => "Config policy must keep at least one admin authority",
+            Self::InvalidConfigPolicyV2ProposalData => {
+                "Config policy v2 proposal account data is invalid"
+            }
+            Self::ConfigPolicyV2ProposalRequired => {
+                "Config policy v2 update requires an approved proposal"
+            }
+            Self::InvalidAdminApprovalThreshold => "Admin approval threshold is invalid",
+            Self::ConfigPolicyV2ProposalNotPending => "Config policy v2 proposal is not pending",
+            Self::ConfigPolicyV2ProposalExpired => "Config policy v2 proposal has expired",
+            Self::ConfigPolicyV2ProposalAlreadyApproved => {
+                "Config policy v2 proposal was already approved by this admin"
+            }
+            Self::ConfigPolicyV2ProposalThresholdNotMet => {
+                "Config policy v2 proposal does not have enough approvals"
+            }
+            Self::InvalidConfigPolicyV2ScheduleData => {
+                "Config policy v2 schedule account data is invalid"
+            }
+            Self::ConfigPolicyV2ScheduleFull => "Config policy v2 schedule has no free slots",
+            Self::ActivationSlotTooEarly => {
+                "Activation slot is before the minimum activation delay"
+            }
+            Self::ScheduledConfigPolicyV2PatchNotFound => {
+                "Scheduled config policy v2 patch was not found"
+            }
+            Self::ProtocolPaused => "Instruction is paused by the config policy",
+            Self::InvalidProtocolFee => "Protocol fee configuration is invalid",
+            Self::InvalidProtocolFeeRecipient => {
+                "Protocol fee recipient does not match the config policy"
+            }
+            Self::InvalidTokenEscrowAccounts => {
+                "Token escrow accounts are missing or do not match the escrow"
+            }
+            Self::InvalidRefundPageV2Input => "Bundle refund page v2 input is invalid",
+            Self::RequesterRefundAlreadyClaimed => "Requester refund was already claimed",
+            Self::InvalidRunnerUpsV2 => "Bundle escrow v2 runner-ups are invalid",
+            Self::NoRunnerUpAvailable => "No runner-up is left to take over the bundle escrow",
+            Self::InvalidNodePenalty => "Node penalty must be at most 10000 bps",
+            Self::NodeNotPenalizable => "Node can not be penalised for this bundle escrow",
+            Self::NodePenaltyAlreadyApplied => "Node was already penalised for this bundle escrow",
+            Self::InvalidNodePenaltyRecord => "Node penalty record belongs to another node or mint",
+            Self::InvalidNodeReputationV2 => "Node did not win or verify the settled bundle escrow",
+            Self::InvalidVerifierCandidates => {
+                "Verifier candidates are duplicated or their stakes overflow"
+            }
+            Self::VerifierSelectionMismatch => {
+                "Committed verifiers do not match the verifier selection seed"
+            }
+            Self::InvalidTokenRanges => {
+                "Verifier token ranges are malformed or exceed the posted output tokens"
+            }
+            Self::InvalidMerkleProof => "Merkle proof does not match the output root",
+            Self::InvalidEncryptionIv => "Encryption IV must not be all zeroes or reused",
+            Self::InvalidEncryptionKey => "Encryption public key does not yield a shared secret",
+            Self::InvalidDataUpload => "Data upload payload, seed or chunk length is invalid",
+            Self::InvalidCompressedPayload => {
+                "Compressed payload is malformed or does not match its decompressed length"
+            }
+            Self::CompressionNotSupported => "Payload compression codec is not supported",
+            Self::DataAccountChainCycle => "Linked data accounts form a cycle",
+            Self::DataAccountAuthorityMismatch => {
+                "Linked data account authority does not match the head account"
+            }
+            Self::InvalidDataAccountChain => "Linked data account chain is invalid",
+            Self::InvalidJobInputAccount => "Input data account does not belong to the job request",
+            Self::JobInputHashMismatch => "Input data does not match the job request input hash",
+            Self::JobInputHashEncrypted => "Job request input hash is encrypted",
+            Self::InvalidCid => "CID is malformed",
+            Self::UnsupportedCid => "CID version, codec or hash function is not supported",
+            Self::InvalidNodeRegistration => "Node registration is invalid",
+            Self::ConfigPolicyV2PatchMustBeScheduled => "Config policy v2 patch must be scheduled",
+            Self::InvalidActivationDelay => "Invalid activation delay",
         }
     }
 
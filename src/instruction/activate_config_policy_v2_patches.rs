@@ -0,0 +1,39 @@
+use crate::error::AuctionError;
+use crate::InstructionAccounts;
+use bytemuck::{Pod, Zeroable};
+
+#[derive(Debug, Clone)]
+#[repr(C)]
+pub struct ActivateConfigPolicyV2PatchesAccounts<'a, T> {
+    pub config_policy: &'a T,
+    pub schedule: &'a T,
+}
+
+impl<'a, T> TryFrom<&'a [T]> for ActivateConfigPolicyV2PatchesAccounts<'a, T> {
+    type Error = AuctionError;
+
+    fn try_from(accounts: &'a [T]) -> Result<Self, Self::Error> {
+        let [config_policy, schedule, ..] = accounts else {
+            return Err(Self::Error::NotEnoughAccounts);
+        };
+
+        Ok(Self {
+            config_policy,
+            schedule,
+        })
+    }
+}
+
+impl<'a, T> InstructionAccounts<'a, T> for ActivateConfigPolicyV2PatchesAccounts<'a, T> {
+    fn iter(&'a self) -> impl Iterator<Item = &'a T> {
+        std::iter::once(self.config_policy).chain(std::iter::once(self.schedule))
+    }
+}
+
+#[derive(Pod, Clone, Copy, Zeroable, PartialEq, Eq, Debug)]
+#[repr(C)]
+pub struct ActivateConfigPolicyV2PatchesArgs {
+    /// Reserved for future use. Activation is permissionless and always drains every due
+    /// patch.
+    pub _reserved0: [u8; 8],
+}
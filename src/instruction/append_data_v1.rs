@@ -0,0 +1,39 @@
+use crate::constant::PUBKEY_BYTES;
+use crate::{AppendDataArgs, MaybePubkey, Pubkey};
+use bytemuck::Pod;
+use bytemuck::Zeroable;
+use std::num::NonZeroU64;
+
+/// Expected header in the beginning of the AppendDataV1 instruction.
+///
+/// Fills a V1 data account, whose [`MetadataV1`](crate::MetadataV1) links to the next account
+/// of the payload. Takes the same accounts as `AppendData`, see
+/// [`AppendDataAccounts`](crate::AppendDataAccounts).
+#[derive(Pod, Clone, Copy, Zeroable, PartialEq, Debug)]
+#[repr(C)]
+pub struct AppendDataV1Args {
+    /// Offset to append data from (inclusive).
+    ///
+    /// NOTE: has to be at least `size_of::<MetadataV1>()`
+    pub offset: u64,
+    /// Seed used to create the data_account.
+    /// Must match seed used in `CreateAccountWithSeed` system program instruction.
+    pub seed: [u8; PUBKEY_BYTES],
+    pub seed_len: u64,
+    /// Length of decompressed data. `None` if no compression is used
+    pub decompressed_data_length: Option<NonZeroU64>,
+    /// The account holding the rest of the payload, `None` in the last account
+    pub next_data_account: MaybePubkey,
+}
+
+impl AppendDataV1Args {
+    pub fn new(args: &AppendDataArgs, next_data_account: Option<Pubkey>) -> Self {
+        Self {
+            offset: args.offset,
+            seed: args.seed,
+            seed_len: args.seed_len,
+            decompressed_data_length: args.decompressed_data_length,
+            next_data_account: next_data_account.into(),
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use crate::error::AuctionError;
+use crate::state::NodePenaltyKind;
+use crate::InstructionAccounts;
+use bytemuck::{Pod, Zeroable};
+
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct ApplyNodePenaltyV2Accounts<'a, T> {
+    pub caller: &'a T,
+    pub bundle_escrow: &'a T,
+    pub config_policy: &'a T,
+    /// Must belong to the penalised node and the escrow's mint.
+    pub node_penalty_record: &'a T,
+}
+
+impl<'a, T> TryFrom<&'a [T]> for ApplyNodePenaltyV2Accounts<'a, T> {
+    type Error = AuctionError;
+
+    fn try_from(accounts: &'a [T]) -> Result<Self, Self::Error> {
+        let [caller, bundle_escrow, config_policy, node_penalty_record, ..] = accounts else {
+            return Err(Self::Error::NotEnoughAccounts);
+        };
+
+        Ok(Self {
+            caller,
+            bundle_escrow,
+            config_policy,
+            node_penalty_record,
+        })
+    }
+}
+
+impl<'a, T> InstructionAccounts<'a, T> for ApplyNodePenaltyV2Accounts<'a, T> {
+    fn iter(&'a self) -> impl Iterator<Item = &'a T> {
+        std::iter::once(self.caller)
+            .chain(std::iter::once(self.bundle_escrow))
+            .chain(std::iter::once(self.config_policy))
+            .chain(std::iter::once(self.node_penalty_record))
+    }
+}
+
+/// Penalises a node of a finalized or expired escrow under the policy's node penalties.
+/// Permissionless; the escrow lets each of its nodes be penalised once.
+#[derive(Pod, Clone, Copy, Zeroable, PartialEq, Eq, Debug)]
+#[repr(C)]
+pub struct ApplyNodePenaltyV2Args {
+    pub penalty_kind: NodePenaltyKind,
+    /// The selected verifier for missed verifications; for missed results the defaulted
+    /// winner, or the current winner one past them. Zero for rejected results.
+    pub node_index: u8,
+    pub _reserved: [u8; 6],
+}
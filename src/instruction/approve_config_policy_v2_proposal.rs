@@ -0,0 +1,42 @@
+use crate::error::AuctionError;
+use crate::InstructionAccounts;
+use bytemuck::{Pod, Zeroable};
+
+#[derive(Debug, Clone)]
+#[repr(C)]
+pub struct ApproveConfigPolicyV2ProposalAccounts<'a, T> {
+    pub approver: &'a T,
+    pub config_policy: &'a T,
+    pub proposal: &'a T,
+}
+
+impl<'a, T> TryFrom<&'a [T]> for ApproveConfigPolicyV2ProposalAccounts<'a, T> {
+    type Error = AuctionError;
+
+    fn try_from(accounts: &'a [T]) -> Result<Self, Self::Error> {
+        let [approver, config_policy, proposal, ..] = accounts else {
+            return Err(Self::Error::NotEnoughAccounts);
+        };
+
+        Ok(Self {
+            approver,
+            config_policy,
+            proposal,
+        })
+    }
+}
+
+impl<'a, T> InstructionAccounts<'a, T> for ApproveConfigPolicyV2ProposalAccounts<'a, T> {
+    fn iter(&'a self) -> impl Iterator<Item = &'a T> {
+        std::iter::once(self.approver)
+            .chain(std::iter::once(self.config_policy))
+            .chain(std::iter::once(self.proposal))
+    }
+}
+
+#[derive(Pod, Clone, Copy, Zeroable, PartialEq, Eq, Debug)]
+#[repr(C)]
+pub struct ApproveConfigPolicyV2ProposalArgs {
+    /// Must match the proposal account, guarding against a mixed-up proposal account.
+    pub proposal_id: u64,
+}
@@ -0,0 +1,42 @@
+use crate::error::AuctionError;
+use crate::InstructionAccounts;
+use bytemuck::{Pod, Zeroable};
+
+#[derive(Debug, Clone)]
+#[repr(C)]
+pub struct CancelConfigPolicyV2ProposalAccounts<'a, T> {
+    pub authority: &'a T,
+    pub config_policy: &'a T,
+    pub proposal: &'a T,
+}
+
+impl<'a, T> TryFrom<&'a [T]> for CancelConfigPolicyV2ProposalAccounts<'a, T> {
+    type Error = AuctionError;
+
+    fn try_from(accounts: &'a [T]) -> Result<Self, Self::Error> {
+        let [authority, config_policy, proposal, ..] = accounts else {
+            return Err(Self::Error::NotEnoughAccounts);
+        };
+
+        Ok(Self {
+            authority,
+            config_policy,
+            proposal,
+        })
+    }
+}
+
+impl<'a, T> InstructionAccounts<'a, T> for CancelConfigPolicyV2ProposalAccounts<'a, T> {
+    fn iter(&'a self) -> impl Iterator<Item = &'a T> {
+        std::iter::once(self.authority)
+            .chain(std::iter::once(self.config_policy))
+            .chain(std::iter::once(self.proposal))
+    }
+}
+
+#[derive(Pod, Clone, Copy, Zeroable, PartialEq, Eq, Debug)]
+#[repr(C)]
+pub struct CancelConfigPolicyV2ProposalArgs {
+    /// Must match the proposal account, guarding against a mixed-up proposal account.
+    pub proposal_id: u64,
+}
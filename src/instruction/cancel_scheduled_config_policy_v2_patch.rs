@@ -0,0 +1,41 @@
+use crate::error::AuctionError;
+use crate::InstructionAccounts;
+use bytemuck::{Pod, Zeroable};
+
+#[derive(Debug, Clone)]
+#[repr(C)]
+pub struct CancelScheduledConfigPolicyV2PatchAccounts<'a, T> {
+    pub authority: &'a T,
+    pub config_policy: &'a T,
+    pub schedule: &'a T,
+}
+
+impl<'a, T> TryFrom<&'a [T]> for CancelScheduledConfigPolicyV2PatchAccounts<'a, T> {
+    type Error = AuctionError;
+
+    fn try_from(accounts: &'a [T]) -> Result<Self, Self::Error> {
+        let [authority, config_policy, schedule, ..] = accounts else {
+            return Err(Self::Error::NotEnoughAccounts);
+        };
+
+        Ok(Self {
+            authority,
+            config_policy,
+            schedule,
+        })
+    }
+}
+
+impl<'a, T> InstructionAccounts<'a, T> for CancelScheduledConfigPolicyV2PatchAccounts<'a, T> {
+    fn iter(&'a self) -> impl Iterator<Item = &'a T> {
+        std::iter::once(self.authority)
+            .chain(std::iter::once(self.config_policy))
+            .chain(std::iter::once(self.schedule))
+    }
+}
+
+#[derive(Pod, Clone, Copy, Zeroable, PartialEq, Eq, Debug)]
+#[repr(C)]
+pub struct CancelScheduledConfigPolicyV2PatchArgs {
+    pub schedule_id: u64,
+}
@@ -0,0 +1,78 @@
+use crate::error::AuctionError;
+use crate::InstructionAccounts;
+use bytemuck::{Pod, Zeroable};
+
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct ClaimRequesterRefundV2Accounts<'a, T> {
+    pub bundle_escrow: &'a T,
+    pub bundle_refund_page: &'a T,
+    pub refund_recipient: &'a T,
+    pub config_policy: &'a T,
+    /// Token escrow accounts, set by [`Self::try_from_token_escrow`].
+    pub token_program: Option<&'a T>,
+    pub escrow_vault: Option<&'a T>,
+    pub refund_token_account: Option<&'a T>,
+}
+
+impl<'a, T> TryFrom<&'a [T]> for ClaimRequesterRefundV2Accounts<'a, T> {
+    type Error = AuctionError;
+
+    fn try_from(accounts: &'a [T]) -> Result<Self, Self::Error> {
+        let [bundle_escrow, bundle_refund_page, refund_recipient, config_policy, ..] = accounts
+        else {
+            return Err(AuctionError::NotEnoughAccounts);
+        };
+
+        Ok(Self {
+            bundle_escrow,
+            bundle_refund_page,
+            refund_recipient,
+            config_policy,
+            token_program: None,
+            escrow_vault: None,
+            refund_token_account: None,
+        })
+    }
+}
+
+impl<'a, T> ClaimRequesterRefundV2Accounts<'a, T> {
+    /// Parses the accounts of an SPL-token escrow, whose token accounts follow `config_policy`.
+    pub fn try_from_token_escrow(accounts: &'a [T]) -> Result<Self, AuctionError> {
+        let [bundle_escrow, bundle_refund_page, refund_recipient, config_policy, token_program, escrow_vault, refund_token_account, ..] =
+            accounts
+        else {
+            return Err(AuctionError::NotEnoughAccounts);
+        };
+
+        Ok(Self {
+            bundle_escrow,
+            bundle_refund_page,
+            refund_recipient,
+            config_policy,
+            token_program: Some(token_program),
+            escrow_vault: Some(escrow_vault),
+            refund_token_account: Some(refund_token_account),
+        })
+    }
+}
+
+impl<'a, T> InstructionAccounts<'a, T> for ClaimRequesterRefundV2Accounts<'a, T> {
+    fn iter(&'a self) -> impl Iterator<Item = &'a T> {
+        std::iter::once(self.bundle_escrow)
+            .chain(std::iter::once(self.bundle_refund_page))
+            .chain(std::iter::once(self.refund_recipient))
+            .chain(std::iter::once(self.config_policy))
+            .chain(self.token_program)
+            .chain(self.escrow_vault)
+            .chain(self.refund_token_account)
+    }
+}
+
+#[derive(Pod, Clone, Copy, Zeroable, PartialEq, Eq, Debug)]
+#[repr(C)]
+pub struct ClaimRequesterRefundV2Args {
+    pub page_index: u16,
+    pub entry_index: u16,
+    pub _reserved: [u8; 4],
+}
@@ -10,7 +10,22 @@ pub struct ClaimVerifierLstakeV2Accounts<'a, T> {
     pub vote_program: &'a T,
     pub vote_authority: &'a T,
     pub config_policy: &'a T,
+    /// Token escrow accounts, set by [`Self::try_from_token_escrow`].
+    pub token_program: Option<&'a T>,
+    pub escrow_vault: Option<&'a T>,
+    pub verifier_token_account: Option<&'a T>,
     pub bundle_verifier_pages: &'a [T],
+    /// The verifier's `NodePenaltyRecord` for the escrow's mint, possibly not created yet.
+    /// Its outstanding penalty is withheld from the claim.
+    ///
+    /// The penalty accounts are optional and follow the verifier pages, so claims built
+    /// without them keep their account order. `None` until split off by
+    /// [`Self::with_verifier_page_count`].
+    pub node_penalty_record: Option<&'a T>,
+    /// Receives the withheld penalty.
+    pub protocol_fee_recipient: Option<&'a T>,
+    /// Receives the withheld penalty of a token escrow.
+    pub protocol_fee_token_account: Option<&'a T>,
 }
 
 impl<'a, T> TryFrom<&'a [T]> for ClaimVerifierLstakeV2Accounts<'a, T> {
@@ -29,11 +44,77 @@ impl<'a, T> TryFrom<&'a [T]> for ClaimVerifierLstakeV2Accounts<'a, T> {
             vote_program,
             vote_authority,
             config_policy,
+            token_program: None,
+            escrow_vault: None,
+            verifier_token_account: None,
             bundle_verifier_pages,
+            node_penalty_record: None,
+            protocol_fee_recipient: None,
+            protocol_fee_token_account: None,
         })
     }
 }
 
+impl<'a, T> ClaimVerifierLstakeV2Accounts<'a, T> {
+    /// Parses the accounts of an SPL-token escrow, whose token accounts follow `config_policy`
+    /// and precede the verifier pages.
+    pub fn try_from_token_escrow(accounts: &'a [T]) -> Result<Self, AuctionError> {
+        let [bundle_escrow, verifier_vote_account, vote_program, vote_authority, config_policy, token_program, escrow_vault, verifier_token_account, bundle_verifier_pages @ ..] =
+            accounts
+        else {
+            return Err(AuctionError::NotEnoughAccounts);
+        };
+
+        Ok(Self {
+            bundle_escrow,
+            verifier_vote_account,
+            vote_program,
+            vote_authority,
+            config_policy,
+            token_program: Some(token_program),
+            escrow_vault: Some(escrow_vault),
+            verifier_token_account: Some(verifier_token_account),
+            bundle_verifier_pages,
+            node_penalty_record: None,
+            protocol_fee_recipient: None,
+            protocol_fee_token_account: None,
+        })
+    }
+
+    /// Moves the accounts after the first `verifier_page_count` accounts of
+    /// `bundle_verifier_pages` into the penalty accounts.
+    ///
+    /// Parsing can not know how many verifier pages the escrow has, so it leaves every
+    /// trailing account in `bundle_verifier_pages`; pass the escrow's `verifier_page_count`
+    /// once the escrow is loaded. Fails with `NotEnoughAccounts` if only some of the penalty
+    /// accounts are passed.
+    pub fn with_verifier_page_count(
+        mut self,
+        verifier_page_count: usize,
+    ) -> Result<Self, AuctionError> {
+        let (bundle_verifier_pages, penalty_accounts) = self
+            .bundle_verifier_pages
+            .split_at_checked(verifier_page_count)
+            .ok_or(AuctionError::NotEnoughAccounts)?;
+        self.bundle_verifier_pages = bundle_verifier_pages;
+
+        match (penalty_accounts, self.token_program.is_some()) {
+            ([], _) => {}
+            ([node_penalty_record, protocol_fee_recipient], false) => {
+                self.node_penalty_record = Some(node_penalty_record);
+                self.protocol_fee_recipient = Some(protocol_fee_recipient);
+            }
+            ([node_penalty_record, protocol_fee_recipient, protocol_fee_token_account], true) => {
+                self.node_penalty_record = Some(node_penalty_record);
+                self.protocol_fee_recipient = Some(protocol_fee_recipient);
+                self.protocol_fee_token_account = Some(protocol_fee_token_account);
+            }
+            _ => return Err(AuctionError::NotEnoughAccounts),
+        }
+        Ok(self)
+    }
+}
+
 impl<'a, T> InstructionAccounts<'a, T> for ClaimVerifierLstakeV2Accounts<'a, T> {
     fn iter(&'a self) -> impl Iterator<Item = &'a T> {
         std::iter::once(self.bundle_escrow)
@@ -41,7 +122,13 @@ impl<'a, T> InstructionAccounts<'a, T> for ClaimVerifierLstakeV2Accounts<'a, T>
             .chain(std::iter::once(self.vote_program))
             .chain(std::iter::once(self.vote_authority))
             .chain(std::iter::once(self.config_policy))
+            .chain(self.token_program)
+            .chain(self.escrow_vault)
+            .chain(self.verifier_token_account)
             .chain(self.bundle_verifier_pages.iter())
+            .chain(self.node_penalty_record)
+            .chain(self.protocol_fee_recipient)
+            .chain(self.protocol_fee_token_account)
     }
 }
 
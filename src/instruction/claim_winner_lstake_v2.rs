@@ -10,24 +10,87 @@ pub struct ClaimWinnerLstakeV2Accounts<'a, T> {
     pub vote_program: &'a T,
     pub vote_authority: &'a T,
     pub config_policy: &'a T,
+    /// Token escrow accounts, set by [`Self::try_from_token_escrow`].
+    pub token_program: Option<&'a T>,
+    pub escrow_vault: Option<&'a T>,
+    pub winner_token_account: Option<&'a T>,
+    /// The winner's `NodePenaltyRecord` for the escrow's mint, possibly not created yet. Its
+    /// outstanding penalty is withheld from the claim.
+    ///
+    /// The penalty accounts are optional and trail the other accounts, so claims built
+    /// without them keep their account order.
+    pub node_penalty_record: Option<&'a T>,
+    /// Receives the withheld penalty.
+    pub protocol_fee_recipient: Option<&'a T>,
+    /// Receives the withheld penalty of a token escrow.
+    pub protocol_fee_token_account: Option<&'a T>,
 }
 
 impl<'a, T> TryFrom<&'a [T]> for ClaimWinnerLstakeV2Accounts<'a, T> {
     type Error = AuctionError;
 
     fn try_from(accounts: &'a [T]) -> Result<Self, Self::Error> {
-        let [bundle_escrow, winner_vote_account, vote_program, vote_authority, config_policy, ..] =
+        let [bundle_escrow, winner_vote_account, vote_program, vote_authority, config_policy, penalty_accounts @ ..] =
             accounts
         else {
             return Err(AuctionError::NotEnoughAccounts);
         };
 
+        let (node_penalty_record, protocol_fee_recipient) = match penalty_accounts {
+            [node_penalty_record, protocol_fee_recipient, ..] => {
+                (Some(node_penalty_record), Some(protocol_fee_recipient))
+            }
+            _ => (None, None),
+        };
+
+        Ok(Self {
+            bundle_escrow,
+            winner_vote_account,
+            vote_program,
+            vote_authority,
+            config_policy,
+            token_program: None,
+            escrow_vault: None,
+            winner_token_account: None,
+            node_penalty_record,
+            protocol_fee_recipient,
+            protocol_fee_token_account: None,
+        })
+    }
+}
+
+impl<'a, T> ClaimWinnerLstakeV2Accounts<'a, T> {
+    /// Parses the accounts of an SPL-token escrow, whose token accounts follow
+    /// `config_policy` and precede the optional penalty accounts.
+    pub fn try_from_token_escrow(accounts: &'a [T]) -> Result<Self, AuctionError> {
+        let [bundle_escrow, winner_vote_account, vote_program, vote_authority, config_policy, token_program, escrow_vault, winner_token_account, penalty_accounts @ ..] =
+            accounts
+        else {
+            return Err(AuctionError::NotEnoughAccounts);
+        };
+
+        let (node_penalty_record, protocol_fee_recipient, protocol_fee_token_account) =
+            match penalty_accounts {
+                [node_penalty_record, protocol_fee_recipient, protocol_fee_token_account, ..] => (
+                    Some(node_penalty_record),
+                    Some(protocol_fee_recipient),
+                    Some(protocol_fee_token_account),
+                ),
+                _ => (None, None, None),
+            };
+
         Ok(Self {
             bundle_escrow,
             winner_vote_account,
             vote_program,
             vote_authority,
             config_policy,
+            token_program: Some(token_program),
+            escrow_vault: Some(escrow_vault),
+            winner_token_account: Some(winner_token_account),
+            node_penalty_record,
+            protocol_fee_recipient,
+            protocol_fee_token_account,
         })
     }
 }
@@ -39,6 +102,12 @@ impl<'a, T> InstructionAccounts<'a, T> for ClaimWinnerLstakeV2Accounts<'a, T> {
             .chain(std::iter::once(self.vote_program))
             .chain(std::iter::once(self.vote_authority))
             .chain(std::iter::once(self.config_policy))
+            .chain(self.token_program)
+            .chain(self.escrow_vault)
+            .chain(self.winner_token_account)
+            .chain(self.node_penalty_record)
+            .chain(self.protocol_fee_recipient)
+            .chain(self.protocol_fee_token_account)
     }
 }
 
@@ -0,0 +1,46 @@
+use crate::error::AuctionError;
+use crate::state::{BundleEscrowV2RunnerUp, MAX_BUNDLE_ESCROW_V2_RUNNER_UPS};
+use crate::InstructionAccounts;
+use bytemuck::{Pod, Zeroable};
+
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct CommitRunnerUpsV2Accounts<'a, T> {
+    pub coordinator: &'a T,
+    pub bundle_escrow: &'a T,
+    pub config_policy: &'a T,
+}
+
+impl<'a, T> TryFrom<&'a [T]> for CommitRunnerUpsV2Accounts<'a, T> {
+    type Error = AuctionError;
+
+    fn try_from(accounts: &'a [T]) -> Result<Self, Self::Error> {
+        let [coordinator, bundle_escrow, config_policy, ..] = accounts else {
+            return Err(Self::Error::NotEnoughAccounts);
+        };
+
+        Ok(Self {
+            coordinator,
+            bundle_escrow,
+            config_policy,
+        })
+    }
+}
+
+impl<'a, T> InstructionAccounts<'a, T> for CommitRunnerUpsV2Accounts<'a, T> {
+    fn iter(&'a self) -> impl Iterator<Item = &'a T> {
+        std::iter::once(self.coordinator)
+            .chain(std::iter::once(self.bundle_escrow))
+            .chain(std::iter::once(self.config_policy))
+    }
+}
+
+/// Records the ranked runner-ups of an awarded V5 escrow.
+#[derive(Pod, Clone, Copy, Zeroable, PartialEq, Eq, Debug)]
+#[repr(C)]
+pub struct CommitRunnerUpsV2Args {
+    pub runner_up_count: u8,
+    pub _reserved: [u8; 7],
+    /// Ranked from the lowest revealed bid up. Entries past `runner_up_count` must be zeroed.
+    pub runner_ups: [BundleEscrowV2RunnerUp; MAX_BUNDLE_ESCROW_V2_RUNNER_UPS],
+}
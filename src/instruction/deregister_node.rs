@@ -0,0 +1,49 @@
+use crate::error::AuctionError;
+use crate::InstructionAccounts;
+use bytemuck::{Pod, Zeroable};
+
+/// DeregisterNode instruction
+///
+/// Closes the node registration and returns its lamports. Bids placed with the registration
+/// can no longer be reached once it is gone.
+///
+/// # Account References:
+///
+/// 0. `[SIGNER]` Node authority
+/// 1. `[WRITE]` Node registration account
+/// 2. `[WRITE]` Lamports recipient
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct DeregisterNodeAccounts<'a, T> {
+    pub node_authority: &'a T,
+    pub node_registration: &'a T,
+    pub recipient: &'a T,
+}
+
+impl<'a, T> TryFrom<&'a [T]> for DeregisterNodeAccounts<'a, T> {
+    type Error = AuctionError;
+
+    fn try_from(accounts: &'a [T]) -> Result<Self, Self::Error> {
+        let [node_authority, node_registration, recipient, ..] = accounts else {
+            return Err(Self::Error::NotEnoughAccounts);
+        };
+
+        Ok(Self {
+            node_authority,
+            node_registration,
+            recipient,
+        })
+    }
+}
+
+impl<'a, T> InstructionAccounts<'a, T> for DeregisterNodeAccounts<'a, T> {
+    fn iter(&'a self) -> impl Iterator<Item = &'a T> {
+        std::iter::once(self.node_authority)
+            .chain(std::iter::once(self.node_registration))
+            .chain(std::iter::once(self.recipient))
+    }
+}
+
+#[derive(Pod, Clone, Copy, Zeroable, PartialEq, Eq, Debug)]
+#[repr(C)]
+pub struct DeregisterNodeArgs {}
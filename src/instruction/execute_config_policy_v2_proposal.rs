@@ -0,0 +1,47 @@
+use crate::error::AuctionError;
+use crate::InstructionAccounts;
+use bytemuck::{Pod, Zeroable};
+
+#[derive(Debug, Clone)]
+#[repr(C)]
+pub struct ExecuteConfigPolicyV2ProposalAccounts<'a, T> {
+    pub executor: &'a T,
+    pub config_policy: &'a T,
+    pub proposal: &'a T,
+    /// `ConfigPolicyV2Schedule` to queue the patch in instead of applying it, required for
+    /// patches that affect escrows in flight.
+    pub schedule: Option<&'a T>,
+}
+
+impl<'a, T> TryFrom<&'a [T]> for ExecuteConfigPolicyV2ProposalAccounts<'a, T> {
+    type Error = AuctionError;
+
+    fn try_from(accounts: &'a [T]) -> Result<Self, Self::Error> {
+        let [executor, config_policy, proposal, rest @ ..] = accounts else {
+            return Err(Self::Error::NotEnoughAccounts);
+        };
+
+        Ok(Self {
+            executor,
+            config_policy,
+            proposal,
+            schedule: rest.first(),
+        })
+    }
+}
+
+impl<'a, T> InstructionAccounts<'a, T> for ExecuteConfigPolicyV2ProposalAccounts<'a, T> {
+    fn iter(&'a self) -> impl Iterator<Item = &'a T> {
+        std::iter::once(self.executor)
+            .chain(std::iter::once(self.config_policy))
+            .chain(std::iter::once(self.proposal))
+            .chain(self.schedule)
+    }
+}
+
+#[derive(Pod, Clone, Copy, Zeroable, PartialEq, Eq, Debug)]
+#[repr(C)]
+pub struct ExecuteConfigPolicyV2ProposalArgs {
+    /// Must match the proposal account, guarding against a mixed-up proposal account.
+    pub proposal_id: u64,
+}
@@ -8,13 +8,21 @@ pub struct ExpireBundleEscrowV2Accounts<'a, T> {
     pub bundle_escrow: &'a T,
     pub requester_refund_recipient: &'a T,
     pub config_policy: &'a T,
+    /// `NodeReputationV2` of the winner. Ignored when the escrow expires before an award.
+    pub winner_reputation: &'a T,
+    /// Token escrow accounts, set by [`Self::try_from_token_escrow`].
+    pub token_program: Option<&'a T>,
+    pub escrow_vault: Option<&'a T>,
+    pub requester_refund_token_account: Option<&'a T>,
 }
 
 impl<'a, T> TryFrom<&'a [T]> for ExpireBundleEscrowV2Accounts<'a, T> {
     type Error = AuctionError;
 
     fn try_from(accounts: &'a [T]) -> Result<Self, Self::Error> {
-        let [bundle_escrow, requester_refund_recipient, config_policy, ..] = accounts else {
+        let [bundle_escrow, requester_refund_recipient, config_policy, winner_reputation, ..] =
+            accounts
+        else {
             return Err(AuctionError::NotEnoughAccounts);
         };
 
@@ -22,6 +30,31 @@ impl<'a, T> TryFrom<&'a [T]> for ExpireBundleEscrowV2Accounts<'a, T> {
             bundle_escrow,
             requester_refund_recipient,
             config_policy,
+            winner_reputation,
+            token_program: None,
+            escrow_vault: None,
+            requester_refund_token_account: None,
+        })
+    }
+}
+
+impl<'a, T> ExpireBundleEscrowV2Accounts<'a, T> {
+    /// Parses the accounts of an SPL-token escrow, whose token accounts follow `winner_reputation`.
+    pub fn try_from_token_escrow(accounts: &'a [T]) -> Result<Self, AuctionError> {
+        let [bundle_escrow, requester_refund_recipient, config_policy, winner_reputation, token_program, escrow_vault, requester_refund_token_account, ..] =
+            accounts
+        else {
+            return Err(AuctionError::NotEnoughAccounts);
+        };
+
+        Ok(Self {
+            bundle_escrow,
+            requester_refund_recipient,
+            config_policy,
+            winner_reputation,
+            token_program: Some(token_program),
+            escrow_vault: Some(escrow_vault),
+            requester_refund_token_account: Some(requester_refund_token_account),
         })
     }
 }
@@ -31,6 +64,10 @@ impl<'a, T> InstructionAccounts<'a, T> for ExpireBundleEscrowV2Accounts<'a, T> {
         std::iter::once(self.bundle_escrow)
             .chain(std::iter::once(self.requester_refund_recipient))
             .chain(std::iter::once(self.config_policy))
+            .chain(std::iter::once(self.winner_reputation))
+            .chain(self.token_program)
+            .chain(self.escrow_vault)
+            .chain(self.requester_refund_token_account)
     }
 }
 
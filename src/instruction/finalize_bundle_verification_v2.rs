@@ -28,6 +28,17 @@ pub struct FinalizeBundleVerificationV2Accounts<'a, T> {
     pub requester_refund_recipient: &'a T,
     pub instructions_sysvar: &'a T,
     pub config_policy: &'a T,
+    pub protocol_fee_recipient: &'a T,
+    /// `NodeReputationV2` of the winner.
+    pub winner_reputation: &'a T,
+    /// Token escrow accounts, set by [`Self::try_from_token_escrow`].
+    pub token_program: Option<&'a T>,
+    pub escrow_vault: Option<&'a T>,
+    pub requester_refund_token_account: Option<&'a T>,
+    pub protocol_fee_token_account: Option<&'a T>,
+    /// `NodeReputationV2` of each slot of the escrow's `BundleEscrowV2VerifierSet`, in slot
+    /// order. Empty until split off by [`Self::with_verifier_count`]; unused slots are ignored.
+    pub verifier_reputations: &'a [T],
     pub bundle_verifier_pages: &'a [T],
 }
 
@@ -35,7 +46,7 @@ impl<'a, T> TryFrom<&'a [T]> for FinalizeBundleVerificationV2Accounts<'a, T> {
     type Error = AuctionError;
 
     fn try_from(accounts: &'a [T]) -> Result<Self, Self::Error> {
-        let [coordinator, bundle_escrow, winner_node, requester_refund_recipient, instructions_sysvar, config_policy, bundle_verifier_pages @ ..] =
+        let [coordinator, bundle_escrow, winner_node, requester_refund_recipient, instructions_sysvar, config_policy, protocol_fee_recipient, winner_reputation, bundle_verifier_pages @ ..] =
             accounts
         else {
             return Err(AuctionError::NotEnoughAccounts);
@@ -48,11 +59,63 @@ impl<'a, T> TryFrom<&'a [T]> for FinalizeBundleVerificationV2Accounts<'a, T> {
             requester_refund_recipient,
             instructions_sysvar,
             config_policy,
+            protocol_fee_recipient,
+            winner_reputation,
+            token_program: None,
+            escrow_vault: None,
+            requester_refund_token_account: None,
+            protocol_fee_token_account: None,
+            verifier_reputations: &[],
             bundle_verifier_pages,
         })
     }
 }
 
+impl<'a, T> FinalizeBundleVerificationV2Accounts<'a, T> {
+    /// Parses the accounts of an SPL-token escrow, whose token accounts follow
+    /// `winner_reputation` and precede the verifier reputations.
+    pub fn try_from_token_escrow(accounts: &'a [T]) -> Result<Self, AuctionError> {
+        let [coordinator, bundle_escrow, winner_node, requester_refund_recipient, instructions_sysvar, config_policy, protocol_fee_recipient, winner_reputation, token_program, escrow_vault, requester_refund_token_account, protocol_fee_token_account, bundle_verifier_pages @ ..] =
+            accounts
+        else {
+            return Err(AuctionError::NotEnoughAccounts);
+        };
+
+        Ok(Self {
+            coordinator,
+            bundle_escrow,
+            winner_node,
+            requester_refund_recipient,
+            instructions_sysvar,
+            config_policy,
+            protocol_fee_recipient,
+            winner_reputation,
+            token_program: Some(token_program),
+            escrow_vault: Some(escrow_vault),
+            requester_refund_token_account: Some(requester_refund_token_account),
+            protocol_fee_token_account: Some(protocol_fee_token_account),
+            verifier_reputations: &[],
+            bundle_verifier_pages,
+        })
+    }
+
+    /// Moves the first `verifier_count` accounts of `bundle_verifier_pages` into
+    /// `verifier_reputations`.
+    ///
+    /// Parsing can not know how many verifiers the escrow selected, so it leaves every
+    /// trailing account in `bundle_verifier_pages`; pass the `len` of the escrow's
+    /// `BundleEscrowV2VerifierSet` once the escrow is loaded.
+    pub fn with_verifier_count(mut self, verifier_count: usize) -> Result<Self, AuctionError> {
+        let (verifier_reputations, bundle_verifier_pages) = self
+            .bundle_verifier_pages
+            .split_at_checked(verifier_count)
+            .ok_or(AuctionError::NotEnoughAccounts)?;
+        self.verifier_reputations = verifier_reputations;
+        self.bundle_verifier_pages = bundle_verifier_pages;
+        Ok(self)
+    }
+}
+
 impl<'a, T> InstructionAccounts<'a, T> for FinalizeBundleVerificationV2Accounts<'a, T> {
     fn iter(&'a self) -> impl Iterator<Item = &'a T> {
         std::iter::once(self.coordinator)
@@ -61,6 +124,13 @@ impl<'a, T> InstructionAccounts<'a, T> for FinalizeBundleVerificationV2Accounts<
             .chain(std::iter::once(self.requester_refund_recipient))
             .chain(std::iter::once(self.instructions_sysvar))
             .chain(std::iter::once(self.config_policy))
+            .chain(std::iter::once(self.protocol_fee_recipient))
+            .chain(std::iter::once(self.winner_reputation))
+            .chain(self.token_program)
+            .chain(self.escrow_vault)
+            .chain(self.requester_refund_token_account)
+            .chain(self.protocol_fee_token_account)
+            .chain(self.verifier_reputations.iter())
             .chain(self.bundle_verifier_pages.iter())
     }
 }
@@ -119,7 +189,18 @@ pub struct FinalizeBundleVerificationV2Args {
     pub winner_payout_lamports: u64,
     pub verdict: VerificationVerdictV2,
     pub quorum_verifier_bitmap: u8,
-    pub _reserved: [u8; 6],
+    /// Verifiers that voted against `verdict`, which are not penalised for a missed
+    /// verification. Disjoint from the quorum.
+    pub dissenting_verifier_bitmap: u16,
+    /// Quorum of a V6 escrow, whose verifiers do not fit `quorum_verifier_bitmap`.
+    pub extended_quorum_verifier_bitmap: u32,
+}
+
+impl FinalizeBundleVerificationV2Args {
+    /// The quorum over all verifier slots, whichever of the two bitmaps carries it.
+    pub fn quorum_bitmap(&self) -> u32 {
+        u32::from(self.quorum_verifier_bitmap) | self.extended_quorum_verifier_bitmap
+    }
 }
 
 #[derive(Clone, Copy, Zeroable, PartialEq, Eq, Debug, Pod)]
@@ -0,0 +1,51 @@
+use crate::error::AuctionError;
+use crate::state::{BundleRefundPageV2Entry, MAX_BUNDLE_REFUND_PAGE_V2_ENTRIES};
+use crate::InstructionAccounts;
+use bytemuck::{Pod, Zeroable};
+
+#[derive(Debug, Clone)]
+#[repr(C)]
+pub struct InitBundleRefundPageV2Accounts<'a, T> {
+    pub coordinator: &'a T,
+    pub bundle_escrow: &'a T,
+    pub bundle_refund_page: &'a T,
+    pub system_program: &'a T,
+}
+
+impl<'a, T> TryFrom<&'a [T]> for InitBundleRefundPageV2Accounts<'a, T> {
+    type Error = AuctionError;
+
+    fn try_from(accounts: &'a [T]) -> Result<Self, Self::Error> {
+        let [coordinator, bundle_escrow, bundle_refund_page, system_program, ..] = accounts else {
+            return Err(Self::Error::NotEnoughAccounts);
+        };
+
+        Ok(Self {
+            coordinator,
+            bundle_escrow,
+            bundle_refund_page,
+            system_program,
+        })
+    }
+}
+
+impl<'a, T> InstructionAccounts<'a, T> for InitBundleRefundPageV2Accounts<'a, T> {
+    fn iter(&'a self) -> impl Iterator<Item = &'a T> {
+        std::iter::once(self.coordinator)
+            .chain(std::iter::once(self.bundle_escrow))
+            .chain(std::iter::once(self.bundle_refund_page))
+            .chain(std::iter::once(self.system_program))
+    }
+}
+
+#[derive(Pod, Clone, Copy, Zeroable, PartialEq, Eq, Debug)]
+#[repr(C)]
+pub struct InitBundleRefundPageV2Args {
+    pub bundle_refund_page_lamports: u64,
+    pub page_index: u16,
+    pub entry_count: u16,
+    pub _reserved: [u8; 4],
+    /// Entries past `entry_count` must be zeroed. `accepted_output_tokens` and
+    /// `refund_amount` are filled in at finalize.
+    pub entries: [BundleRefundPageV2Entry; MAX_BUNDLE_REFUND_PAGE_V2_ENTRIES],
+}
@@ -0,0 +1,56 @@
+use crate::error::AuctionError;
+use crate::{InstructionAccounts, MIN_CONFIG_POLICY_V2_ACTIVATION_DELAY_SLOTS};
+use bytemuck::{Pod, Zeroable};
+
+#[derive(Debug, Clone)]
+#[repr(C)]
+pub struct InitConfigPolicyV2ScheduleAccounts<'a, T> {
+    pub authority: &'a T,
+    pub config_policy: &'a T,
+    pub schedule: &'a T,
+    pub system_program: &'a T,
+}
+
+impl<'a, T> TryFrom<&'a [T]> for InitConfigPolicyV2ScheduleAccounts<'a, T> {
+    type Error = AuctionError;
+
+    fn try_from(accounts: &'a [T]) -> Result<Self, Self::Error> {
+        let [authority, config_policy, schedule, system_program, ..] = accounts else {
+            return Err(Self::Error::NotEnoughAccounts);
+        };
+
+        Ok(Self {
+            authority,
+            config_policy,
+            schedule,
+            system_program,
+        })
+    }
+}
+
+impl<'a, T> InstructionAccounts<'a, T> for InitConfigPolicyV2ScheduleAccounts<'a, T> {
+    fn iter(&'a self) -> impl Iterator<Item = &'a T> {
+        std::iter::once(self.authority)
+            .chain(std::iter::once(self.config_policy))
+            .chain(std::iter::once(self.schedule))
+            .chain(std::iter::once(self.system_program))
+    }
+}
+
+#[derive(Pod, Clone, Copy, Zeroable, PartialEq, Eq, Debug)]
+#[repr(C)]
+pub struct InitConfigPolicyV2ScheduleArgs {
+    pub schedule_lamports: u64,
+    pub min_activation_delay_slots: u64,
+}
+
+impl InitConfigPolicyV2ScheduleArgs {
+    /// Fails with `InvalidActivationDelay` if `min_activation_delay_slots` is below
+    /// [`MIN_CONFIG_POLICY_V2_ACTIVATION_DELAY_SLOTS`].
+    pub fn validate(&self) -> Result<(), AuctionError> {
+        if self.min_activation_delay_slots < MIN_CONFIG_POLICY_V2_ACTIVATION_DELAY_SLOTS {
+            return Err(AuctionError::InvalidActivationDelay);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+use crate::error::AuctionError;
+use crate::{InstructionAccounts, Pubkey};
+use bytemuck::{Pod, Zeroable};
+
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct InitNodePenaltyRecordAccounts<'a, T> {
+    pub payer: &'a T,
+    pub node_penalty_record: &'a T,
+    pub system_program: &'a T,
+}
+
+impl<'a, T> TryFrom<&'a [T]> for InitNodePenaltyRecordAccounts<'a, T> {
+    type Error = AuctionError;
+
+    fn try_from(accounts: &'a [T]) -> Result<Self, Self::Error> {
+        let [payer, node_penalty_record, system_program, ..] = accounts else {
+            return Err(Self::Error::NotEnoughAccounts);
+        };
+
+        Ok(Self {
+            payer,
+            node_penalty_record,
+            system_program,
+        })
+    }
+}
+
+impl<'a, T> InstructionAccounts<'a, T> for InitNodePenaltyRecordAccounts<'a, T> {
+    fn iter(&'a self) -> impl Iterator<Item = &'a T> {
+        std::iter::once(self.payer)
+            .chain(std::iter::once(self.node_penalty_record))
+            .chain(std::iter::once(self.system_program))
+    }
+}
+
+#[derive(Pod, Clone, Copy, Zeroable, PartialEq, Eq, Debug)]
+#[repr(C)]
+pub struct InitNodePenaltyRecordArgs {
+    pub node_pubkey: Pubkey,
+    /// Mint of the token escrows the record penalises, default for lamport escrows.
+    pub mint: Pubkey,
+    pub node_penalty_record_lamports: u64,
+}
@@ -0,0 +1,42 @@
+use crate::error::AuctionError;
+use crate::{InstructionAccounts, Pubkey};
+use bytemuck::{Pod, Zeroable};
+
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct InitNodeReputationV2Accounts<'a, T> {
+    pub payer: &'a T,
+    pub node_reputation: &'a T,
+    pub system_program: &'a T,
+}
+
+impl<'a, T> TryFrom<&'a [T]> for InitNodeReputationV2Accounts<'a, T> {
+    type Error = AuctionError;
+
+    fn try_from(accounts: &'a [T]) -> Result<Self, Self::Error> {
+        let [payer, node_reputation, system_program, ..] = accounts else {
+            return Err(Self::Error::NotEnoughAccounts);
+        };
+
+        Ok(Self {
+            payer,
+            node_reputation,
+            system_program,
+        })
+    }
+}
+
+impl<'a, T> InstructionAccounts<'a, T> for InitNodeReputationV2Accounts<'a, T> {
+    fn iter(&'a self) -> impl Iterator<Item = &'a T> {
+        std::iter::once(self.payer)
+            .chain(std::iter::once(self.node_reputation))
+            .chain(std::iter::once(self.system_program))
+    }
+}
+
+#[derive(Pod, Clone, Copy, Zeroable, PartialEq, Eq, Debug)]
+#[repr(C)]
+pub struct InitNodeReputationV2Args {
+    pub node_pubkey: Pubkey,
+    pub node_reputation_lamports: u64,
+}
@@ -0,0 +1,48 @@
+use crate::error::AuctionError;
+use crate::InstructionAccounts;
+use bytemuck::{Pod, Zeroable};
+
+#[derive(Debug, Clone)]
+#[repr(C)]
+pub struct InitTierRegistryV2Accounts<'a, T> {
+    pub authority: &'a T,
+    pub config_policy: &'a T,
+    pub tier_registry: &'a T,
+    pub system_program: &'a T,
+}
+
+impl<'a, T> TryFrom<&'a [T]> for InitTierRegistryV2Accounts<'a, T> {
+    type Error = AuctionError;
+
+    fn try_from(accounts: &'a [T]) -> Result<Self, Self::Error> {
+        let [authority, config_policy, tier_registry, system_program, ..] = accounts else {
+            return Err(Self::Error::NotEnoughAccounts);
+        };
+
+        Ok(Self {
+            authority,
+            config_policy,
+            tier_registry,
+            system_program,
+        })
+    }
+}
+
+impl<'a, T> InstructionAccounts<'a, T> for InitTierRegistryV2Accounts<'a, T> {
+    fn iter(&'a self) -> impl Iterator<Item = &'a T> {
+        std::iter::once(self.authority)
+            .chain(std::iter::once(self.config_policy))
+            .chain(std::iter::once(self.tier_registry))
+            .chain(std::iter::once(self.system_program))
+    }
+}
+
+#[derive(Pod, Clone, Copy, Zeroable, PartialEq, Eq, Debug)]
+#[repr(C)]
+pub struct InitTierRegistryV2Args {
+    pub tier_registry_lamports: u64,
+    pub capacity: u16,
+    /// Non-zero to seed the registry with the five built-in tiers from the config policy.
+    pub import_policy_tiers: u8,
+    pub _reserved0: [u8; 5],
+}
@@ -1,57 +1,100 @@
+use crate::ConfigPolicyV2PauseGroup;
 use bytemuck::{Pod, Zeroable};
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use std::net;
 
+mod activate_config_policy_v2_patches;
 mod append_data;
+mod append_data_v1;
+mod apply_node_penalty_v2;
+mod approve_config_policy_v2_proposal;
 mod cancel_bundle;
+mod cancel_config_policy_v2_proposal;
+mod cancel_scheduled_config_policy_v2_patch;
+mod claim_requester_refund_v2;
 mod claim_verifier_lstake_v2;
 mod claim_winner_lstake_v2;
 mod close_bid;
 mod close_request;
 mod commit_auction_settlement_v2;
+mod commit_runner_ups_v2;
+mod deregister_node;
 mod end_auction;
+mod execute_config_policy_v2_proposal;
 mod expire_bundle_escrow_v2;
 mod finalize_bundle_verification_v2;
 mod init_bundle;
+mod init_bundle_refund_page_v2;
 #[cfg(feature = "global-config")]
 mod init_config;
 mod init_bundle_verifier_page_v2;
 mod init_config_policy_v2;
+mod init_config_policy_v2_schedule;
+mod init_node_penalty_record;
+mod init_node_reputation_v2;
+mod init_tier_registry_v2;
 mod open_bundle_escrow_v2;
 mod place_bid;
 mod post_bundle_result_v2;
+mod propose_config_policy_v2;
+mod reassign_winner_v2;
+mod register_node;
 mod request_job;
 mod reveal_bid;
+mod schedule_config_policy_v2_patch;
 mod set_config_policy_v2;
+mod set_tier_registry_entry_v2;
 mod submit_job_output;
 mod submit_validation;
+mod update_node_registration;
 
 use crate::macros::impl_instruction_data;
+pub use activate_config_policy_v2_patches::*;
 pub use append_data::*;
+pub use append_data_v1::*;
+pub use apply_node_penalty_v2::*;
+pub use approve_config_policy_v2_proposal::*;
 pub use cancel_bundle::*;
+pub use cancel_config_policy_v2_proposal::*;
+pub use cancel_scheduled_config_policy_v2_patch::*;
+pub use claim_requester_refund_v2::*;
 pub use claim_verifier_lstake_v2::*;
 pub use claim_winner_lstake_v2::*;
 pub use close_bid::*;
 pub use close_request::*;
 pub use commit_auction_settlement_v2::*;
+pub use commit_runner_ups_v2::*;
+pub use deregister_node::*;
 pub use end_auction::*;
+pub use execute_config_policy_v2_proposal::*;
 pub use expire_bundle_escrow_v2::*;
 pub use finalize_bundle_verification_v2::*;
 pub use init_bundle::*;
+pub use init_bundle_refund_page_v2::*;
 #[cfg(feature = "global-config")]
 pub use init_config::*;
 pub use init_bundle_verifier_page_v2::*;
 pub use init_config_policy_v2::*;
+pub use init_config_policy_v2_schedule::*;
+pub use init_node_penalty_record::*;
+pub use init_node_reputation_v2::*;
+pub use init_tier_registry_v2::*;
 pub use open_bundle_escrow_v2::*;
 pub use place_bid::*;
 pub use post_bundle_result_v2::*;
+pub use propose_config_policy_v2::*;
+pub use reassign_winner_v2::*;
+pub use register_node::*;
 pub use request_job::*;
 pub use reveal_bid::*;
+pub use schedule_config_policy_v2_patch::*;
 pub use set_config_policy_v2::*;
+pub use set_tier_registry_entry_v2::*;
 pub use submit_job_output::*;
 pub use submit_validation::*;
+pub use update_node_registration::*;
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
 #[repr(u8)]
@@ -79,6 +122,80 @@ pub enum AuctionInstruction {
     InitConfigPolicyV2 = 19,
     SetConfigPolicyV2 = 20,
     InitBundleVerifierPageV2 = 21,
+    InitTierRegistryV2 = 22,
+    SetTierRegistryEntryV2 = 23,
+    ProposeConfigPolicyV2 = 24,
+    ApproveConfigPolicyV2Proposal = 25,
+    ExecuteConfigPolicyV2Proposal = 26,
+    CancelConfigPolicyV2Proposal = 27,
+    InitConfigPolicyV2Schedule = 28,
+    ScheduleConfigPolicyV2Patch = 29,
+    CancelScheduledConfigPolicyV2Patch = 30,
+    ActivateConfigPolicyV2Patches = 31,
+    InitBundleRefundPageV2 = 32,
+    ClaimRequesterRefundV2 = 33,
+    CommitRunnerUpsV2 = 34,
+    ReassignWinnerV2 = 35,
+    InitNodePenaltyRecord = 36,
+    ApplyNodePenaltyV2 = 37,
+    InitNodeReputationV2 = 38,
+    RegisterNode = 39,
+    UpdateNodeRegistration = 40,
+    DeregisterNode = 41,
+    AppendDataV1 = 42,
+}
+
+impl AuctionInstruction {
+    /// The group that pauses this instruction, or `None` for governance instructions that
+    /// stay available while the protocol is paused.
+    pub const fn pause_group(self) -> Option<ConfigPolicyV2PauseGroup> {
+        match self {
+            Self::RequestJob
+            | Self::PlaceBid
+            | Self::InitBundle
+            | Self::AppendData
+            | Self::AppendDataV1
+            | Self::OpenBundleEscrowV2
+            | Self::RegisterNode
+            | Self::UpdateNodeRegistration => Some(ConfigPolicyV2PauseGroup::Intake),
+            Self::EndAuction
+            | Self::RevealBid
+            | Self::SubmitJobOutput
+            | Self::SubmitValidation
+            | Self::CommitAuctionSettlementV2
+            | Self::PostBundleResultV2
+            | Self::InitBundleVerifierPageV2
+            | Self::InitBundleRefundPageV2
+            | Self::CommitRunnerUpsV2
+            | Self::ReassignWinnerV2
+            | Self::InitNodePenaltyRecord
+            | Self::ApplyNodePenaltyV2
+            | Self::InitNodeReputationV2
+            | Self::FinalizeBundleVerificationV2 => Some(ConfigPolicyV2PauseGroup::Settlement),
+            Self::CloseBid
+            | Self::CloseRequest
+            | Self::CancelBundle
+            | Self::ClaimWinnerLstakeV2
+            | Self::ClaimVerifierLstakeV2
+            | Self::ClaimRequesterRefundV2
+            | Self::ExpireBundleEscrowV2
+            | Self::DeregisterNode => Some(ConfigPolicyV2PauseGroup::Payout),
+            #[cfg(feature = "global-config")]
+            Self::InitConfig => None,
+            Self::InitConfigPolicyV2
+            | Self::SetConfigPolicyV2
+            | Self::InitTierRegistryV2
+            | Self::SetTierRegistryEntryV2
+            | Self::ProposeConfigPolicyV2
+            | Self::ApproveConfigPolicyV2Proposal
+            | Self::ExecuteConfigPolicyV2Proposal
+            | Self::CancelConfigPolicyV2Proposal
+            | Self::InitConfigPolicyV2Schedule
+            | Self::ScheduleConfigPolicyV2Patch
+            | Self::CancelScheduledConfigPolicyV2Patch
+            | Self::ActivateConfigPolicyV2Patches => None,
+        }
+    }
 }
 
 #[derive(Clone, Copy, Zeroable, PartialEq, Eq, Debug)]
@@ -162,6 +279,27 @@ impl_instruction_data!(
     InitConfigPolicyV2Args => InitConfigPolicyV2,
     SetConfigPolicyV2Args => SetConfigPolicyV2,
     InitBundleVerifierPageV2Args => InitBundleVerifierPageV2,
+    InitTierRegistryV2Args => InitTierRegistryV2,
+    SetTierRegistryEntryV2Args => SetTierRegistryEntryV2,
+    ProposeConfigPolicyV2Args => ProposeConfigPolicyV2,
+    ApproveConfigPolicyV2ProposalArgs => ApproveConfigPolicyV2Proposal,
+    ExecuteConfigPolicyV2ProposalArgs => ExecuteConfigPolicyV2Proposal,
+    CancelConfigPolicyV2ProposalArgs => CancelConfigPolicyV2Proposal,
+    InitConfigPolicyV2ScheduleArgs => InitConfigPolicyV2Schedule,
+    ScheduleConfigPolicyV2PatchArgs => ScheduleConfigPolicyV2Patch,
+    CancelScheduledConfigPolicyV2PatchArgs => CancelScheduledConfigPolicyV2Patch,
+    ActivateConfigPolicyV2PatchesArgs => ActivateConfigPolicyV2Patches,
+    InitBundleRefundPageV2Args => InitBundleRefundPageV2,
+    ClaimRequesterRefundV2Args => ClaimRequesterRefundV2,
+    CommitRunnerUpsV2Args => CommitRunnerUpsV2,
+    ReassignWinnerV2Args => ReassignWinnerV2,
+    InitNodePenaltyRecordArgs => InitNodePenaltyRecord,
+    ApplyNodePenaltyV2Args => ApplyNodePenaltyV2,
+    InitNodeReputationV2Args => InitNodeReputationV2,
+    RegisterNodeArgs => RegisterNode,
+    UpdateNodeRegistrationArgs => UpdateNodeRegistration,
+    DeregisterNodeArgs => DeregisterNode,
+    AppendDataV1Args => AppendDataV1,
 );
 
 #[cfg(feature = "global-config")]
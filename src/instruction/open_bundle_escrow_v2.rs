@@ -9,6 +9,11 @@ pub struct OpenBundleEscrowV2Accounts<'a, T> {
     pub bundle_escrow: &'a T,
     pub config_policy: &'a T,
     pub system_program: &'a T,
+    /// Token escrow accounts, set by [`Self::try_from_token_escrow`].
+    pub token_program: Option<&'a T>,
+    pub token_mint: Option<&'a T>,
+    pub escrow_vault: Option<&'a T>,
+    pub payer_token_account: Option<&'a T>,
 }
 
 impl<'a, T> TryFrom<&'a [T]> for OpenBundleEscrowV2Accounts<'a, T> {
@@ -24,6 +29,33 @@ impl<'a, T> TryFrom<&'a [T]> for OpenBundleEscrowV2Accounts<'a, T> {
             bundle_escrow,
             config_policy,
             system_program,
+            token_program: None,
+            token_mint: None,
+            escrow_vault: None,
+            payer_token_account: None,
+        })
+    }
+}
+
+impl<'a, T> OpenBundleEscrowV2Accounts<'a, T> {
+    /// Parses the accounts of an SPL-token escrow, whose token accounts follow
+    /// `system_program`. Used when [`OpenBundleEscrowV2Args::token_escrow`] is set.
+    pub fn try_from_token_escrow(accounts: &'a [T]) -> Result<Self, AuctionError> {
+        let [payer, bundle_escrow, config_policy, system_program, token_program, token_mint, escrow_vault, payer_token_account, ..] =
+            accounts
+        else {
+            return Err(AuctionError::NotEnoughAccounts);
+        };
+
+        Ok(Self {
+            payer,
+            bundle_escrow,
+            config_policy,
+            system_program,
+            token_program: Some(token_program),
+            token_mint: Some(token_mint),
+            escrow_vault: Some(escrow_vault),
+            payer_token_account: Some(payer_token_account),
         })
     }
 }
@@ -34,6 +66,10 @@ pub struct OpenBundleEscrowV2AccountKeys<T> {
     pub bundle_escrow: T,
     pub config_policy: T,
     pub system_program: T,
+    pub token_program: Option<T>,
+    pub token_mint: Option<T>,
+    pub escrow_vault: Option<T>,
+    pub payer_token_account: Option<T>,
 }
 
 impl<T> OpenBundleEscrowV2AccountKeys<T> {
@@ -43,6 +79,10 @@ impl<T> OpenBundleEscrowV2AccountKeys<T> {
             bundle_escrow: &self.bundle_escrow,
             config_policy: &self.config_policy,
             system_program: &self.system_program,
+            token_program: self.token_program.as_ref(),
+            token_mint: self.token_mint.as_ref(),
+            escrow_vault: self.escrow_vault.as_ref(),
+            payer_token_account: self.payer_token_account.as_ref(),
         }
     }
 }
@@ -56,6 +96,10 @@ where
             .chain(std::iter::once(&self.bundle_escrow))
             .chain(std::iter::once(&self.config_policy))
             .chain(std::iter::once(&self.system_program))
+            .chain(&self.token_program)
+            .chain(&self.token_mint)
+            .chain(&self.escrow_vault)
+            .chain(&self.payer_token_account)
     }
 }
 
@@ -69,6 +113,10 @@ where
             bundle_escrow: self.bundle_escrow.clone(),
             config_policy: self.config_policy.clone(),
             system_program: self.system_program.clone(),
+            token_program: self.token_program.cloned(),
+            token_mint: self.token_mint.cloned(),
+            escrow_vault: self.escrow_vault.cloned(),
+            payer_token_account: self.payer_token_account.cloned(),
         }
     }
 }
@@ -79,6 +127,10 @@ impl<'a, T> InstructionAccounts<'a, T> for OpenBundleEscrowV2Accounts<'a, T> {
             .chain(std::iter::once(self.bundle_escrow))
             .chain(std::iter::once(self.config_policy))
             .chain(std::iter::once(self.system_program))
+            .chain(self.token_program)
+            .chain(self.token_mint)
+            .chain(self.escrow_vault)
+            .chain(self.payer_token_account)
     }
 }
 
@@ -87,7 +139,10 @@ impl<'a, T> InstructionAccounts<'a, T> for OpenBundleEscrowV2Accounts<'a, T> {
 #[repr(C)]
 pub struct OpenBundleEscrowV2Args {
     pub bundle_version: u32,
-    pub _reserved0: [u8; 4],
+    /// Non-zero opens an escrow denominated in the token of `token_mint`, with
+    /// `escrow_lamports` counted in its base units. Needs a V4 or later escrow layout.
+    pub token_escrow: u8,
+    pub _reserved0: [u8; 3],
     pub reward_tier: u64,
     pub bundle_hash: [u8; 32],
     pub coordinator: [u8; PUBKEY_BYTES],
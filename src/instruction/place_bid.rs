@@ -13,6 +13,8 @@ use bytemuck::{Pod, Zeroable};
 /// 1. `[WRITE]` New bid account
 /// 2. `[WRITE]` Auction account
 /// 3. `[READ]` System program
+/// 4. `[READ]` Optional node registration of the bid authority. When present, the endpoint
+///    fields of the args and of the bid stay zeroed and the endpoint is read from it.
 #[derive(Debug, Clone)]
 #[repr(C)]
 pub struct PlaceBidAccounts<'a, T> {
@@ -20,12 +22,13 @@ pub struct PlaceBidAccounts<'a, T> {
     pub bid: &'a T,
     pub auction: &'a T,
     pub system_program: &'a T,
+    pub node_registration: Option<&'a T>,
 }
 
 impl<'a, T> TryFrom<&'a [T]> for PlaceBidAccounts<'a, T> {
     type Error = AuctionError;
     fn try_from(accounts: &'a [T]) -> Result<Self, Self::Error> {
-        let [payer, bid, auction, system_program, ..] = accounts else {
+        let [payer, bid, auction, system_program, rest @ ..] = accounts else {
             return Err(Self::Error::NotEnoughAccounts);
         };
         Ok(Self {
@@ -33,6 +36,7 @@ impl<'a, T> TryFrom<&'a [T]> for PlaceBidAccounts<'a, T> {
             bid,
             auction,
             system_program,
+            node_registration: rest.first(),
         })
     }
 }
@@ -43,6 +47,7 @@ pub struct PlaceBidAccountKeys<T> {
     pub bid: T,
     pub auction: T,
     pub system_program: T,
+    pub node_registration: Option<T>,
 }
 
 impl<T> PlaceBidAccountKeys<T> {
@@ -52,6 +57,7 @@ impl<T> PlaceBidAccountKeys<T> {
             bid: &self.bid,
             auction: &self.auction,
             system_program: &self.system_program,
+            node_registration: self.node_registration.as_ref(),
         }
     }
 }
@@ -65,6 +71,7 @@ where
             .chain(std::iter::once(&self.bid))
             .chain(std::iter::once(&self.auction))
             .chain(std::iter::once(&self.system_program))
+            .chain(self.node_registration.as_ref())
     }
 }
 
@@ -78,6 +85,7 @@ where
             bid: self.bid.clone(),
             auction: self.auction.clone(),
             system_program: self.system_program.clone(),
+            node_registration: self.node_registration.cloned(),
         }
     }
 }
@@ -88,6 +96,7 @@ impl<'a, T> InstructionAccounts<'a, T> for PlaceBidAccounts<'a, T> {
             .chain(std::iter::once(self.bid))
             .chain(std::iter::once(self.auction))
             .chain(std::iter::once(self.system_program))
+            .chain(self.node_registration)
     }
 }
 #[derive(Pod, Clone, Copy, Zeroable, PartialEq, Eq, Debug)]
@@ -126,4 +135,16 @@ impl PlaceBidArgs {
             pad: [0; 10],
         }
     }
+
+    /// Args for a bid whose endpoint is read from the authority's node registration.
+    pub fn new_with_registration(price_hash: [u8; 32], authority: [u8; PUBKEY_BYTES]) -> Self {
+        Self::new(price_hash, authority, IpAddr::default(), 0, None)
+    }
+
+    /// Whether the endpoint fields are zeroed, as required with a node registration.
+    pub fn uses_node_registration(&self) -> bool {
+        self.ip == IpAddr::default()
+            && self.port == 0
+            && self.encryption_node_public_key == [0; PUBKEY_BYTES]
+    }
 }
@@ -34,7 +34,7 @@ impl<'a, T> InstructionAccounts<'a, T> for PostBundleResultV2Accounts<'a, T> {
         std::iter::once(self.authority)
             .chain(std::iter::once(self.bundle_escrow))
             .chain(std::iter::once(self.config_policy))
-            .chain(self.bundle_verifier_page.into_iter())
+            .chain(self.bundle_verifier_page)
     }
 }
 
@@ -0,0 +1,48 @@
+use crate::error::AuctionError;
+use crate::{InstructionAccounts, SetConfigPolicyV2Args};
+use bytemuck::{Pod, Zeroable};
+
+#[derive(Debug, Clone)]
+#[repr(C)]
+pub struct ProposeConfigPolicyV2Accounts<'a, T> {
+    pub proposer: &'a T,
+    pub config_policy: &'a T,
+    pub proposal: &'a T,
+    pub system_program: &'a T,
+}
+
+impl<'a, T> TryFrom<&'a [T]> for ProposeConfigPolicyV2Accounts<'a, T> {
+    type Error = AuctionError;
+
+    fn try_from(accounts: &'a [T]) -> Result<Self, Self::Error> {
+        let [proposer, config_policy, proposal, system_program, ..] = accounts else {
+            return Err(Self::Error::NotEnoughAccounts);
+        };
+
+        Ok(Self {
+            proposer,
+            config_policy,
+            proposal,
+            system_program,
+        })
+    }
+}
+
+impl<'a, T> InstructionAccounts<'a, T> for ProposeConfigPolicyV2Accounts<'a, T> {
+    fn iter(&'a self) -> impl Iterator<Item = &'a T> {
+        std::iter::once(self.proposer)
+            .chain(std::iter::once(self.config_policy))
+            .chain(std::iter::once(self.proposal))
+            .chain(std::iter::once(self.system_program))
+    }
+}
+
+#[derive(Pod, Clone, Copy, Zeroable, PartialEq, Eq, Debug)]
+#[repr(C)]
+pub struct ProposeConfigPolicyV2Args {
+    pub proposal_lamports: u64,
+    /// Proposal PDA seed, unique per config policy.
+    pub proposal_id: u64,
+    pub expiry_slot: u64,
+    pub patch: SetConfigPolicyV2Args,
+}
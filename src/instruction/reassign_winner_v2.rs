@@ -0,0 +1,47 @@
+use crate::error::AuctionError;
+use crate::InstructionAccounts;
+use bytemuck::{Pod, Zeroable};
+
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct ReassignWinnerV2Accounts<'a, T> {
+    pub caller: &'a T,
+    pub bundle_escrow: &'a T,
+    pub config_policy: &'a T,
+    /// Must match the promoted runner-up's `vote_account`.
+    pub new_winner_vote_account: &'a T,
+}
+
+impl<'a, T> TryFrom<&'a [T]> for ReassignWinnerV2Accounts<'a, T> {
+    type Error = AuctionError;
+
+    fn try_from(accounts: &'a [T]) -> Result<Self, Self::Error> {
+        let [caller, bundle_escrow, config_policy, new_winner_vote_account, ..] = accounts else {
+            return Err(Self::Error::NotEnoughAccounts);
+        };
+
+        Ok(Self {
+            caller,
+            bundle_escrow,
+            config_policy,
+            new_winner_vote_account,
+        })
+    }
+}
+
+impl<'a, T> InstructionAccounts<'a, T> for ReassignWinnerV2Accounts<'a, T> {
+    fn iter(&'a self) -> impl Iterator<Item = &'a T> {
+        std::iter::once(self.caller)
+            .chain(std::iter::once(self.bundle_escrow))
+            .chain(std::iter::once(self.config_policy))
+            .chain(std::iter::once(self.new_winner_vote_account))
+    }
+}
+
+/// Promotes the next runner-up once the winner missed `result_deadline_slot`. Permissionless;
+/// the result window restarts with the escrow's snapshotted `result_window_slots`.
+#[derive(Pod, Clone, Copy, Zeroable, PartialEq, Eq, Debug)]
+#[repr(C)]
+pub struct ReassignWinnerV2Args {
+    pub _reserved: [u8; 8],
+}
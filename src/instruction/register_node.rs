@@ -0,0 +1,52 @@
+use crate::error::AuctionError;
+use crate::state::NodeRegistrationInfo;
+use crate::InstructionAccounts;
+use bytemuck::{Pod, Zeroable};
+
+/// RegisterNode instruction
+///
+/// Creates the [`NodeRegistration`](crate::NodeRegistration) of the signing node authority.
+///
+/// # Account References:
+///
+/// 0. `[WRITE, SIGNER]` Node authority, funds the registration
+/// 1. `[WRITE]` New node registration account
+/// 2. `[READ]` System program
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct RegisterNodeAccounts<'a, T> {
+    pub node_authority: &'a T,
+    pub node_registration: &'a T,
+    pub system_program: &'a T,
+}
+
+impl<'a, T> TryFrom<&'a [T]> for RegisterNodeAccounts<'a, T> {
+    type Error = AuctionError;
+
+    fn try_from(accounts: &'a [T]) -> Result<Self, Self::Error> {
+        let [node_authority, node_registration, system_program, ..] = accounts else {
+            return Err(Self::Error::NotEnoughAccounts);
+        };
+
+        Ok(Self {
+            node_authority,
+            node_registration,
+            system_program,
+        })
+    }
+}
+
+impl<'a, T> InstructionAccounts<'a, T> for RegisterNodeAccounts<'a, T> {
+    fn iter(&'a self) -> impl Iterator<Item = &'a T> {
+        std::iter::once(self.node_authority)
+            .chain(std::iter::once(self.node_registration))
+            .chain(std::iter::once(self.system_program))
+    }
+}
+
+#[derive(Pod, Clone, Copy, Zeroable, PartialEq, Eq, Debug)]
+#[repr(C)]
+pub struct RegisterNodeArgs {
+    pub info: NodeRegistrationInfo,
+    pub node_registration_lamports: u64,
+}
@@ -320,5 +320,6 @@ pub struct RequestJobArgs {
     pub bump: u64,
     /// lamports to initialize the new auction with
     pub new_auction_lamports: u64,
+    /// Data account holding the input, or the head of its linked data accounts
     pub input_data_account: MaybePubkey,
 }
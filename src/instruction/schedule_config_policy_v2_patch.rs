@@ -0,0 +1,42 @@
+use crate::error::AuctionError;
+use crate::{InstructionAccounts, SetConfigPolicyV2Args};
+use bytemuck::{Pod, Zeroable};
+
+#[derive(Debug, Clone)]
+#[repr(C)]
+pub struct ScheduleConfigPolicyV2PatchAccounts<'a, T> {
+    pub authority: &'a T,
+    pub config_policy: &'a T,
+    pub schedule: &'a T,
+}
+
+impl<'a, T> TryFrom<&'a [T]> for ScheduleConfigPolicyV2PatchAccounts<'a, T> {
+    type Error = AuctionError;
+
+    fn try_from(accounts: &'a [T]) -> Result<Self, Self::Error> {
+        let [authority, config_policy, schedule, ..] = accounts else {
+            return Err(Self::Error::NotEnoughAccounts);
+        };
+
+        Ok(Self {
+            authority,
+            config_policy,
+            schedule,
+        })
+    }
+}
+
+impl<'a, T> InstructionAccounts<'a, T> for ScheduleConfigPolicyV2PatchAccounts<'a, T> {
+    fn iter(&'a self) -> impl Iterator<Item = &'a T> {
+        std::iter::once(self.authority)
+            .chain(std::iter::once(self.config_policy))
+            .chain(std::iter::once(self.schedule))
+    }
+}
+
+#[derive(Pod, Clone, Copy, Zeroable, PartialEq, Eq, Debug)]
+#[repr(C)]
+pub struct ScheduleConfigPolicyV2PatchArgs {
+    pub activation_slot: u64,
+    pub patch: SetConfigPolicyV2Args,
+}
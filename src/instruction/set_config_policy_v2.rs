@@ -1,5 +1,8 @@
 use crate::error::AuctionError;
-use crate::{ConfigPolicyV2Flags, InstructionAccounts, Pubkey, RequestTierConfigV2};
+use crate::{
+    ConfigPolicyV2Flags, ConfigPolicyV2PauseGroups, InstructionAccounts, Pubkey, RequestTier,
+    RequestTierConfigV2,
+};
 use bytemuck::{Pod, Zeroable};
 
 #[derive(Debug, Clone)]
@@ -40,6 +43,10 @@ impl ConfigPolicyV2PatchKind {
     pub const VERIFIER_SETTINGS: Self = Self(2);
     pub const TIER_CONFIG: Self = Self(3);
     pub const MAX_AUCTION_CREDITS_PER_UPDATE: Self = Self(4);
+    pub const ADMIN_APPROVAL_THRESHOLD: Self = Self(5);
+    pub const PAUSED_INSTRUCTION_GROUPS: Self = Self(6);
+    pub const PROTOCOL_FEE: Self = Self(7);
+    pub const NODE_PENALTIES: Self = Self(8);
 }
 
 #[derive(Pod, Clone, Copy, Zeroable, PartialEq, Eq, Debug)]
@@ -51,6 +58,12 @@ impl ConfigPolicyV2AuthorityKind {
     pub const SERVICE: Self = Self(1);
 }
 
+/// Instruction args of every config policy patch kind.
+///
+/// The size is fixed, so patch kinds added after the original layout reuse its bytes:
+/// `admin_approval_threshold` and `paused_instruction_groups` sit in formerly reserved bytes,
+/// and `PROTOCOL_FEE` and `NODE_PENALTIES` store a [`ConfigPolicyV2FeeArgs`] over the
+/// `tier_config` bytes, see [`Self::fee_args`].
 #[derive(Pod, Clone, Copy, Zeroable, PartialEq, Eq, Debug)]
 #[repr(C)]
 pub struct SetConfigPolicyV2Args {
@@ -59,10 +72,247 @@ pub struct SetConfigPolicyV2Args {
     pub authority_index: u8,
     pub v2_verifiers_per_auction: u8,
     pub v2_verifier_quorum: u8,
-    pub _reserved0: [u8; 3],
+    pub admin_approval_threshold: u8,
+    pub paused_instruction_groups: ConfigPolicyV2PauseGroups,
+    pub _reserved0: [u8; 1],
     pub tier: u64,
     pub policy_flags: ConfigPolicyV2Flags,
     pub max_auction_credits_per_update: u64,
     pub authority: Pubkey,
     pub tier_config: RequestTierConfigV2,
 }
+
+/// Protocol fee and node penalty values, stored over [`SetConfigPolicyV2Args::tier_config`].
+#[derive(Pod, Clone, Copy, Zeroable, PartialEq, Eq, Debug)]
+#[repr(C)]
+pub struct ConfigPolicyV2FeeArgs {
+    pub protocol_fee_recipient: Pubkey,
+    pub protocol_min_fee_lamports: u64,
+    pub protocol_fee_bps: u16,
+    pub rejected_result_penalty_bps: u16,
+    pub missed_result_penalty_bps: u16,
+    pub missed_verification_penalty_bps: u16,
+    pub _reserved0: [u8; 48],
+}
+
+const _: () = assert!(
+    std::mem::size_of::<ConfigPolicyV2FeeArgs>() == std::mem::size_of::<RequestTierConfigV2>()
+);
+
+impl SetConfigPolicyV2Args {
+    /// The `tier_config` bytes read as fee args, for the `PROTOCOL_FEE` and `NODE_PENALTIES`
+    /// patch kinds.
+    pub fn fee_args(&self) -> ConfigPolicyV2FeeArgs {
+        bytemuck::cast(self.tier_config)
+    }
+
+    pub fn set_fee_args(&mut self, fee_args: ConfigPolicyV2FeeArgs) {
+        self.tier_config = bytemuck::cast(fee_args);
+    }
+}
+
+/// Typed form of [`SetConfigPolicyV2Args`].
+///
+/// Each variant carries only the fields its patch kind reads, so encoding always zeroes the
+/// rest and decoding rejects args with stray values in unused fields.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConfigPolicyV2Patch {
+    Flags(ConfigPolicyV2Flags),
+    /// Writes `authority` into slot `index`. The default pubkey clears the slot.
+    Authority {
+        kind: ConfigPolicyV2AuthorityKind,
+        index: u8,
+        authority: Pubkey,
+    },
+    VerifierSettings {
+        v2_verifiers_per_auction: u8,
+        v2_verifier_quorum: u8,
+    },
+    TierConfig {
+        tier: RequestTier,
+        tier_config: RequestTierConfigV2,
+    },
+    MaxAuctionCreditsPerUpdate(u64),
+    AdminApprovalThreshold(u8),
+    /// Replaces the whole set of paused instruction groups.
+    PausedInstructionGroups(ConfigPolicyV2PauseGroups),
+    /// Replaces the protocol fee withheld from V2 winner payouts. A non-zero fee needs a
+    /// recipient.
+    ProtocolFee {
+        protocol_fee_bps: u16,
+        protocol_min_fee_lamports: u64,
+        protocol_fee_recipient: Pubkey,
+    },
+    /// Replaces the node penalties, each at most 10 000 bps.
+    NodePenalties {
+        rejected_result_penalty_bps: u16,
+        missed_result_penalty_bps: u16,
+        missed_verification_penalty_bps: u16,
+    },
+}
+
+impl ConfigPolicyV2Patch {
+    /// Whether the patch changes values that open escrows were priced and timed under, in
+    /// which case it should be scheduled through a `ConfigPolicyV2Schedule` rather than
+    /// applied immediately.
+    pub fn affects_in_flight_escrows(&self) -> bool {
+        matches!(
+            self,
+            Self::VerifierSettings { .. } | Self::TierConfig { .. }
+        )
+    }
+
+    pub fn kind(&self) -> ConfigPolicyV2PatchKind {
+        match self {
+            Self::Flags(_) => ConfigPolicyV2PatchKind::FLAGS,
+            Self::Authority { .. } => ConfigPolicyV2PatchKind::AUTHORITY,
+            Self::VerifierSettings { .. } => ConfigPolicyV2PatchKind::VERIFIER_SETTINGS,
+            Self::TierConfig { .. } => ConfigPolicyV2PatchKind::TIER_CONFIG,
+            Self::MaxAuctionCreditsPerUpdate(_) => {
+                ConfigPolicyV2PatchKind::MAX_AUCTION_CREDITS_PER_UPDATE
+            }
+            Self::AdminApprovalThreshold(_) => ConfigPolicyV2PatchKind::ADMIN_APPROVAL_THRESHOLD,
+            Self::PausedInstructionGroups(_) => ConfigPolicyV2PatchKind::PAUSED_INSTRUCTION_GROUPS,
+            Self::ProtocolFee { .. } => ConfigPolicyV2PatchKind::PROTOCOL_FEE,
+            Self::NodePenalties { .. } => ConfigPolicyV2PatchKind::NODE_PENALTIES,
+        }
+    }
+
+    pub fn to_args(&self) -> SetConfigPolicyV2Args {
+        let mut args = SetConfigPolicyV2Args {
+            patch_kind: self.kind(),
+            ..SetConfigPolicyV2Args::zeroed()
+        };
+
+        match *self {
+            Self::Flags(policy_flags) => args.policy_flags = policy_flags,
+            Self::Authority {
+                kind,
+                index,
+                authority,
+            } => {
+                args.authority_kind = kind;
+                args.authority_index = index;
+                args.authority = authority;
+            }
+            Self::VerifierSettings {
+                v2_verifiers_per_auction,
+                v2_verifier_quorum,
+            } => {
+                args.v2_verifiers_per_auction = v2_verifiers_per_auction;
+                args.v2_verifier_quorum = v2_verifier_quorum;
+            }
+            Self::TierConfig { tier, tier_config } => {
+                args.tier = tier.into();
+                args.tier_config = tier_config;
+            }
+            Self::MaxAuctionCreditsPerUpdate(max_auction_credits_per_update) => {
+                args.max_auction_credits_per_update = max_auction_credits_per_update;
+            }
+            Self::AdminApprovalThreshold(admin_approval_threshold) => {
+                args.admin_approval_threshold = admin_approval_threshold;
+            }
+            Self::PausedInstructionGroups(paused_instruction_groups) => {
+                args.paused_instruction_groups = paused_instruction_groups;
+            }
+            Self::ProtocolFee {
+                protocol_fee_bps,
+                protocol_min_fee_lamports,
+                protocol_fee_recipient,
+            } => args.set_fee_args(ConfigPolicyV2FeeArgs {
+                protocol_fee_recipient,
+                protocol_min_fee_lamports,
+                protocol_fee_bps,
+                ..ConfigPolicyV2FeeArgs::zeroed()
+            }),
+            Self::NodePenalties {
+                rejected_result_penalty_bps,
+                missed_result_penalty_bps,
+                missed_verification_penalty_bps,
+            } => args.set_fee_args(ConfigPolicyV2FeeArgs {
+                rejected_result_penalty_bps,
+                missed_result_penalty_bps,
+                missed_verification_penalty_bps,
+                ..ConfigPolicyV2FeeArgs::zeroed()
+            }),
+        }
+
+        args
+    }
+}
+
+impl From<ConfigPolicyV2Patch> for SetConfigPolicyV2Args {
+    fn from(patch: ConfigPolicyV2Patch) -> Self {
+        patch.to_args()
+    }
+}
+
+impl TryFrom<&SetConfigPolicyV2Args> for ConfigPolicyV2Patch {
+    type Error = AuctionError;
+
+    fn try_from(args: &SetConfigPolicyV2Args) -> Result<Self, Self::Error> {
+        let patch = match args.patch_kind {
+            ConfigPolicyV2PatchKind::FLAGS => Self::Flags(args.policy_flags),
+            ConfigPolicyV2PatchKind::AUTHORITY => {
+                if args.authority_kind != ConfigPolicyV2AuthorityKind::ADMIN
+                    && args.authority_kind != ConfigPolicyV2AuthorityKind::SERVICE
+                {
+                    return Err(AuctionError::InvalidConfigPolicyV2Patch);
+                }
+                Self::Authority {
+                    kind: args.authority_kind,
+                    index: args.authority_index,
+                    authority: args.authority,
+                }
+            }
+            ConfigPolicyV2PatchKind::VERIFIER_SETTINGS => Self::VerifierSettings {
+                v2_verifiers_per_auction: args.v2_verifiers_per_auction,
+                v2_verifier_quorum: args.v2_verifier_quorum,
+            },
+            ConfigPolicyV2PatchKind::TIER_CONFIG => Self::TierConfig {
+                tier: RequestTier::try_from(args.tier)
+                    .map_err(|_| AuctionError::InvalidConfigPolicyV2Patch)?,
+                tier_config: args.tier_config,
+            },
+            ConfigPolicyV2PatchKind::MAX_AUCTION_CREDITS_PER_UPDATE => {
+                Self::MaxAuctionCreditsPerUpdate(args.max_auction_credits_per_update)
+            }
+            ConfigPolicyV2PatchKind::ADMIN_APPROVAL_THRESHOLD => {
+                Self::AdminApprovalThreshold(args.admin_approval_threshold)
+            }
+            ConfigPolicyV2PatchKind::PAUSED_INSTRUCTION_GROUPS => {
+                Self::PausedInstructionGroups(args.paused_instruction_groups)
+            }
+            ConfigPolicyV2PatchKind::PROTOCOL_FEE => {
+                let fee_args = args.fee_args();
+                Self::ProtocolFee {
+                    protocol_fee_bps: fee_args.protocol_fee_bps,
+                    protocol_min_fee_lamports: fee_args.protocol_min_fee_lamports,
+                    protocol_fee_recipient: fee_args.protocol_fee_recipient,
+                }
+            }
+            ConfigPolicyV2PatchKind::NODE_PENALTIES => {
+                let fee_args = args.fee_args();
+                Self::NodePenalties {
+                    rejected_result_penalty_bps: fee_args.rejected_result_penalty_bps,
+                    missed_result_penalty_bps: fee_args.missed_result_penalty_bps,
+                    missed_verification_penalty_bps: fee_args.missed_verification_penalty_bps,
+                }
+            }
+            _ => return Err(AuctionError::InvalidConfigPolicyV2Patch),
+        };
+
+        if patch.to_args() != *args {
+            return Err(AuctionError::InvalidConfigPolicyV2Patch);
+        }
+        Ok(patch)
+    }
+}
+
+impl TryFrom<SetConfigPolicyV2Args> for ConfigPolicyV2Patch {
+    type Error = AuctionError;
+
+    fn try_from(args: SetConfigPolicyV2Args) -> Result<Self, Self::Error> {
+        Self::try_from(&args)
+    }
+}
@@ -0,0 +1,54 @@
+use crate::error::AuctionError;
+use crate::{InstructionAccounts, RequestTierConfigV2};
+use bytemuck::{Pod, Zeroable};
+
+#[derive(Debug, Clone)]
+#[repr(C)]
+pub struct SetTierRegistryEntryV2Accounts<'a, T> {
+    pub authority: &'a T,
+    pub config_policy: &'a T,
+    pub tier_registry: &'a T,
+}
+
+impl<'a, T> TryFrom<&'a [T]> for SetTierRegistryEntryV2Accounts<'a, T> {
+    type Error = AuctionError;
+
+    fn try_from(accounts: &'a [T]) -> Result<Self, Self::Error> {
+        let [authority, config_policy, tier_registry, ..] = accounts else {
+            return Err(Self::Error::NotEnoughAccounts);
+        };
+
+        Ok(Self {
+            authority,
+            config_policy,
+            tier_registry,
+        })
+    }
+}
+
+impl<'a, T> InstructionAccounts<'a, T> for SetTierRegistryEntryV2Accounts<'a, T> {
+    fn iter(&'a self) -> impl Iterator<Item = &'a T> {
+        std::iter::once(self.authority)
+            .chain(std::iter::once(self.config_policy))
+            .chain(std::iter::once(self.tier_registry))
+    }
+}
+
+#[derive(Pod, Clone, Copy, Zeroable, PartialEq, Eq, Debug)]
+#[repr(transparent)]
+pub struct TierRegistryEntryActionV2(pub u8);
+
+impl TierRegistryEntryActionV2 {
+    pub const UPSERT: Self = Self(0);
+    pub const REMOVE: Self = Self(1);
+}
+
+#[derive(Pod, Clone, Copy, Zeroable, PartialEq, Eq, Debug)]
+#[repr(C)]
+pub struct SetTierRegistryEntryV2Args {
+    pub action: TierRegistryEntryActionV2,
+    pub _reserved0: [u8; 7],
+    pub tier_id: u64,
+    /// Ignored for `REMOVE`.
+    pub tier_config: RequestTierConfigV2,
+}
@@ -1,5 +1,5 @@
 use crate::error::AuctionError;
-use crate::InstructionAccounts;
+use crate::{is_encrypted, InstructionAccounts};
 use bytemuck::{Pod, Zeroable};
 
 /// SubmitJobOutput instruction
@@ -118,3 +118,13 @@ pub struct SubmitJobOutputArgs {
     pub output_hash_iv: [u8; 16],
     pub encryption_node_publickey: [u8; 32],
 }
+
+impl SubmitJobOutputArgs {
+    pub fn is_merkle_root_encrypted(&self) -> bool {
+        is_encrypted(&self.merkle_root_iv)
+    }
+
+    pub fn is_output_hash_encrypted(&self) -> bool {
+        is_encrypted(&self.output_hash_iv)
+    }
+}
@@ -0,0 +1,47 @@
+use crate::error::AuctionError;
+use crate::state::NodeRegistrationInfo;
+use crate::InstructionAccounts;
+use bytemuck::{Pod, Zeroable};
+
+/// UpdateNodeRegistration instruction
+///
+/// Replaces the registration info and records a heartbeat. Sending the current info again is
+/// a plain heartbeat.
+///
+/// # Account References:
+///
+/// 0. `[SIGNER]` Node authority
+/// 1. `[WRITE]` Node registration account
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct UpdateNodeRegistrationAccounts<'a, T> {
+    pub node_authority: &'a T,
+    pub node_registration: &'a T,
+}
+
+impl<'a, T> TryFrom<&'a [T]> for UpdateNodeRegistrationAccounts<'a, T> {
+    type Error = AuctionError;
+
+    fn try_from(accounts: &'a [T]) -> Result<Self, Self::Error> {
+        let [node_authority, node_registration, ..] = accounts else {
+            return Err(Self::Error::NotEnoughAccounts);
+        };
+
+        Ok(Self {
+            node_authority,
+            node_registration,
+        })
+    }
+}
+
+impl<'a, T> InstructionAccounts<'a, T> for UpdateNodeRegistrationAccounts<'a, T> {
+    fn iter(&'a self) -> impl Iterator<Item = &'a T> {
+        std::iter::once(self.node_authority).chain(std::iter::once(self.node_registration))
+    }
+}
+
+#[derive(Pod, Clone, Copy, Zeroable, PartialEq, Eq, Debug)]
+#[repr(C)]
+pub struct UpdateNodeRegistrationArgs {
+    pub info: NodeRegistrationInfo,
+}
@@ -1,11 +1,25 @@
 extern crate core;
 
+pub mod cid;
 pub mod constant;
+#[cfg(feature = "crypto")]
+pub mod crypto;
+pub mod data_account;
 pub mod error;
 pub mod instruction;
 mod macros;
+pub mod merkle;
 pub mod state;
+pub mod token_ranges;
+pub mod verifier_selection;
 
+pub use crate::cid::*;
 pub use crate::constant::*;
+#[cfg(feature = "crypto")]
+pub use crate::crypto::*;
+pub use crate::data_account::*;
 pub use crate::instruction::*;
+pub use crate::merkle::*;
 pub use crate::state::*;
+pub use crate::token_ranges::*;
+pub use crate::verifier_selection::*;
@@ -0,0 +1,195 @@
+//! The output-token Merkle tree behind `merkle_root` in `SubmitJobOutputArgs` and
+//! `VerificationState`.
+//!
+//! Leaf `i` is `sha256(0x00 || i || token_id || logprob)` with every field little-endian, and
+//! an inner node is `sha256(0x01 || left || right)`. A level with an odd number of nodes
+//! promotes its last node unchanged instead of duplicating it, so no two output lengths share
+//! a root.
+//!
+//! Proofs cover a `[start, end)` range of tokens, such as one verifier's assigned range; a
+//! single-token inclusion proof is the range `[index, index + 1)`.
+//!
+//! The root does not commit to the number of tokens, so the shape of a proof depends on a
+//! leaf count the root can not confirm. [`OutputMerkleProof::verify`] takes the expected
+//! count from the caller, normally the `output_token_count` submitted with the root, and
+//! rejects proofs built for any other count.
+
+use crate::error::AuctionError;
+use bytemuck::{Pod, Zeroable};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+const MERKLE_LEAF_PREFIX: u8 = 0x00;
+const MERKLE_NODE_PREFIX: u8 = 0x01;
+
+/// One generated token as committed to by the output root.
+#[derive(Pod, Clone, Copy, Zeroable, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[repr(C)]
+pub struct OutputToken {
+    pub token_id: u32,
+    pub logprob: f32,
+}
+
+impl OutputToken {
+    /// The leaf hash of this token at position `index` of the output.
+    pub fn leaf_hash(&self, index: u64) -> [u8; 32] {
+        Sha256::new()
+            .chain_update([MERKLE_LEAF_PREFIX])
+            .chain_update(index.to_le_bytes())
+            .chain_update(self.token_id.to_le_bytes())
+            .chain_update(self.logprob.to_le_bytes())
+            .finalize()
+            .into()
+    }
+}
+
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    Sha256::new()
+        .chain_update([MERKLE_NODE_PREFIX])
+        .chain_update(left)
+        .chain_update(right)
+        .finalize()
+        .into()
+}
+
+fn parent_level(nodes: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    nodes
+        .chunks(2)
+        .map(|pair| match pair {
+            [left, right] => node_hash(left, right),
+            [promoted] => *promoted,
+            _ => unreachable!(),
+        })
+        .collect()
+}
+
+/// Every level of the tree, from the leaves up to the root.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OutputMerkleTree {
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl OutputMerkleTree {
+    /// Builds the tree over `tokens`, which must not be empty.
+    pub fn new(tokens: &[OutputToken]) -> Result<Self, AuctionError> {
+        if tokens.is_empty() {
+            return Err(AuctionError::InvalidTokenRanges);
+        }
+
+        let mut levels = vec![tokens
+            .iter()
+            .zip(0u64..)
+            .map(|(token, index)| token.leaf_hash(index))
+            .collect::<Vec<_>>()];
+        while levels.last().unwrap().len() > 1 {
+            let parent = parent_level(levels.last().unwrap());
+            levels.push(parent);
+        }
+        Ok(Self { levels })
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.levels.last().unwrap()[0]
+    }
+
+    pub fn leaf_count(&self) -> u64 {
+        self.levels[0].len() as u64
+    }
+
+    /// Proves the tokens in `[start, end)`. Fails with `InvalidTokenRanges` for an empty range
+    /// or one past the last token.
+    pub fn prove_range(&self, start: u64, end: u64) -> Result<OutputMerkleProof, AuctionError> {
+        if start >= end || end > self.leaf_count() {
+            return Err(AuctionError::InvalidTokenRanges);
+        }
+
+        let (mut low, mut high) = (start as usize, end as usize);
+        let mut siblings = Vec::new();
+        for level in &self.levels[..self.levels.len() - 1] {
+            if low % 2 == 1 {
+                siblings.push(level[low - 1]);
+            }
+            if high % 2 == 1 && high < level.len() {
+                siblings.push(level[high]);
+            }
+            low /= 2;
+            high = high.div_ceil(2);
+        }
+
+        Ok(OutputMerkleProof {
+            leaf_count: self.leaf_count(),
+            start,
+            end,
+            siblings,
+        })
+    }
+
+    /// Proves the single token at `index`.
+    pub fn prove(&self, index: u64) -> Result<OutputMerkleProof, AuctionError> {
+        self.prove_range(index, index.saturating_add(1))
+    }
+}
+
+/// The sibling hashes needed to recompute the root from the tokens in `[start, end)`, ordered
+/// from the leaves up and, within a level, left before right.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct OutputMerkleProof {
+    pub leaf_count: u64,
+    pub start: u64,
+    pub end: u64,
+    pub siblings: Vec<[u8; 32]>,
+}
+
+impl OutputMerkleProof {
+    /// Checks that `tokens` are the tokens at `[start, end)` of the `leaf_count`-token output
+    /// committed to by `root`. Fails if the proof was built for another leaf count.
+    pub fn verify(
+        &self,
+        root: &[u8; 32],
+        leaf_count: u64,
+        tokens: &[OutputToken],
+    ) -> Result<(), AuctionError> {
+        if self.leaf_count != leaf_count
+            || self.start >= self.end
+            || self.end > self.leaf_count
+            || tokens.len() as u64 != self.end - self.start
+        {
+            return Err(AuctionError::InvalidMerkleProof);
+        }
+
+        let mut nodes: Vec<[u8; 32]> = tokens
+            .iter()
+            .zip(self.start..)
+            .map(|(token, index)| token.leaf_hash(index))
+            .collect();
+        let mut siblings = self.siblings.iter();
+        let (mut low, mut high, mut level_len) = (self.start, self.end, self.leaf_count);
+        while level_len > 1 {
+            let mut row = Vec::with_capacity(nodes.len() + 2);
+            if low % 2 == 1 {
+                row.push(*siblings.next().ok_or(AuctionError::InvalidMerkleProof)?);
+            }
+            row.append(&mut nodes);
+            if high % 2 == 1 && high < level_len {
+                row.push(*siblings.next().ok_or(AuctionError::InvalidMerkleProof)?);
+            }
+            nodes = parent_level(&row);
+            low /= 2;
+            high = high.div_ceil(2);
+            level_len = level_len.div_ceil(2);
+        }
+
+        if siblings.next().is_some() || nodes != [*root] {
+            return Err(AuctionError::InvalidMerkleProof);
+        }
+        Ok(())
+    }
+}
+
+/// The output root of `tokens`.
+pub fn output_merkle_root(tokens: &[OutputToken]) -> Result<[u8; 32], AuctionError> {
+    OutputMerkleTree::new(tokens).map(|tree| tree.root())
+}
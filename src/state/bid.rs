@@ -53,6 +53,12 @@ impl Bid {
     pub fn from_bytes<A: AsRef<[u8]>>(bytes: &A) -> Option<&Self> {
         bytemuck::try_from_bytes(bytes.as_ref()).ok()
     }
+
+    /// Whether the endpoint fields are zeroed because the bid was placed with a
+    /// [`NodeRegistration`](crate::NodeRegistration), which then holds the endpoint.
+    pub fn uses_node_registration(&self) -> bool {
+        self.ip == IpAddr::default() && self.port == 0 && self.public_key == [0; 32]
+    }
 }
 
 impl Default for Bid {
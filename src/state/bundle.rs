@@ -3,8 +3,8 @@ use super::{
     Pubkey,
 };
 use crate::constant::PUBKEY_BYTES;
-use crate::state::request_tier::RequestTier;
-use crate::{MaybePubkey, VERIFIERS_PER_AUCTION};
+use crate::state::request_tier::{RequestTier, TierParams};
+use crate::{ConfigPolicyV2, MaybePubkey, VERIFIERS_PER_AUCTION};
 use bytemuck::{offset_of, Pod, Zeroable};
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 #[cfg(feature = "serde")]
@@ -84,6 +84,12 @@ pub struct BundleLayoutTrailerV1 {
     pub reserved: [u8; 6],
 }
 
+impl Default for BundleLayoutTrailerV1 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl BundleLayoutTrailerV1 {
     pub const LEN: usize = std::mem::size_of::<BundleLayoutTrailerV1>();
 
@@ -146,12 +152,17 @@ pub enum BundleDataMut<'a> {
     Canceled(&'a mut RawBundleData),
 }
 
-pub const fn bundle_account_len(version: AccountLayoutVersion) -> usize {
+/// Length of a bundle account with the given layout, `None` for versions bundles do not use.
+pub const fn bundle_account_len(version: AccountLayoutVersion) -> Option<usize> {
     match version {
-        AccountLayoutVersion::LegacyV0 => RawBundleData::LEGACY_LEN,
+        AccountLayoutVersion::LegacyV0 => Some(RawBundleData::LEGACY_LEN),
         AccountLayoutVersion::V1 | AccountLayoutVersion::V2 => {
-            RawBundleData::LEGACY_LEN + BundleLayoutTrailerV1::LEN
+            Some(RawBundleData::LEGACY_LEN + BundleLayoutTrailerV1::LEN)
         }
+        AccountLayoutVersion::V3
+        | AccountLayoutVersion::V4
+        | AccountLayoutVersion::V5
+        | AccountLayoutVersion::V6 => None,
     }
 }
 
@@ -189,15 +200,63 @@ impl RawBundleData {
         current_slot: u64,
         context_length_tier: RequestTier,
         expiry_duration_tier: RequestTier,
+    ) -> Self {
+        Self::new_with_params(
+            payer,
+            parent_bundle_key,
+            bump,
+            current_slot,
+            context_length_tier,
+            expiry_duration_tier,
+            &context_length_tier,
+            &expiry_duration_tier,
+        )
+    }
+
+    /// Same as [`RawBundleData::new`], but reads the bundle duration and context length
+    /// from the tier configs stored in `policy` instead of the compiled-in defaults.
+    pub fn new_with_policy(
+        payer: [u8; PUBKEY_BYTES],
+        parent_bundle_key: [u8; PUBKEY_BYTES],
+        bump: u64,
+        current_slot: u64,
+        context_length_tier: RequestTier,
+        expiry_duration_tier: RequestTier,
+        policy: &ConfigPolicyV2,
+    ) -> Self {
+        Self::new_with_params(
+            payer,
+            parent_bundle_key,
+            bump,
+            current_slot,
+            context_length_tier,
+            expiry_duration_tier,
+            policy.tier_config(context_length_tier),
+            policy.tier_config(expiry_duration_tier),
+        )
+    }
+
+    /// `context_params` and `expiry_params` must describe `context_length_tier` and
+    /// `expiry_duration_tier` respectively.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_with_params<C: TierParams, E: TierParams>(
+        payer: [u8; PUBKEY_BYTES],
+        parent_bundle_key: [u8; PUBKEY_BYTES],
+        bump: u64,
+        current_slot: u64,
+        context_length_tier: RequestTier,
+        expiry_duration_tier: RequestTier,
+        context_params: &C,
+        expiry_params: &E,
     ) -> Self {
         RawBundleData {
             payer: payer.into(),
             parent_bundle_key: parent_bundle_key.into(),
             bump,
-            expiry_slot: current_slot.saturating_add(expiry_duration_tier.get_bundle_duration()),
+            expiry_slot: current_slot.saturating_add(expiry_params.get_bundle_duration()),
             context_length_tier,
             expiry_duration_tier,
-            max_context_length: context_length_tier.get_max_context_length_tokens(),
+            max_context_length: context_params.get_max_context_length_tokens(),
             ..Default::default()
         }
     }
@@ -222,8 +281,18 @@ impl RawBundleData {
     }
 
     pub fn is_expired(&self, slot: u64) -> bool {
-        self.requests_len < self.context_length_tier.get_request_per_bundle()
-            && self.expiry_slot <= slot
+        self.is_expired_with_params(slot, &self.context_length_tier)
+    }
+
+    /// Same as [`RawBundleData::is_expired`], using the policy's config for the
+    /// bundle's context length tier.
+    pub fn is_expired_with_policy(&self, slot: u64, policy: &ConfigPolicyV2) -> bool {
+        self.is_expired_with_params(slot, policy.tier_config(self.context_length_tier))
+    }
+
+    /// `params` must describe the bundle's context length tier.
+    pub fn is_expired_with_params<P: TierParams>(&self, slot: u64, params: &P) -> bool {
+        self.requests_len < params.get_request_per_bundle() && self.expiry_slot <= slot
     }
 
     pub fn write_legacy_bytes(&self, bytes: &mut [u8]) -> bool {
@@ -241,10 +310,30 @@ impl RawBundleData {
     }
 
     pub fn is_expired_from_bytes(bytes: &[u8], slot: u64) -> Option<bool> {
+        let context_len_tier = Self::read_context_len_tier_from_bytes(bytes)?;
+        Self::is_expired_from_bytes_with_params(bytes, slot, &context_len_tier)
+    }
+
+    /// Same as [`RawBundleData::is_expired_from_bytes`], using the policy's config for the
+    /// bundle's context length tier.
+    pub fn is_expired_from_bytes_with_policy(
+        bytes: &[u8],
+        slot: u64,
+        policy: &ConfigPolicyV2,
+    ) -> Option<bool> {
+        let context_len_tier = Self::read_context_len_tier_from_bytes(bytes)?;
+        Self::is_expired_from_bytes_with_params(bytes, slot, policy.tier_config(context_len_tier))
+    }
+
+    /// `params` must describe the bundle's context length tier.
+    pub fn is_expired_from_bytes_with_params<P: TierParams>(
+        bytes: &[u8],
+        slot: u64,
+        params: &P,
+    ) -> Option<bool> {
         let requests_len = Self::read_requests_len_from_bytes(bytes)?;
         let expiry_slot = Self::read_expiry_slot_from_bytes(bytes)?;
-        let context_len_tier = Self::read_context_len_tier_from_bytes(bytes)?;
-        Some(requests_len < context_len_tier.get_request_per_bundle() && expiry_slot <= slot)
+        Some(requests_len < params.get_request_per_bundle() && expiry_slot <= slot)
     }
 
     fn read_expiry_slot_from_bytes(bytes: &[u8]) -> Option<u64> {
@@ -1,8 +1,9 @@
 use super::{
-    AccountDiscriminator, AccountHeaderV1, AccountLayoutVersion, ParsedAccountLayout, Pubkey,
-    RequestTier, CONFIG_POLICY_V2_BUNDLE_ESCROW_RESERVED_BYTES,
+    AccountDiscriminator, AccountHeaderV1, AccountLayoutVersion, ConfigPolicyV2, NodePenaltyKind,
+    ParsedAccountLayout, Pubkey, RequestTier, CONFIG_POLICY_V2_BUNDLE_ESCROW_RESERVED_BYTES,
 };
-use crate::MAX_VERIFIERS_PER_AUCTION;
+use crate::error::AuctionError;
+use crate::{MAX_EXTENDED_VERIFIERS_PER_AUCTION, MAX_VERIFIERS_PER_AUCTION};
 use bytemuck::{Pod, Zeroable};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -21,9 +22,11 @@ pub struct RawBundleEscrowV2Data {
     pub bundle_hash: [u8; 32],
     pub total_input_tokens: u64,
     pub max_output_tokens: u64,
+    /// Escrowed amount in lamports, or in token base units for token escrows.
     pub escrow_lamports: u64,
     pub winner_node_pubkey: Pubkey,
     pub winner_vote_account: Pubkey,
+    /// Priced in the same unit as `escrow_lamports`.
     pub clearing_price_per_output_token: u64,
     pub selected_verifiers: [Pubkey; MAX_VERIFIERS_PER_AUCTION],
     pub auction_hash: [u8; 32],
@@ -31,6 +34,7 @@ pub struct RawBundleEscrowV2Data {
     pub verification_hash: [u8; 32],
     pub posted_output_tokens: u64,
     pub accepted_output_tokens: u64,
+    /// Gross winner payout, in the same unit as `escrow_lamports`.
     pub winner_payout_lamports: u64,
     pub settlement_deadline_slot: u64,
     pub result_deadline_slot: u64,
@@ -40,12 +44,525 @@ pub struct RawBundleEscrowV2Data {
     pub verifier_reward_claimed_bitmap: u8,
     pub quorum_verifier_bitmap: u8,
     pub verifier_page_count: u8,
-    pub _reserved1: [u8; 4],
+    /// `BundleRefundPageV2` accounts holding per-job refunds. Zero refunds everything left
+    /// to `requester_refund_recipient`.
+    pub refund_page_count: u8,
+    /// Nodes already penalised through `ApplyNodePenaltyV2`: the selected verifiers from bit
+    /// 0, the defaulted winners from bit 3 and the current winner in bit 7. V6 escrows keep
+    /// the verifier bits in [`BundleEscrowV2VerifierSet`] instead.
+    pub penalized_node_bitmap: u8,
+    /// Selected verifiers that voted against the final verdict. They are outside the quorum
+    /// but are not penalised for a missed verification. V6 escrows keep them in
+    /// [`BundleEscrowV2VerifierSet`] instead.
+    pub dissenting_verifier_bitmap: u8,
+    pub _reserved1: [u8; 1],
     pub verifier_reward_remaining: [u64; MAX_VERIFIERS_PER_AUCTION],
 }
 
 pub type BundleEscrowV2 = RawBundleEscrowV2Data;
 
+/// The policy values an escrow was opened under, kept at the start of the V2 reserved tail so
+/// later `tier_configs` or quorum changes do not move the deadlines of escrows in flight.
+#[derive(Pod, Clone, Copy, Zeroable, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[repr(C)]
+pub struct BundleEscrowV2PolicySnapshot {
+    pub settlement_window_slots: u64,
+    pub result_window_slots: u64,
+    pub verification_window_slots: u64,
+    pub claim_window_slots: u64,
+    pub v2_verifiers_per_auction: u8,
+    pub v2_verifier_quorum: u8,
+    /// Non-zero once captured. Escrows opened before snapshots existed read as zero.
+    pub captured: u8,
+    pub _reserved0: [u8; 5],
+}
+
+impl BundleEscrowV2PolicySnapshot {
+    pub const LEN: usize = std::mem::size_of::<BundleEscrowV2PolicySnapshot>();
+
+    pub fn capture(policy: &ConfigPolicyV2, tier: RequestTier) -> Self {
+        let tier_config = policy.tier_config(tier);
+        Self {
+            settlement_window_slots: tier_config.settlement_window_slots,
+            result_window_slots: tier_config.result_window_slots,
+            verification_window_slots: tier_config.verification_window_slots,
+            claim_window_slots: tier_config.claim_window_slots,
+            v2_verifiers_per_auction: policy.v2_verifiers_per_auction,
+            v2_verifier_quorum: policy.v2_verifier_quorum,
+            captured: 1,
+            _reserved0: [0; 5],
+        }
+    }
+}
+
+const _: () =
+    assert!(BundleEscrowV2PolicySnapshot::LEN <= CONFIG_POLICY_V2_BUNDLE_ESCROW_RESERVED_BYTES);
+
+/// The SPL token a V4 escrow is denominated in, stored right after the reserved tail.
+///
+/// A default `mint` marks a native escrow, so V4 accounts can also hold lamports.
+#[derive(Pod, Clone, Copy, Zeroable, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[repr(C)]
+pub struct BundleEscrowV2TokenDenomination {
+    pub mint: Pubkey,
+    /// Token account owned by the escrow that holds the escrowed amount.
+    pub vault: Pubkey,
+    /// The SPL Token or Token-2022 program that owns `vault`.
+    pub token_program: Pubkey,
+    pub decimals: u8,
+    pub _reserved0: [u8; 7],
+}
+
+impl BundleEscrowV2TokenDenomination {
+    pub const LEN: usize = std::mem::size_of::<BundleEscrowV2TokenDenomination>();
+
+    /// Checks the vault and token program accounts passed alongside a token escrow.
+    pub fn check_accounts(
+        &self,
+        vault: &Pubkey,
+        token_program: &Pubkey,
+    ) -> Result<(), AuctionError> {
+        if *vault != self.vault || *token_program != self.token_program {
+            return Err(AuctionError::InvalidTokenEscrowAccounts);
+        }
+        Ok(())
+    }
+}
+
+/// What a `BundleEscrowV2` amount is counted in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BundleEscrowV2Denomination {
+    Lamports,
+    Token(BundleEscrowV2TokenDenomination),
+}
+
+impl BundleEscrowV2Denomination {
+    /// The token mint, default for lamports.
+    pub fn mint(&self) -> Pubkey {
+        match self {
+            Self::Lamports => Pubkey::default(),
+            Self::Token(denomination) => denomination.mint,
+        }
+    }
+}
+
+fn read_token_denomination(
+    layout: ParsedAccountLayout,
+    reserved: &[u8],
+) -> Option<BundleEscrowV2TokenDenomination> {
+    if !has_token_denomination(layout.version) {
+        return None;
+    }
+    let denomination = bytemuck::try_pod_read_unaligned::<BundleEscrowV2TokenDenomination>(
+        reserved.get(token_denomination_range())?,
+    )
+    .ok()?;
+    (denomination.mint != Pubkey::default()).then_some(denomination)
+}
+
+fn token_denomination_range() -> std::ops::Range<usize> {
+    CONFIG_POLICY_V2_BUNDLE_ESCROW_RESERVED_BYTES
+        ..CONFIG_POLICY_V2_BUNDLE_ESCROW_RESERVED_BYTES + BundleEscrowV2TokenDenomination::LEN
+}
+
+pub const MAX_BUNDLE_ESCROW_V2_RUNNER_UPS: usize = 3;
+
+/// A revealed bidder that can take over the award if the winner defaults.
+#[derive(Pod, Clone, Copy, Zeroable, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[repr(C)]
+pub struct BundleEscrowV2RunnerUp {
+    pub node_pubkey: Pubkey,
+    pub vote_account: Pubkey,
+    /// Clearing price the escrow switches to when this bidder is promoted.
+    pub clearing_price_per_output_token: u64,
+}
+
+/// Ranked runner-ups of a V5 escrow, stored after the token denomination.
+///
+/// Runner-ups are promoted in order. Every winner replaced by [`BundleEscrowV2Mut::reassign_winner`]
+/// is kept in `defaulted_winners` so it can be penalised.
+#[derive(Pod, Clone, Copy, Zeroable, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[repr(C)]
+pub struct BundleEscrowV2RunnerUps {
+    pub runner_ups: [BundleEscrowV2RunnerUp; MAX_BUNDLE_ESCROW_V2_RUNNER_UPS],
+    pub defaulted_winners: [Pubkey; MAX_BUNDLE_ESCROW_V2_RUNNER_UPS],
+    pub runner_up_count: u8,
+    pub promoted_count: u8,
+    pub _reserved0: [u8; 6],
+}
+
+impl BundleEscrowV2RunnerUps {
+    pub const LEN: usize = std::mem::size_of::<BundleEscrowV2RunnerUps>();
+
+    /// Ranks `runner_ups` behind the awarded winner of `escrow`. Prices must not decrease, must
+    /// not undercut the winner's clearing price and must stay payable from the escrow. Each
+    /// runner-up must be listed once and must not be in `verifier_set`, since a promoted
+    /// runner-up would otherwise verify its own result.
+    pub fn new(
+        escrow: &RawBundleEscrowV2Data,
+        verifier_set: &BundleEscrowV2VerifierSet,
+        runner_ups: &[BundleEscrowV2RunnerUp],
+    ) -> Result<Self, AuctionError> {
+        if runner_ups.len() > MAX_BUNDLE_ESCROW_V2_RUNNER_UPS {
+            return Err(AuctionError::InvalidRunnerUpsV2);
+        }
+
+        let mut previous_price = escrow.clearing_price_per_output_token;
+        for (index, runner_up) in runner_ups.iter().enumerate() {
+            let max_payout = runner_up
+                .clearing_price_per_output_token
+                .checked_mul(escrow.max_output_tokens);
+            if runner_up.node_pubkey == Pubkey::default()
+                || runner_up.node_pubkey == escrow.winner_node_pubkey
+                || verifier_set.position(&runner_up.node_pubkey).is_some()
+                || runner_ups[..index]
+                    .iter()
+                    .any(|earlier| earlier.node_pubkey == runner_up.node_pubkey)
+                || runner_up.clearing_price_per_output_token < previous_price
+                || max_payout.is_none_or(|max_payout| max_payout > escrow.escrow_lamports)
+            {
+                return Err(AuctionError::InvalidRunnerUpsV2);
+            }
+            previous_price = runner_up.clearing_price_per_output_token;
+        }
+
+        let mut ranked = Self {
+            runner_up_count: runner_ups.len() as u8,
+            ..Self::default()
+        };
+        ranked.runner_ups[..runner_ups.len()].copy_from_slice(runner_ups);
+        Ok(ranked)
+    }
+
+    pub fn next_runner_up(&self) -> Option<&BundleEscrowV2RunnerUp> {
+        self.runner_ups[..usize::from(self.runner_up_count)].get(usize::from(self.promoted_count))
+    }
+
+    pub fn defaulted_winners(&self) -> &[Pubkey] {
+        &self.defaulted_winners[..usize::from(self.promoted_count)]
+    }
+}
+
+const PENALIZED_DEFAULTED_WINNER_SHIFT: usize = MAX_VERIFIERS_PER_AUCTION;
+const PENALIZED_WINNER_BIT: u8 = 1 << 7;
+const _: () =
+    assert!(PENALIZED_DEFAULTED_WINNER_SHIFT + MAX_BUNDLE_ESCROW_V2_RUNNER_UPS < u8::BITS as usize);
+
+fn runner_ups_range() -> std::ops::Range<usize> {
+    let start = token_denomination_range().end;
+    start..start + BundleEscrowV2RunnerUps::LEN
+}
+
+const fn has_runner_ups(version: AccountLayoutVersion) -> bool {
+    matches!(version, AccountLayoutVersion::V5 | AccountLayoutVersion::V6)
+}
+
+fn read_runner_ups(
+    layout: ParsedAccountLayout,
+    reserved: &[u8],
+) -> Option<BundleEscrowV2RunnerUps> {
+    if !has_runner_ups(layout.version) {
+        return None;
+    }
+    bytemuck::try_pod_read_unaligned(reserved.get(runner_ups_range())?).ok()
+}
+
+/// How many verifiers a `BundleEscrowV2` or `BundleVerifierPageV2` of `version` can hold.
+pub const fn bundle_v2_verifier_capacity(version: AccountLayoutVersion) -> usize {
+    match version {
+        AccountLayoutVersion::V6 => MAX_EXTENDED_VERIFIERS_PER_AUCTION,
+        _ => MAX_VERIFIERS_PER_AUCTION,
+    }
+}
+
+/// The selected verifiers of an escrow with their rewards and bitmaps.
+///
+/// V6 escrows store it after the runner-ups; earlier layouts keep the first
+/// `MAX_VERIFIERS_PER_AUCTION` slots in the payload fields of the same names.
+#[derive(Pod, Clone, Copy, Zeroable, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[repr(C)]
+pub struct BundleEscrowV2VerifierSet {
+    pub selected_verifiers: [Pubkey; MAX_EXTENDED_VERIFIERS_PER_AUCTION],
+    pub verifier_reward_remaining: [u64; MAX_EXTENDED_VERIFIERS_PER_AUCTION],
+    pub quorum_verifier_bitmap: u32,
+    pub verifier_reward_claimed_bitmap: u32,
+    /// Verifiers already penalised through `ApplyNodePenaltyV2` for a missed verification.
+    pub penalized_verifier_bitmap: u32,
+    /// Verifiers that voted against the final verdict, disjoint from the quorum.
+    pub dissenting_verifier_bitmap: u32,
+}
+
+const _: () = assert!(MAX_EXTENDED_VERIFIERS_PER_AUCTION <= u32::BITS as usize);
+const LEGACY_VERIFIER_MASK: u8 = (1 << MAX_VERIFIERS_PER_AUCTION) - 1;
+
+impl BundleEscrowV2VerifierSet {
+    pub const LEN: usize = std::mem::size_of::<BundleEscrowV2VerifierSet>();
+
+    /// A set holding `selected_verifiers` and nothing else.
+    pub fn new(selected_verifiers: &[Pubkey]) -> Result<Self, AuctionError> {
+        if selected_verifiers.len() > MAX_EXTENDED_VERIFIERS_PER_AUCTION {
+            return Err(AuctionError::InvalidVerifierCount);
+        }
+        let mut set = Self::default();
+        set.selected_verifiers[..selected_verifiers.len()].copy_from_slice(selected_verifiers);
+        Ok(set)
+    }
+
+    /// The number of slots in use, up to and including the last selected verifier.
+    pub fn len(&self) -> usize {
+        self.selected_verifiers
+            .iter()
+            .rposition(|verifier| *verifier != Pubkey::default())
+            .map_or(0, |index| index + 1)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn position(&self, node: &Pubkey) -> Option<usize> {
+        if *node == Pubkey::default() {
+            return None;
+        }
+        self.selected_verifiers
+            .iter()
+            .position(|verifier| verifier == node)
+    }
+
+    pub fn in_quorum(&self, verifier_index: usize) -> bool {
+        verifier_index < MAX_EXTENDED_VERIFIERS_PER_AUCTION
+            && self.quorum_verifier_bitmap & (1 << verifier_index) != 0
+    }
+
+    /// Whether the verifier voted, either in the quorum or against the verdict.
+    pub fn voted(&self, verifier_index: usize) -> bool {
+        verifier_index < MAX_EXTENDED_VERIFIERS_PER_AUCTION
+            && (self.quorum_verifier_bitmap | self.dissenting_verifier_bitmap)
+                & (1 << verifier_index)
+                != 0
+    }
+
+    pub fn total_reward_remaining(&self) -> Option<u64> {
+        self.verifier_reward_remaining
+            .iter()
+            .try_fold(0u64, |total, reward| total.checked_add(*reward))
+    }
+
+    /// Takes `claimed_amount` off the verifier's remaining reward and returns whether it is
+    /// now fully claimed.
+    pub fn claim_reward(&mut self, verifier_index: usize, claimed_amount: u64) -> bool {
+        let remaining = self.verifier_reward_remaining[verifier_index]
+            .checked_sub(claimed_amount)
+            .expect("verifier reward underflow");
+        self.verifier_reward_remaining[verifier_index] = remaining;
+        if remaining == 0 {
+            self.verifier_reward_claimed_bitmap |= 1 << verifier_index;
+        }
+        remaining == 0
+    }
+
+    pub fn all_quorum_rewards_claimed(&self) -> bool {
+        self.verifier_reward_claimed_bitmap & self.quorum_verifier_bitmap
+            == self.quorum_verifier_bitmap
+    }
+
+    /// Whether every slot, reward and bitmap bit in use is below `capacity`.
+    pub fn fits(&self, capacity: usize) -> bool {
+        let capacity = capacity.min(MAX_EXTENDED_VERIFIERS_PER_AUCTION);
+        let outside = u32::MAX.checked_shl(capacity as u32).unwrap_or(0);
+        self.len() <= capacity
+            && self.verifier_reward_remaining[capacity..]
+                .iter()
+                .all(|reward| *reward == 0)
+            && (self.quorum_verifier_bitmap
+                | self.verifier_reward_claimed_bitmap
+                | self.penalized_verifier_bitmap
+                | self.dissenting_verifier_bitmap)
+                & outside
+                == 0
+    }
+
+    fn write_legacy(&self, raw: &mut RawBundleEscrowV2Data) {
+        raw.selected_verifiers
+            .copy_from_slice(&self.selected_verifiers[..MAX_VERIFIERS_PER_AUCTION]);
+        raw.verifier_reward_remaining
+            .copy_from_slice(&self.verifier_reward_remaining[..MAX_VERIFIERS_PER_AUCTION]);
+        raw.quorum_verifier_bitmap = self.quorum_verifier_bitmap as u8;
+        raw.verifier_reward_claimed_bitmap = self.verifier_reward_claimed_bitmap as u8;
+        raw.penalized_node_bitmap = (raw.penalized_node_bitmap & !LEGACY_VERIFIER_MASK)
+            | (self.penalized_verifier_bitmap as u8 & LEGACY_VERIFIER_MASK);
+        raw.dissenting_verifier_bitmap = self.dissenting_verifier_bitmap as u8;
+    }
+}
+
+impl From<&RawBundleEscrowV2Data> for BundleEscrowV2VerifierSet {
+    /// The verifier set of a layout before V6, read from the payload fields.
+    fn from(raw: &RawBundleEscrowV2Data) -> Self {
+        let mut set = Self {
+            quorum_verifier_bitmap: u32::from(raw.quorum_verifier_bitmap),
+            verifier_reward_claimed_bitmap: u32::from(raw.verifier_reward_claimed_bitmap),
+            penalized_verifier_bitmap: u32::from(raw.penalized_node_bitmap & LEGACY_VERIFIER_MASK),
+            dissenting_verifier_bitmap: u32::from(raw.dissenting_verifier_bitmap),
+            ..Self::default()
+        };
+        set.selected_verifiers[..MAX_VERIFIERS_PER_AUCTION]
+            .copy_from_slice(&raw.selected_verifiers);
+        set.verifier_reward_remaining[..MAX_VERIFIERS_PER_AUCTION]
+            .copy_from_slice(&raw.verifier_reward_remaining);
+        set
+    }
+}
+
+fn verifier_set_range() -> std::ops::Range<usize> {
+    let start = runner_ups_range().end;
+    start..start + BundleEscrowV2VerifierSet::LEN
+}
+
+fn read_verifier_set(
+    layout: ParsedAccountLayout,
+    raw: &RawBundleEscrowV2Data,
+    reserved: &[u8],
+) -> BundleEscrowV2VerifierSet {
+    if layout.version != AccountLayoutVersion::V6 {
+        return BundleEscrowV2VerifierSet::from(raw);
+    }
+    bytemuck::pod_read_unaligned(&reserved[verifier_set_range()])
+}
+
+/// Offset of `protocol_fee_lamports` in the V3 reserved tail, right after the policy snapshot.
+pub const BUNDLE_ESCROW_V2_PROTOCOL_FEE_OFFSET: usize = BundleEscrowV2PolicySnapshot::LEN;
+
+const _: () = assert!(
+    BUNDLE_ESCROW_V2_PROTOCOL_FEE_OFFSET + std::mem::size_of::<u64>()
+        <= CONFIG_POLICY_V2_BUNDLE_ESCROW_RESERVED_BYTES
+);
+
+fn protocol_fee_range() -> std::ops::Range<usize> {
+    BUNDLE_ESCROW_V2_PROTOCOL_FEE_OFFSET
+        ..BUNDLE_ESCROW_V2_PROTOCOL_FEE_OFFSET + std::mem::size_of::<u64>()
+}
+
+const fn records_protocol_fee(version: AccountLayoutVersion) -> bool {
+    matches!(
+        version,
+        AccountLayoutVersion::V3
+            | AccountLayoutVersion::V4
+            | AccountLayoutVersion::V5
+            | AccountLayoutVersion::V6
+    )
+}
+
+const fn has_token_denomination(version: AccountLayoutVersion) -> bool {
+    matches!(
+        version,
+        AccountLayoutVersion::V4 | AccountLayoutVersion::V5 | AccountLayoutVersion::V6
+    )
+}
+
+fn read_protocol_fee_lamports(layout: ParsedAccountLayout, reserved: &[u8]) -> Option<u64> {
+    if !records_protocol_fee(layout.version) {
+        return None;
+    }
+    bytemuck::try_pod_read_unaligned(reserved.get(protocol_fee_range())?).ok()
+}
+
+fn read_policy_snapshot(reserved: &[u8]) -> Option<BundleEscrowV2PolicySnapshot> {
+    let snapshot = bytemuck::try_pod_read_unaligned::<BundleEscrowV2PolicySnapshot>(
+        reserved.get(..BundleEscrowV2PolicySnapshot::LEN)?,
+    )
+    .ok()?;
+    (snapshot.captured != 0).then_some(snapshot)
+}
+
+fn read_policy_windows(
+    raw: &RawBundleEscrowV2Data,
+    reserved: &[u8],
+    policy: &ConfigPolicyV2,
+) -> Result<BundleEscrowV2PolicySnapshot, AuctionError> {
+    if let Some(snapshot) = read_policy_snapshot(reserved) {
+        return Ok(snapshot);
+    }
+    let tier = RequestTier::try_from(raw.reward_tier).map_err(|_| AuctionError::TierNotFound)?;
+    Ok(BundleEscrowV2PolicySnapshot::capture(policy, tier))
+}
+
+fn read_verifier_seed_slot(raw: &RawBundleEscrowV2Data, reserved: &[u8]) -> Option<u64> {
+    let snapshot = read_policy_snapshot(reserved)?;
+    raw.settlement_deadline_slot
+        .checked_sub(snapshot.settlement_window_slots)
+}
+
+/// How a finalized escrow is split, in the escrow's unit: lamports, or token base units for
+/// token escrows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct BundleEscrowV2Payout {
+    /// Winner payout net of the protocol fee.
+    pub winner_amount: u64,
+    pub protocol_fee_amount: u64,
+    pub verifier_reward_amount: u64,
+    pub requester_refund_amount: u64,
+}
+
+impl BundleEscrowV2Payout {
+    /// Splits `escrow_amount` for a gross `winner_payout_amount`. The protocol fee is taken out
+    /// of the winner payout, and whatever the winner and verifiers do not receive is refunded
+    /// to the requester.
+    ///
+    /// `protocol_min_fee_lamports` only applies to lamport escrows; token escrows pay the
+    /// basis-point fee alone.
+    pub fn split(
+        policy: &ConfigPolicyV2,
+        denomination: &BundleEscrowV2Denomination,
+        escrow_amount: u64,
+        winner_payout_amount: u64,
+        verifier_reward_amount: u64,
+    ) -> Result<Self, AuctionError> {
+        let protocol_fee_amount = match denomination {
+            BundleEscrowV2Denomination::Lamports => {
+                policy.protocol_fee_lamports(winner_payout_amount)
+            }
+            BundleEscrowV2Denomination::Token(_) => {
+                policy.protocol_fee_bps_amount(winner_payout_amount)
+            }
+        };
+        Self::with_protocol_fee(
+            protocol_fee_amount,
+            escrow_amount,
+            winner_payout_amount,
+            verifier_reward_amount,
+        )
+    }
+
+    /// [`Self::split`] with a protocol fee already fixed, such as the one a V3+ escrow records
+    /// at finalize. Fails with `InsufficientEscrowBalance` if the fee exceeds the winner payout.
+    pub fn with_protocol_fee(
+        protocol_fee_amount: u64,
+        escrow_amount: u64,
+        winner_payout_amount: u64,
+        verifier_reward_amount: u64,
+    ) -> Result<Self, AuctionError> {
+        let requester_refund_amount = escrow_amount
+            .checked_sub(winner_payout_amount)
+            .and_then(|remaining| remaining.checked_sub(verifier_reward_amount))
+            .ok_or(AuctionError::InsufficientEscrowBalance)?;
+        let winner_amount = winner_payout_amount
+            .checked_sub(protocol_fee_amount)
+            .ok_or(AuctionError::InsufficientEscrowBalance)?;
+
+        Ok(Self {
+            winner_amount,
+            protocol_fee_amount,
+            verifier_reward_amount,
+            requester_refund_amount,
+        })
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct InvalidBundleEscrowV2Transition {
     pub from: BundleEscrowV2Status,
@@ -62,12 +579,14 @@ impl InvalidBundleEscrowV2Transition {
 pub struct BundleEscrowV2Ref<'a> {
     header: &'a AccountHeaderV1,
     raw: &'a RawBundleEscrowV2Data,
+    reserved: &'a [u8],
 }
 
 #[derive(Debug)]
 pub struct BundleEscrowV2Mut<'a> {
     header: &'a mut AccountHeaderV1,
     raw: &'a mut RawBundleEscrowV2Data,
+    reserved: &'a mut [u8],
 }
 
 impl<'a> BundleEscrowV2Ref<'a> {
@@ -86,6 +605,91 @@ impl<'a> BundleEscrowV2Ref<'a> {
     pub fn into_raw(self) -> &'a RawBundleEscrowV2Data {
         self.raw
     }
+
+    /// The policy snapshot taken when the escrow was opened. Always `None` for V1 escrows.
+    pub fn policy_snapshot(&self) -> Option<BundleEscrowV2PolicySnapshot> {
+        read_policy_snapshot(self.reserved)
+    }
+
+    /// The windows and verifier counts this escrow runs under: its policy snapshot, or the
+    /// current values of `policy` for escrows opened without one.
+    pub fn policy_windows(
+        &self,
+        policy: &ConfigPolicyV2,
+    ) -> Result<BundleEscrowV2PolicySnapshot, AuctionError> {
+        read_policy_windows(self.raw, self.reserved, policy)
+    }
+
+    /// Slot whose hash seeds the verifier selection: the slot the escrow was opened in,
+    /// recovered from the settlement deadline and the snapshot's settlement window. It is
+    /// fixed before bids are revealed, so the coordinator can not pick it at commit time.
+    /// `None` without a policy snapshot.
+    pub fn verifier_seed_slot(&self) -> Option<u64> {
+        read_verifier_seed_slot(self.raw, self.reserved)
+    }
+
+    /// The protocol fee withheld from the winner payout at finalize, in the escrow's unit.
+    /// Always `None` before V3.
+    pub fn protocol_fee_lamports(&self) -> Option<u64> {
+        read_protocol_fee_lamports(self.layout(), self.reserved)
+    }
+
+    /// The SPL token the escrow is denominated in. Always `None` before V4.
+    pub fn token_denomination(&self) -> Option<BundleEscrowV2TokenDenomination> {
+        read_token_denomination(self.layout(), self.reserved)
+    }
+
+    pub fn denomination(&self) -> BundleEscrowV2Denomination {
+        self.token_denomination().map_or(
+            BundleEscrowV2Denomination::Lamports,
+            BundleEscrowV2Denomination::Token,
+        )
+    }
+
+    /// The payout split for the values recorded by `finalize`, before any reward is claimed.
+    ///
+    /// V3+ escrows pay the protocol fee recorded at finalize, so later fee patches do not
+    /// change it; earlier layouts compute it from `policy`.
+    pub fn payout(&self, policy: &ConfigPolicyV2) -> Result<BundleEscrowV2Payout, AuctionError> {
+        self.raw.payout_for_verifier_set(
+            policy,
+            &self.denomination(),
+            &self.verifier_set(),
+            self.protocol_fee_lamports(),
+        )
+    }
+
+    /// The payout split with the protocol fee computed from `policy`, for finalize to record
+    /// with [`BundleEscrowV2Mut::set_protocol_fee_lamports`].
+    pub fn finalize_payout(
+        &self,
+        policy: &ConfigPolicyV2,
+    ) -> Result<BundleEscrowV2Payout, AuctionError> {
+        self.raw
+            .payout_for_verifier_set(policy, &self.denomination(), &self.verifier_set(), None)
+    }
+
+    /// The ranked runner-ups. Always `None` before V5.
+    pub fn runner_ups(&self) -> Option<BundleEscrowV2RunnerUps> {
+        read_runner_ups(self.layout(), self.reserved)
+    }
+
+    pub fn verifier_capacity(&self) -> usize {
+        bundle_v2_verifier_capacity(self.layout().version)
+    }
+
+    /// The selected verifiers, from the V6 trailer or the payload fields of earlier layouts.
+    pub fn verifier_set(&self) -> BundleEscrowV2VerifierSet {
+        read_verifier_set(self.layout(), self.raw, self.reserved)
+    }
+
+    pub fn all_quorum_verifier_rewards_claimed(&self) -> bool {
+        self.verifier_set().all_quorum_rewards_claimed()
+    }
+
+    pub fn final_reward_claims_complete(&self) -> bool {
+        self.raw.winner_reward_done() && self.all_quorum_verifier_rewards_claimed()
+    }
 }
 
 impl Deref for BundleEscrowV2Ref<'_> {
@@ -116,6 +720,271 @@ impl<'a> BundleEscrowV2Mut<'a> {
     pub fn into_raw(self) -> &'a mut RawBundleEscrowV2Data {
         self.raw
     }
+
+    pub fn policy_snapshot(&self) -> Option<BundleEscrowV2PolicySnapshot> {
+        read_policy_snapshot(self.reserved)
+    }
+
+    pub fn policy_windows(
+        &self,
+        policy: &ConfigPolicyV2,
+    ) -> Result<BundleEscrowV2PolicySnapshot, AuctionError> {
+        read_policy_windows(self.raw, self.reserved, policy)
+    }
+
+    pub fn verifier_seed_slot(&self) -> Option<u64> {
+        read_verifier_seed_slot(self.raw, self.reserved)
+    }
+
+    /// Stores `snapshot` in the reserved tail. Returns `false` for V1 escrows, which have no
+    /// room for it.
+    pub fn set_policy_snapshot(&mut self, snapshot: &BundleEscrowV2PolicySnapshot) -> bool {
+        let Some(bytes) = self.reserved.get_mut(..BundleEscrowV2PolicySnapshot::LEN) else {
+            return false;
+        };
+        bytes.copy_from_slice(bytemuck::bytes_of(snapshot));
+        true
+    }
+
+    pub fn protocol_fee_lamports(&self) -> Option<u64> {
+        read_protocol_fee_lamports(self.layout(), self.reserved)
+    }
+
+    pub fn token_denomination(&self) -> Option<BundleEscrowV2TokenDenomination> {
+        read_token_denomination(self.layout(), self.reserved)
+    }
+
+    pub fn denomination(&self) -> BundleEscrowV2Denomination {
+        self.token_denomination().map_or(
+            BundleEscrowV2Denomination::Lamports,
+            BundleEscrowV2Denomination::Token,
+        )
+    }
+
+    pub fn payout(&self, policy: &ConfigPolicyV2) -> Result<BundleEscrowV2Payout, AuctionError> {
+        self.raw.payout_for_verifier_set(
+            policy,
+            &self.denomination(),
+            &self.verifier_set(),
+            self.protocol_fee_lamports(),
+        )
+    }
+
+    pub fn finalize_payout(
+        &self,
+        policy: &ConfigPolicyV2,
+    ) -> Result<BundleEscrowV2Payout, AuctionError> {
+        self.raw
+            .payout_for_verifier_set(policy, &self.denomination(), &self.verifier_set(), None)
+    }
+
+    /// Records the token the escrow is denominated in. Returns `false` for escrows before V4.
+    pub fn set_token_denomination(
+        &mut self,
+        denomination: &BundleEscrowV2TokenDenomination,
+    ) -> bool {
+        if !has_token_denomination(self.layout().version) {
+            return false;
+        }
+        self.reserved[token_denomination_range()].copy_from_slice(bytemuck::bytes_of(denomination));
+        true
+    }
+
+    /// Records the protocol fee in the reserved tail. Returns `false` for escrows before V3.
+    pub fn set_protocol_fee_lamports(&mut self, protocol_fee_lamports: u64) -> bool {
+        if !records_protocol_fee(self.layout().version) {
+            return false;
+        }
+        self.reserved[protocol_fee_range()].copy_from_slice(&protocol_fee_lamports.to_le_bytes());
+        true
+    }
+
+    pub fn runner_ups(&self) -> Option<BundleEscrowV2RunnerUps> {
+        read_runner_ups(self.layout(), self.reserved)
+    }
+
+    /// Stores the ranked runner-ups. Returns `false` for escrows before V5.
+    pub fn set_runner_ups(&mut self, runner_ups: &BundleEscrowV2RunnerUps) -> bool {
+        if !has_runner_ups(self.layout().version) {
+            return false;
+        }
+        self.reserved[runner_ups_range()].copy_from_slice(bytemuck::bytes_of(runner_ups));
+        true
+    }
+
+    pub fn verifier_capacity(&self) -> usize {
+        bundle_v2_verifier_capacity(self.layout().version)
+    }
+
+    pub fn verifier_set(&self) -> BundleEscrowV2VerifierSet {
+        read_verifier_set(self.layout(), self.raw, self.reserved)
+    }
+
+    /// Stores `verifier_set` in the V6 trailer, or in the payload fields of earlier layouts.
+    /// Fails with `InvalidVerifierCount` if it uses slots past [`Self::verifier_capacity`].
+    pub fn set_verifier_set(
+        &mut self,
+        verifier_set: &BundleEscrowV2VerifierSet,
+    ) -> Result<(), AuctionError> {
+        if !verifier_set.fits(self.verifier_capacity()) {
+            return Err(AuctionError::InvalidVerifierCount);
+        }
+        if self.layout().version == AccountLayoutVersion::V6 {
+            self.reserved[verifier_set_range()].copy_from_slice(bytemuck::bytes_of(verifier_set));
+        } else {
+            verifier_set.write_legacy(self.raw);
+        }
+        Ok(())
+    }
+
+    /// Layout-aware [`RawBundleEscrowV2Data::claim_verifier_reward`].
+    pub fn claim_verifier_reward(&mut self, verifier_index: usize, claimed_amount: u64) -> bool {
+        let mut verifier_set = self.verifier_set();
+        let claimed = verifier_set.claim_reward(verifier_index, claimed_amount);
+        self.set_verifier_set(&verifier_set)
+            .expect("claims stay within the verifier capacity");
+        claimed
+    }
+
+    pub fn all_quorum_verifier_rewards_claimed(&self) -> bool {
+        self.verifier_set().all_quorum_rewards_claimed()
+    }
+
+    pub fn final_reward_claims_complete(&self) -> bool {
+        self.raw.winner_reward_done() && self.all_quorum_verifier_rewards_claimed()
+    }
+
+    /// Replaces a winner that let `result_deadline_slot` pass without posting a result with
+    /// the next runner-up, and returns the defaulted winner.
+    ///
+    /// The result window restarts at `current_slot` with the length from
+    /// [`Self::policy_windows`], and the verification and claim deadlines move by the same
+    /// amount so every later window keeps its length.
+    pub fn reassign_winner(
+        &mut self,
+        current_slot: u64,
+        policy: &ConfigPolicyV2,
+    ) -> Result<Pubkey, AuctionError> {
+        if self.status != BundleEscrowV2Status::Awarded {
+            return Err(AuctionError::InvalidBundleEscrowV2Status);
+        }
+        if current_slot <= self.result_deadline_slot {
+            return Err(AuctionError::DeadlineNotReached);
+        }
+        let mut runner_ups = self.runner_ups().ok_or(AuctionError::NoRunnerUpAvailable)?;
+        let next = *runner_ups
+            .next_runner_up()
+            .ok_or(AuctionError::NoRunnerUpAvailable)?;
+        // Verifiers may have been selected after the runner-ups were ranked.
+        if self.verifier_set().position(&next.node_pubkey).is_some()
+            || runner_ups.defaulted_winners().contains(&next.node_pubkey)
+        {
+            return Err(AuctionError::InvalidRunnerUpsV2);
+        }
+
+        let result_window_slots = self.policy_windows(policy)?.result_window_slots;
+
+        let defaulted_winner = self.winner_node_pubkey;
+        runner_ups.defaulted_winners[usize::from(runner_ups.promoted_count)] = defaulted_winner;
+        runner_ups.promoted_count += 1;
+        self.set_runner_ups(&runner_ups);
+
+        let result_deadline_slot = current_slot.saturating_add(result_window_slots);
+        let shift = result_deadline_slot - self.result_deadline_slot;
+        self.winner_node_pubkey = next.node_pubkey;
+        self.winner_vote_account = next.vote_account;
+        self.clearing_price_per_output_token = next.clearing_price_per_output_token;
+        self.result_deadline_slot = result_deadline_slot;
+        self.verification_deadline_slot = self.verification_deadline_slot.saturating_add(shift);
+        self.claim_deadline_slot = self.claim_deadline_slot.saturating_add(shift);
+        Ok(defaulted_winner)
+    }
+
+    /// Marks a node of this escrow as penalised for `kind` and returns its pubkey.
+    ///
+    /// `node_index` picks the selected verifier for missed verifications, and the defaulted
+    /// winner for missed results. Past the defaulted winners it picks the current winner, which
+    /// is penalised for a missed result once the escrow expired without one.
+    pub fn mark_penalized(
+        &mut self,
+        kind: NodePenaltyKind,
+        node_index: usize,
+    ) -> Result<Pubkey, AuctionError> {
+        let (node, bit) = match kind {
+            NodePenaltyKind::REJECTED_RESULT
+                if self.status == BundleEscrowV2Status::FinalizedRejected && node_index == 0 =>
+            {
+                (self.winner_node_pubkey, PENALIZED_WINNER_BIT)
+            }
+            NodePenaltyKind::MISSED_RESULT => {
+                let runner_ups = self.runner_ups().unwrap_or_default();
+                let defaulted_winners = runner_ups.defaulted_winners();
+                if let Some(node) = defaulted_winners.get(node_index) {
+                    (*node, 1 << (PENALIZED_DEFAULTED_WINNER_SHIFT + node_index))
+                } else if node_index == defaulted_winners.len()
+                    && self.status == BundleEscrowV2Status::Expired
+                    && self.result_hash == [0; 32]
+                {
+                    (self.winner_node_pubkey, PENALIZED_WINNER_BIT)
+                } else {
+                    return Err(AuctionError::NodeNotPenalizable);
+                }
+            }
+            NodePenaltyKind::MISSED_VERIFICATION
+                if matches!(
+                    self.status,
+                    BundleEscrowV2Status::FinalizedVerified
+                        | BundleEscrowV2Status::FinalizedRejected
+                ) && node_index < self.verifier_capacity() =>
+            {
+                return self.mark_verifier_penalized(node_index);
+            }
+            _ => return Err(AuctionError::NodeNotPenalizable),
+        };
+
+        if node == Pubkey::default() {
+            return Err(AuctionError::NodeNotPenalizable);
+        }
+        if self.penalized_node_bitmap & bit != 0 {
+            return Err(AuctionError::NodePenaltyAlreadyApplied);
+        }
+        self.penalized_node_bitmap |= bit;
+        Ok(node)
+    }
+
+    fn mark_verifier_penalized(&mut self, verifier_index: usize) -> Result<Pubkey, AuctionError> {
+        let mut verifier_set = self.verifier_set();
+        let node = verifier_set.selected_verifiers[verifier_index];
+        if node == Pubkey::default() || verifier_set.voted(verifier_index) {
+            return Err(AuctionError::NodeNotPenalizable);
+        }
+        if verifier_set.penalized_verifier_bitmap & (1 << verifier_index) != 0 {
+            return Err(AuctionError::NodePenaltyAlreadyApplied);
+        }
+        verifier_set.penalized_verifier_bitmap |= 1 << verifier_index;
+        self.set_verifier_set(&verifier_set)?;
+        Ok(node)
+    }
+
+    /// The penalty for `kind`, charged on the award the escrow was opened for in the escrow's
+    /// unit. Token escrow penalties go to the node's record for the escrow's mint.
+    pub fn node_penalty_lamports(&self, policy: &ConfigPolicyV2, kind: NodePenaltyKind) -> u64 {
+        policy.node_penalty_amount(kind, self.award_amount())
+    }
+
+    /// Records the verifiers that voted against the final verdict, so they are not penalised
+    /// for a missed verification. They must be selected verifiers outside the quorum.
+    pub fn set_dissenting_verifiers(&mut self, dissenting_bitmap: u32) -> Result<(), AuctionError> {
+        let mut verifier_set = self.verifier_set();
+        let selected = u32::MAX
+            .checked_shl(verifier_set.len() as u32)
+            .map_or(u32::MAX, |outside| !outside);
+        if dissenting_bitmap & (verifier_set.quorum_verifier_bitmap | !selected) != 0 {
+            return Err(AuctionError::InvalidVerifierQuorum);
+        }
+        verifier_set.dissenting_verifier_bitmap = dissenting_bitmap;
+        self.set_verifier_set(&verifier_set)
+    }
 }
 
 impl Deref for BundleEscrowV2Mut<'_> {
@@ -138,11 +1007,23 @@ impl RawBundleEscrowV2Data {
     pub const LEN_V1: usize = AccountHeaderV1::LEN + Self::PAYLOAD_LEN;
     pub const LEN_V2: usize =
         AccountHeaderV1::LEN + Self::PAYLOAD_LEN + CONFIG_POLICY_V2_BUNDLE_ESCROW_RESERVED_BYTES;
+    /// Same size as V2; the reserved tail additionally carries `protocol_fee_lamports`.
+    pub const LEN_V3: usize = Self::LEN_V2;
+    /// V3 followed by a [`BundleEscrowV2TokenDenomination`] for token escrows.
+    pub const LEN_V4: usize = Self::LEN_V3 + BundleEscrowV2TokenDenomination::LEN;
+    /// V4 followed by [`BundleEscrowV2RunnerUps`].
+    pub const LEN_V5: usize = Self::LEN_V4 + BundleEscrowV2RunnerUps::LEN;
+    /// V5 followed by a [`BundleEscrowV2VerifierSet`] that replaces the payload verifier fields.
+    pub const LEN_V6: usize = Self::LEN_V5 + BundleEscrowV2VerifierSet::LEN;
 
     pub const fn account_len(version: AccountLayoutVersion) -> usize {
         match version {
             AccountLayoutVersion::V1 => Self::LEN_V1,
             AccountLayoutVersion::V2 => Self::LEN_V2,
+            AccountLayoutVersion::V3 => Self::LEN_V3,
+            AccountLayoutVersion::V4 => Self::LEN_V4,
+            AccountLayoutVersion::V5 => Self::LEN_V5,
+            AccountLayoutVersion::V6 => Self::LEN_V6,
             AccountLayoutVersion::LegacyV0 => 0,
         }
     }
@@ -163,9 +1044,13 @@ impl RawBundleEscrowV2Data {
             return None;
         }
 
-        let (raw_bytes, _reserved) = raw_bytes.split_at(Self::PAYLOAD_LEN);
+        let (raw_bytes, reserved) = raw_bytes.split_at(Self::PAYLOAD_LEN);
         let raw = bytemuck::try_from_bytes::<RawBundleEscrowV2Data>(raw_bytes).ok()?;
-        Some(BundleEscrowV2Ref { header, raw })
+        Some(BundleEscrowV2Ref {
+            header,
+            raw,
+            reserved,
+        })
     }
 
     pub fn from_bytes_mut(bytes: &mut [u8]) -> Option<BundleEscrowV2Mut<'_>> {
@@ -185,9 +1070,13 @@ impl RawBundleEscrowV2Data {
             return None;
         }
 
-        let (raw_bytes, _reserved) = raw_bytes.split_at_mut(Self::PAYLOAD_LEN);
+        let (raw_bytes, reserved) = raw_bytes.split_at_mut(Self::PAYLOAD_LEN);
         let raw = bytemuck::try_from_bytes_mut::<RawBundleEscrowV2Data>(raw_bytes).ok()?;
-        Some(BundleEscrowV2Mut { header, raw })
+        Some(BundleEscrowV2Mut {
+            header,
+            raw,
+            reserved,
+        })
     }
 
     pub fn read(bytes: &[u8]) -> Option<Self> {
@@ -202,21 +1091,45 @@ impl RawBundleEscrowV2Data {
         self.write_bytes_with_layout(bytes, AccountLayoutVersion::V2)
     }
 
+    pub fn write_v3_bytes(&self, bytes: &mut [u8]) -> bool {
+        self.write_bytes_with_layout(bytes, AccountLayoutVersion::V3)
+    }
+
+    pub fn write_v4_bytes(&self, bytes: &mut [u8]) -> bool {
+        self.write_bytes_with_layout(bytes, AccountLayoutVersion::V4)
+    }
+
+    pub fn write_v5_bytes(&self, bytes: &mut [u8]) -> bool {
+        self.write_bytes_with_layout(bytes, AccountLayoutVersion::V5)
+    }
+
+    pub fn write_v6_bytes(&self, bytes: &mut [u8]) -> bool {
+        self.write_bytes_with_layout(bytes, AccountLayoutVersion::V6)
+    }
+
+    /// Writes the whole account. Everything after the payload (policy snapshot, protocol fee,
+    /// token denomination, runner-ups and the V6 verifier set) is kept when `bytes` already
+    /// hold a `version` escrow and zeroed otherwise, so storing a fresh escrow needs
+    /// [`BundleEscrowV2Mut::set_policy_snapshot`] and friends afterwards.
     pub fn write_bytes_with_layout(&self, bytes: &mut [u8], version: AccountLayoutVersion) -> bool {
         let expected_len = Self::account_len(version);
         if expected_len == 0 || bytes.len() != expected_len {
             return false;
         }
 
-        let (header_bytes, raw_bytes) = bytes.split_at_mut(AccountHeaderV1::LEN);
-        header_bytes.copy_from_slice(bytemuck::bytes_of(&AccountHeaderV1 {
+        let header = AccountHeaderV1 {
             discriminator: AccountDiscriminator::BundleEscrowV2 as u8,
             version: version as u8,
             reserved: [0; 6],
-        }));
+        };
+        let (header_bytes, raw_bytes) = bytes.split_at_mut(AccountHeaderV1::LEN);
+        let keep_reserved = header_bytes == bytemuck::bytes_of(&header);
+        header_bytes.copy_from_slice(bytemuck::bytes_of(&header));
         let (raw_bytes, reserved_bytes) = raw_bytes.split_at_mut(Self::PAYLOAD_LEN);
         raw_bytes.copy_from_slice(bytemuck::bytes_of(self));
-        reserved_bytes.fill(0);
+        if !keep_reserved {
+            reserved_bytes.fill(0);
+        }
         true
     }
 
@@ -265,6 +1178,7 @@ impl RawBundleEscrowV2Data {
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn finalize(
         &mut self,
         final_status: BundleEscrowV2Status,
@@ -303,6 +1217,36 @@ impl RawBundleEscrowV2Data {
         Ok(())
     }
 
+    /// The payout split for the values recorded by [`Self::finalize`] and `verifier_set`, which
+    /// only the account views can read for V6 escrows. Pays `recorded_protocol_fee` if
+    /// finalize recorded one and computes the fee from `policy` otherwise.
+    fn payout_for_verifier_set(
+        &self,
+        policy: &ConfigPolicyV2,
+        denomination: &BundleEscrowV2Denomination,
+        verifier_set: &BundleEscrowV2VerifierSet,
+        recorded_protocol_fee: Option<u64>,
+    ) -> Result<BundleEscrowV2Payout, AuctionError> {
+        let verifier_reward_amount = verifier_set
+            .total_reward_remaining()
+            .ok_or(AuctionError::InsufficientEscrowBalance)?;
+        match recorded_protocol_fee {
+            Some(protocol_fee_amount) => BundleEscrowV2Payout::with_protocol_fee(
+                protocol_fee_amount,
+                self.escrow_lamports,
+                self.winner_payout_lamports,
+                verifier_reward_amount,
+            ),
+            None => BundleEscrowV2Payout::split(
+                policy,
+                denomination,
+                self.escrow_lamports,
+                self.winner_payout_lamports,
+                verifier_reward_amount,
+            ),
+        }
+    }
+
     pub fn claim_verifier_reward(&mut self, verifier_index: usize, claimed_amount: u64) -> bool {
         let remaining = self.verifier_reward_remaining[verifier_index]
             .checked_sub(claimed_amount)
@@ -315,6 +1259,19 @@ impl RawBundleEscrowV2Data {
         remaining == 0
     }
 
+    /// Whether the deadline of the current stage has passed, so the escrow can be expired.
+    /// The deadlines are set from the policy snapshot when the escrow opens and only move
+    /// with [`BundleEscrowV2Mut::reassign_winner`], so later policy changes do not affect them.
+    pub fn is_expired(&self, current_slot: u64) -> bool {
+        let deadline_slot = match self.status {
+            BundleEscrowV2Status::Open => self.settlement_deadline_slot,
+            BundleEscrowV2Status::Awarded => self.result_deadline_slot,
+            BundleEscrowV2Status::ResultPosted => self.verification_deadline_slot,
+            _ => return false,
+        };
+        current_slot > deadline_slot
+    }
+
     pub fn expire(&mut self) -> Result<(), InvalidBundleEscrowV2Transition> {
         match self.status {
             BundleEscrowV2Status::Open
@@ -330,15 +1287,25 @@ impl RawBundleEscrowV2Data {
         }
     }
 
+    /// The most the winner can be paid: the clearing price for every output token, capped at
+    /// the escrowed amount.
+    pub fn award_amount(&self) -> u64 {
+        self.clearing_price_per_output_token
+            .saturating_mul(self.max_output_tokens)
+            .min(self.escrow_lamports)
+    }
+
     pub fn all_quorum_verifier_rewards_claimed(&self) -> bool {
         self.verifier_reward_claimed_bitmap & self.quorum_verifier_bitmap
             == self.quorum_verifier_bitmap
     }
 
     pub fn final_reward_claims_complete(&self) -> bool {
-        let winner_done = self.status != BundleEscrowV2Status::FinalizedVerified
-            || self.winner_reward_claimed != 0;
-        winner_done && self.all_quorum_verifier_rewards_claimed()
+        self.winner_reward_done() && self.all_quorum_verifier_rewards_claimed()
+    }
+
+    fn winner_reward_done(&self) -> bool {
+        self.status != BundleEscrowV2Status::FinalizedVerified || self.winner_reward_claimed != 0
     }
 }
 
@@ -373,7 +1340,10 @@ impl Default for RawBundleEscrowV2Data {
             verifier_reward_claimed_bitmap: 0,
             quorum_verifier_bitmap: 0,
             verifier_page_count: 0,
-            _reserved1: [0; 4],
+            refund_page_count: 0,
+            penalized_node_bitmap: 0,
+            dissenting_verifier_bitmap: 0,
+            _reserved1: [0; 1],
             verifier_reward_remaining: [0; MAX_VERIFIERS_PER_AUCTION],
         }
     }
@@ -0,0 +1,293 @@
+use super::{
+    AccountDiscriminator, AccountHeaderV1, AccountLayoutVersion, ParsedAccountLayout, Pubkey,
+    RawBundleVerifierPageV2Data,
+};
+use crate::error::AuctionError;
+use bytemuck::{Pod, Zeroable};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::ops::{Deref, DerefMut};
+
+/// Entries per `BundleRefundPageV2`. Three pages cover the largest (Eco) bundles.
+pub const MAX_BUNDLE_REFUND_PAGE_V2_ENTRIES: usize = 10;
+
+/// The refund owed to one job's requester.
+///
+/// Written with `refund_recipient` and `max_output_tokens` before finalize; `settle` fills in
+/// the accepted tokens and the refund once the verifier pages are final.
+#[derive(Pod, Clone, Copy, Zeroable, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[repr(C)]
+pub struct BundleRefundPageV2Entry {
+    pub job_id: Pubkey,
+    pub refund_recipient: Pubkey,
+    pub max_output_tokens: u64,
+    pub accepted_output_tokens: u64,
+    /// In the escrow's unit: lamports, or token base units for token escrows.
+    pub refund_amount: u64,
+}
+
+impl BundleRefundPageV2Entry {
+    pub fn new(job_id: Pubkey, refund_recipient: Pubkey, max_output_tokens: u64) -> Self {
+        Self {
+            job_id,
+            refund_recipient,
+            max_output_tokens,
+            accepted_output_tokens: 0,
+            refund_amount: 0,
+        }
+    }
+
+    /// Refunds the output tokens the job reserved but did not get accepted, at the clearing
+    /// price.
+    pub fn settle(
+        &mut self,
+        accepted_output_tokens: u64,
+        clearing_price_per_output_token: u64,
+    ) -> Result<u64, AuctionError> {
+        let refund_amount = self
+            .max_output_tokens
+            .checked_sub(accepted_output_tokens)
+            .and_then(|unused| unused.checked_mul(clearing_price_per_output_token))
+            .ok_or(AuctionError::InvalidRefundPageV2Input)?;
+
+        self.accepted_output_tokens = accepted_output_tokens;
+        self.refund_amount = refund_amount;
+        Ok(refund_amount)
+    }
+}
+
+#[derive(Pod, Clone, Copy, Zeroable, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[repr(C)]
+pub struct RawBundleRefundPageV2Data {
+    pub bundle_escrow: Pubkey,
+    pub page_index: u16,
+    pub entry_count: u16,
+    /// Bit `i` is set once entry `i` has been refunded.
+    pub refund_claimed_bitmap: u16,
+    pub _reserved0: [u8; 2],
+    pub entries: [BundleRefundPageV2Entry; MAX_BUNDLE_REFUND_PAGE_V2_ENTRIES],
+}
+
+pub type BundleRefundPageV2 = RawBundleRefundPageV2Data;
+
+const _: () = assert!(MAX_BUNDLE_REFUND_PAGE_V2_ENTRIES <= u16::BITS as usize);
+
+#[derive(Debug)]
+pub struct BundleRefundPageV2Ref<'a> {
+    header: &'a AccountHeaderV1,
+    raw: &'a RawBundleRefundPageV2Data,
+}
+
+#[derive(Debug)]
+pub struct BundleRefundPageV2Mut<'a> {
+    header: &'a mut AccountHeaderV1,
+    raw: &'a mut RawBundleRefundPageV2Data,
+}
+
+impl<'a> BundleRefundPageV2Ref<'a> {
+    pub fn header(&self) -> &AccountHeaderV1 {
+        self.header
+    }
+
+    pub fn layout(&self) -> ParsedAccountLayout {
+        self.header.layout().unwrap()
+    }
+
+    pub fn as_raw(&self) -> &RawBundleRefundPageV2Data {
+        self.raw
+    }
+}
+
+impl Deref for BundleRefundPageV2Ref<'_> {
+    type Target = RawBundleRefundPageV2Data;
+
+    fn deref(&self) -> &Self::Target {
+        self.raw
+    }
+}
+
+impl<'a> BundleRefundPageV2Mut<'a> {
+    pub fn header(&self) -> &AccountHeaderV1 {
+        self.header
+    }
+
+    pub fn layout(&self) -> ParsedAccountLayout {
+        self.header.layout().unwrap()
+    }
+
+    pub fn as_raw(&self) -> &RawBundleRefundPageV2Data {
+        self.raw
+    }
+
+    pub fn as_raw_mut(&mut self) -> &mut RawBundleRefundPageV2Data {
+        self.raw
+    }
+}
+
+impl Deref for BundleRefundPageV2Mut<'_> {
+    type Target = RawBundleRefundPageV2Data;
+
+    fn deref(&self) -> &Self::Target {
+        self.raw
+    }
+}
+
+impl DerefMut for BundleRefundPageV2Mut<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.raw
+    }
+}
+
+impl RawBundleRefundPageV2Data {
+    pub const PAYLOAD_LEN: usize = std::mem::size_of::<RawBundleRefundPageV2Data>();
+    pub const LEN: usize = AccountHeaderV1::LEN + Self::PAYLOAD_LEN;
+
+    pub fn new(
+        bundle_escrow: Pubkey,
+        page_index: u16,
+        entries: &[BundleRefundPageV2Entry],
+    ) -> Result<Self, AuctionError> {
+        if entries.len() > MAX_BUNDLE_REFUND_PAGE_V2_ENTRIES {
+            return Err(AuctionError::InvalidRefundPageV2Input);
+        }
+
+        let mut page = Self {
+            bundle_escrow,
+            page_index,
+            entry_count: entries.len() as u16,
+            ..Self::zeroed()
+        };
+        page.entries[..entries.len()].copy_from_slice(entries);
+        Ok(page)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<BundleRefundPageV2Ref<'_>> {
+        if bytes.len() != Self::LEN {
+            return None;
+        }
+
+        let (header_bytes, raw_bytes) = bytes.split_at(AccountHeaderV1::LEN);
+        let header = bytemuck::try_from_bytes::<AccountHeaderV1>(header_bytes).ok()?;
+        if !Self::is_supported_layout(header.layout()?) {
+            return None;
+        }
+
+        let raw = bytemuck::try_from_bytes::<RawBundleRefundPageV2Data>(raw_bytes).ok()?;
+        if usize::from(raw.entry_count) > MAX_BUNDLE_REFUND_PAGE_V2_ENTRIES {
+            return None;
+        }
+        Some(BundleRefundPageV2Ref { header, raw })
+    }
+
+    pub fn from_bytes_mut(bytes: &mut [u8]) -> Option<BundleRefundPageV2Mut<'_>> {
+        if bytes.len() != Self::LEN {
+            return None;
+        }
+
+        let (header_bytes, raw_bytes) = bytes.split_at_mut(AccountHeaderV1::LEN);
+        let header = bytemuck::try_from_bytes_mut::<AccountHeaderV1>(header_bytes).ok()?;
+        if !Self::is_supported_layout(header.layout()?) {
+            return None;
+        }
+
+        let raw = bytemuck::try_from_bytes_mut::<RawBundleRefundPageV2Data>(raw_bytes).ok()?;
+        if usize::from(raw.entry_count) > MAX_BUNDLE_REFUND_PAGE_V2_ENTRIES {
+            return None;
+        }
+        Some(BundleRefundPageV2Mut { header, raw })
+    }
+
+    pub fn read(bytes: &[u8]) -> Option<Self> {
+        Self::from_bytes(bytes).map(|account| *account.as_raw())
+    }
+
+    pub fn write_bytes(&self, bytes: &mut [u8]) -> bool {
+        if bytes.len() != Self::LEN {
+            return false;
+        }
+
+        let (header_bytes, raw_bytes) = bytes.split_at_mut(AccountHeaderV1::LEN);
+        header_bytes.copy_from_slice(bytemuck::bytes_of(&AccountHeaderV1::new(
+            AccountDiscriminator::BundleRefundPageV2,
+        )));
+        raw_bytes.copy_from_slice(bytemuck::bytes_of(self));
+        true
+    }
+
+    fn is_supported_layout(layout: ParsedAccountLayout) -> bool {
+        layout
+            == ParsedAccountLayout::new(
+                AccountDiscriminator::BundleRefundPageV2,
+                AccountLayoutVersion::V1,
+            )
+    }
+
+    pub fn active_entries(&self) -> &[BundleRefundPageV2Entry] {
+        &self.entries[..usize::from(self.entry_count)]
+    }
+
+    pub fn total_refund_amount(&self) -> Option<u64> {
+        self.active_entries()
+            .iter()
+            .try_fold(0u64, |total, entry| total.checked_add(entry.refund_amount))
+    }
+
+    /// Settles every entry against the accepted tokens of the same job in `verifier_pages`
+    /// and returns the page's total refund. The program must check that the refund pages
+    /// together stay within the escrow's requester refund.
+    pub fn settle(
+        &mut self,
+        verifier_pages: &[RawBundleVerifierPageV2Data],
+        clearing_price_per_output_token: u64,
+    ) -> Result<u64, AuctionError> {
+        let entry_count = usize::from(self.entry_count);
+        let mut total = 0u64;
+        for entry in &mut self.entries[..entry_count] {
+            let accepted_output_tokens = verifier_pages
+                .iter()
+                .flat_map(|page| &page.entries[..usize::from(page.entry_count)])
+                .find(|verified| verified.job_id == entry.job_id)
+                .ok_or(AuctionError::InvalidRefundPageV2Input)?
+                .accepted_output_tokens;
+            let refund_amount =
+                entry.settle(accepted_output_tokens, clearing_price_per_output_token)?;
+            total = total
+                .checked_add(refund_amount)
+                .ok_or(AuctionError::InvalidRefundPageV2Input)?;
+        }
+        Ok(total)
+    }
+
+    pub fn is_refund_claimed(&self, entry_index: usize) -> bool {
+        entry_index < MAX_BUNDLE_REFUND_PAGE_V2_ENTRIES
+            && self.refund_claimed_bitmap & (1 << entry_index) != 0
+    }
+
+    pub fn all_refunds_claimed(&self) -> bool {
+        (0..usize::from(self.entry_count)).all(|index| self.is_refund_claimed(index))
+    }
+
+    /// Marks the refund of `entry_index` as claimed by `recipient` and returns its amount.
+    pub fn claim_refund(
+        &mut self,
+        entry_index: usize,
+        recipient: &Pubkey,
+    ) -> Result<u64, AuctionError> {
+        let entry = self
+            .active_entries()
+            .get(entry_index)
+            .ok_or(AuctionError::InvalidRefundPageV2Input)?;
+        if entry.refund_recipient != *recipient {
+            return Err(AuctionError::InvalidRefundRecipient);
+        }
+        if self.is_refund_claimed(entry_index) {
+            return Err(AuctionError::RequesterRefundAlreadyClaimed);
+        }
+
+        let refund_amount = entry.refund_amount;
+        self.refund_claimed_bitmap |= 1 << entry_index;
+        Ok(refund_amount)
+    }
+}
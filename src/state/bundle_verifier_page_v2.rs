@@ -1,8 +1,8 @@
 use super::{
-    AccountDiscriminator, AccountHeaderV1, AccountLayoutVersion, ParsedAccountLayout, Pubkey,
-    CONFIG_POLICY_V2_BUNDLE_VERIFIER_PAGE_RESERVED_BYTES,
+    bundle_v2_verifier_capacity, AccountDiscriminator, AccountHeaderV1, AccountLayoutVersion,
+    ParsedAccountLayout, Pubkey, CONFIG_POLICY_V2_BUNDLE_VERIFIER_PAGE_RESERVED_BYTES,
 };
-use crate::{VerificationVerdictV2, MAX_VERIFIERS_PER_AUCTION};
+use crate::{VerificationVerdictV2, MAX_EXTENDED_VERIFIERS_PER_AUCTION, MAX_VERIFIERS_PER_AUCTION};
 use bytemuck::{Pod, Zeroable};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -27,6 +27,69 @@ pub struct BundleVerifierPageV2Entry {
     pub _reserved: [u8; 6],
 }
 
+/// The verifier columns of one page entry.
+///
+/// V6 pages keep one per entry after the reserved tail; earlier layouts keep the first
+/// `MAX_VERIFIERS_PER_AUCTION` slots in [`BundleVerifierPageV2Entry`] itself.
+#[derive(Pod, Clone, Copy, Zeroable, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[repr(C)]
+pub struct BundleVerifierPageV2EntryVerifiers {
+    pub assigned_verifiers_token_ranges: [u64; MAX_EXTENDED_VERIFIERS_PER_AUCTION * 2],
+    pub verifier_reward_tokens: [u64; MAX_EXTENDED_VERIFIERS_PER_AUCTION],
+    pub verifier_claimed_bitmap: u32,
+    pub _reserved0: [u8; 4],
+}
+
+impl BundleVerifierPageV2EntryVerifiers {
+    pub const LEN: usize = std::mem::size_of::<BundleVerifierPageV2EntryVerifiers>();
+
+    /// Whether every range, reward and claimed bit in use is below `capacity`.
+    pub fn fits(&self, capacity: usize) -> bool {
+        let capacity = capacity.min(MAX_EXTENDED_VERIFIERS_PER_AUCTION);
+        self.assigned_verifiers_token_ranges[capacity * 2..]
+            .iter()
+            .chain(&self.verifier_reward_tokens[capacity..])
+            .all(|value| *value == 0)
+            && self.verifier_claimed_bitmap & u32::MAX.checked_shl(capacity as u32).unwrap_or(0)
+                == 0
+    }
+}
+
+impl From<&BundleVerifierPageV2Entry> for BundleVerifierPageV2EntryVerifiers {
+    /// The verifier columns of a layout before V6, read from the entry fields.
+    fn from(entry: &BundleVerifierPageV2Entry) -> Self {
+        let mut verifiers = Self {
+            verifier_claimed_bitmap: u32::from(entry.verifier_claimed_bitmap),
+            ..Self::default()
+        };
+        verifiers.assigned_verifiers_token_ranges[..MAX_VERIFIERS_PER_AUCTION * 2]
+            .copy_from_slice(&entry.assigned_verifiers_token_ranges);
+        verifiers.verifier_reward_tokens[..MAX_VERIFIERS_PER_AUCTION]
+            .copy_from_slice(&entry.verifier_reward_tokens);
+        verifiers
+    }
+}
+
+fn entry_verifiers_range(index: usize) -> std::ops::Range<usize> {
+    let start = CONFIG_POLICY_V2_BUNDLE_VERIFIER_PAGE_RESERVED_BYTES
+        + index * BundleVerifierPageV2EntryVerifiers::LEN;
+    start..start + BundleVerifierPageV2EntryVerifiers::LEN
+}
+
+fn read_entry_verifiers(
+    layout: ParsedAccountLayout,
+    raw: &RawBundleVerifierPageV2Data,
+    reserved: &[u8],
+    index: usize,
+) -> Option<BundleVerifierPageV2EntryVerifiers> {
+    let entry = raw.entries.get(index)?;
+    if layout.version != AccountLayoutVersion::V6 {
+        return Some(BundleVerifierPageV2EntryVerifiers::from(entry));
+    }
+    bytemuck::try_pod_read_unaligned(reserved.get(entry_verifiers_range(index))?).ok()
+}
+
 #[derive(Pod, Clone, Copy, Zeroable, Debug, PartialEq, Eq, Default)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 #[repr(C)]
@@ -44,12 +107,14 @@ pub type BundleVerifierPageV2 = RawBundleVerifierPageV2Data;
 pub struct BundleVerifierPageV2Ref<'a> {
     header: &'a AccountHeaderV1,
     raw: &'a RawBundleVerifierPageV2Data,
+    reserved: &'a [u8],
 }
 
 #[derive(Debug)]
 pub struct BundleVerifierPageV2Mut<'a> {
     header: &'a mut AccountHeaderV1,
     raw: &'a mut RawBundleVerifierPageV2Data,
+    reserved: &'a mut [u8],
 }
 
 impl<'a> BundleVerifierPageV2Ref<'a> {
@@ -64,6 +129,15 @@ impl<'a> BundleVerifierPageV2Ref<'a> {
     pub fn as_raw(&self) -> &RawBundleVerifierPageV2Data {
         self.raw
     }
+
+    pub fn verifier_capacity(&self) -> usize {
+        bundle_v2_verifier_capacity(self.layout().version)
+    }
+
+    /// The verifier columns of entry `index`, from the V6 trailer or the entry itself.
+    pub fn entry_verifiers(&self, index: usize) -> Option<BundleVerifierPageV2EntryVerifiers> {
+        read_entry_verifiers(self.layout(), self.raw, self.reserved, index)
+    }
 }
 
 impl Deref for BundleVerifierPageV2Ref<'_> {
@@ -90,6 +164,42 @@ impl<'a> BundleVerifierPageV2Mut<'a> {
     pub fn as_raw_mut(&mut self) -> &mut RawBundleVerifierPageV2Data {
         self.raw
     }
+
+    pub fn verifier_capacity(&self) -> usize {
+        bundle_v2_verifier_capacity(self.layout().version)
+    }
+
+    pub fn entry_verifiers(&self, index: usize) -> Option<BundleVerifierPageV2EntryVerifiers> {
+        read_entry_verifiers(self.layout(), self.raw, self.reserved, index)
+    }
+
+    /// Stores the verifier columns of entry `index`. Returns `false` for an index past the
+    /// page or columns that use slots past [`Self::verifier_capacity`].
+    pub fn set_entry_verifiers(
+        &mut self,
+        index: usize,
+        verifiers: &BundleVerifierPageV2EntryVerifiers,
+    ) -> bool {
+        if index >= MAX_BUNDLE_VERIFIER_PAGE_V2_ENTRIES || !verifiers.fits(self.verifier_capacity())
+        {
+            return false;
+        }
+        if self.layout().version == AccountLayoutVersion::V6 {
+            self.reserved[entry_verifiers_range(index)]
+                .copy_from_slice(bytemuck::bytes_of(verifiers));
+            return true;
+        }
+
+        let entry = &mut self.raw.entries[index];
+        entry.assigned_verifiers_token_ranges.copy_from_slice(
+            &verifiers.assigned_verifiers_token_ranges[..MAX_VERIFIERS_PER_AUCTION * 2],
+        );
+        entry
+            .verifier_reward_tokens
+            .copy_from_slice(&verifiers.verifier_reward_tokens[..MAX_VERIFIERS_PER_AUCTION]);
+        entry.verifier_claimed_bitmap = verifiers.verifier_claimed_bitmap as u8;
+        true
+    }
 }
 
 impl Deref for BundleVerifierPageV2Mut<'_> {
@@ -113,12 +223,20 @@ impl RawBundleVerifierPageV2Data {
     pub const LEN_V2: usize = AccountHeaderV1::LEN
         + Self::PAYLOAD_LEN
         + CONFIG_POLICY_V2_BUNDLE_VERIFIER_PAGE_RESERVED_BYTES;
+    /// V2 followed by a [`BundleVerifierPageV2EntryVerifiers`] per entry, which replace the
+    /// verifier fields of the entries.
+    pub const LEN_V6: usize = Self::LEN_V2
+        + MAX_BUNDLE_VERIFIER_PAGE_V2_ENTRIES * BundleVerifierPageV2EntryVerifiers::LEN;
 
     pub const fn account_len(version: AccountLayoutVersion) -> usize {
         match version {
             AccountLayoutVersion::V1 => Self::LEN_V1,
             AccountLayoutVersion::V2 => Self::LEN_V2,
-            AccountLayoutVersion::LegacyV0 => 0,
+            AccountLayoutVersion::V6 => Self::LEN_V6,
+            AccountLayoutVersion::LegacyV0
+            | AccountLayoutVersion::V3
+            | AccountLayoutVersion::V4
+            | AccountLayoutVersion::V5 => 0,
         }
     }
 
@@ -138,9 +256,13 @@ impl RawBundleVerifierPageV2Data {
             return None;
         }
 
-        let (raw_bytes, _reserved) = raw_bytes.split_at(Self::PAYLOAD_LEN);
+        let (raw_bytes, reserved) = raw_bytes.split_at(Self::PAYLOAD_LEN);
         let raw = bytemuck::try_from_bytes::<RawBundleVerifierPageV2Data>(raw_bytes).ok()?;
-        Some(BundleVerifierPageV2Ref { header, raw })
+        Some(BundleVerifierPageV2Ref {
+            header,
+            raw,
+            reserved,
+        })
     }
 
     pub fn from_bytes_mut(bytes: &mut [u8]) -> Option<BundleVerifierPageV2Mut<'_>> {
@@ -160,9 +282,13 @@ impl RawBundleVerifierPageV2Data {
             return None;
         }
 
-        let (raw_bytes, _reserved) = raw_bytes.split_at_mut(Self::PAYLOAD_LEN);
+        let (raw_bytes, reserved) = raw_bytes.split_at_mut(Self::PAYLOAD_LEN);
         let raw = bytemuck::try_from_bytes_mut::<RawBundleVerifierPageV2Data>(raw_bytes).ok()?;
-        Some(BundleVerifierPageV2Mut { header, raw })
+        Some(BundleVerifierPageV2Mut {
+            header,
+            raw,
+            reserved,
+        })
     }
 
     pub fn read(bytes: &[u8]) -> Option<Self> {
@@ -177,6 +303,12 @@ impl RawBundleVerifierPageV2Data {
         self.write_bytes_with_layout(bytes, AccountLayoutVersion::V2)
     }
 
+    pub fn write_v6_bytes(&self, bytes: &mut [u8]) -> bool {
+        self.write_bytes_with_layout(bytes, AccountLayoutVersion::V6)
+    }
+
+    /// Writes the whole account, zeroing everything after the payload. Store the verifier
+    /// columns of a V6 page with [`BundleVerifierPageV2Mut::set_entry_verifiers`] afterwards.
     pub fn write_bytes_with_layout(&self, bytes: &mut [u8], version: AccountLayoutVersion) -> bool {
         let expected_len = Self::account_len(version);
         if expected_len == 0 || bytes.len() != expected_len {
@@ -1,5 +1,11 @@
-use super::{AccountLayoutVersion, Pubkey, RequestTier};
-use crate::MAX_VERIFIERS_PER_AUCTION;
+use super::{
+    bundle_v2_verifier_capacity, AccountLayoutVersion, NodePenaltyKind, Pubkey, RequestTier,
+    TierParams,
+};
+use crate::error::AuctionError;
+use crate::{
+    AuctionInstruction, ConfigPolicyV2AuthorityKind, ConfigPolicyV2Patch, MAX_VERIFIERS_PER_AUCTION,
+};
 use bytemuck::{Pod, Zeroable};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -23,11 +29,61 @@ pub enum ConfigPolicyV2Flag {
 }
 
 impl ConfigPolicyV2Flag {
+    pub const ALL: [ConfigPolicyV2Flag; 6] = [
+        ConfigPolicyV2Flag::AllowServiceOpenEscrowArgsBypass,
+        ConfigPolicyV2Flag::AllowServiceCommitOverride,
+        ConfigPolicyV2Flag::AllowServiceResultPostOverride,
+        ConfigPolicyV2Flag::AllowServiceFinalizeOverride,
+        ConfigPolicyV2Flag::AllowServicePageBackedFinalizeBypass,
+        ConfigPolicyV2Flag::AllowServicePageBackedFinalizePayout,
+    ];
+
     pub const fn mask(self) -> ConfigPolicyV2Flags {
         ConfigPolicyV2Flags(1u64 << self as u8)
     }
 }
 
+/// Something a signer may be allowed to do under a [`ConfigPolicyV2`], see [`ConfigPolicyV2::can`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ConfigPolicyV2Action {
+    UpdatePolicy,
+    OpenEscrowArgsBypass,
+    CommitOverride,
+    ResultPostOverride,
+    FinalizeOverride,
+    PageBackedFinalizeBypass,
+    PageBackedFinalizePayout,
+}
+
+impl ConfigPolicyV2Action {
+    /// The flag that must be enabled for a service authority to take this action, or `None`
+    /// for admin-only actions.
+    pub const fn required_flag(self) -> Option<ConfigPolicyV2Flag> {
+        match self {
+            Self::UpdatePolicy => None,
+            Self::OpenEscrowArgsBypass => {
+                Some(ConfigPolicyV2Flag::AllowServiceOpenEscrowArgsBypass)
+            }
+            Self::CommitOverride => Some(ConfigPolicyV2Flag::AllowServiceCommitOverride),
+            Self::ResultPostOverride => Some(ConfigPolicyV2Flag::AllowServiceResultPostOverride),
+            Self::FinalizeOverride => Some(ConfigPolicyV2Flag::AllowServiceFinalizeOverride),
+            Self::PageBackedFinalizeBypass => {
+                Some(ConfigPolicyV2Flag::AllowServicePageBackedFinalizeBypass)
+            }
+            Self::PageBackedFinalizePayout => {
+                Some(ConfigPolicyV2Flag::AllowServicePageBackedFinalizePayout)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ConfigPolicyV2AuthoritySlot {
+    pub kind: ConfigPolicyV2AuthorityKind,
+    pub index: u8,
+    pub authority: Pubkey,
+}
+
 #[derive(Pod, Clone, Copy, Zeroable, Debug, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 #[cfg_attr(feature = "serde", serde(transparent))]
@@ -60,10 +116,71 @@ impl ConfigPolicyV2Flags {
     }
 }
 
+/// Instruction families that can be paused independently during an incident.
+///
+/// Governance instructions (config policy, proposals, schedules and the tier registry) never
+/// belong to a group so a paused protocol can still be reconfigured.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[repr(u8)]
+pub enum ConfigPolicyV2PauseGroup {
+    /// New work entering the protocol: job requests, bids, bundles and escrows.
+    Intake = 0,
+    /// Progressing work already in flight: auctions, results and verification.
+    Settlement = 1,
+    /// Funds leaving the protocol: claims, refunds, closes and expiries.
+    Payout = 2,
+}
+
+impl ConfigPolicyV2PauseGroup {
+    pub const ALL: [ConfigPolicyV2PauseGroup; 3] = [
+        ConfigPolicyV2PauseGroup::Intake,
+        ConfigPolicyV2PauseGroup::Settlement,
+        ConfigPolicyV2PauseGroup::Payout,
+    ];
+
+    pub const fn mask(self) -> ConfigPolicyV2PauseGroups {
+        ConfigPolicyV2PauseGroups(1u8 << self as u8)
+    }
+}
+
+#[derive(Pod, Clone, Copy, Zeroable, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[repr(transparent)]
+pub struct ConfigPolicyV2PauseGroups(u8);
+
+impl ConfigPolicyV2PauseGroups {
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    pub const fn from_bits(bits: u8) -> Self {
+        Self(bits)
+    }
+
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    pub const fn contains(self, group: ConfigPolicyV2PauseGroup) -> bool {
+        self.0 & group.mask().0 != 0
+    }
+
+    pub const fn contains_all(self, groups: Self) -> bool {
+        self.0 & groups.0 == groups.0
+    }
+
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
 pub const CONFIG_POLICY_V2_BUNDLE_ESCROW_RESERVED_BYTES: usize = 64;
 pub const CONFIG_POLICY_V2_BUNDLE_VERIFIER_PAGE_RESERVED_BYTES: usize = 64;
-pub const CONFIG_POLICY_V2_TYPED_RESERVED_WORDS: usize = 8;
-pub const CONFIG_POLICY_V2_TYPED_RESERVED_LAYOUT_PADDING_BYTES: usize = 7;
+pub const CONFIG_POLICY_V2_TYPED_RESERVED_WORDS: usize = 6;
+pub const PROTOCOL_FEE_BPS_DENOMINATOR: u16 = 10_000;
+pub const CONFIG_POLICY_V2_TYPED_RESERVED_LAYOUT_PADDING_BYTES: usize = 5;
 pub const CONFIG_POLICY_V2_TYPED_RESERVED_TAIL_BYTES: usize = 16;
 
 #[derive(Pod, Clone, Copy, Zeroable, Debug, PartialEq, Eq)]
@@ -138,6 +255,72 @@ impl RequestTierConfigV2 {
     }
 }
 
+impl TierParams for RequestTierConfigV2 {
+    fn get_bid_reveal_duration(&self) -> u64 {
+        self.bid_reveal_duration
+    }
+
+    fn get_active_auction_duration(&self) -> u64 {
+        self.active_auction_duration
+    }
+
+    fn get_bundle_duration(&self) -> u64 {
+        self.bundle_duration
+    }
+
+    fn get_request_per_bundle(&self) -> u64 {
+        self.requests_per_bundle
+    }
+
+    fn get_max_context_length_tokens(&self) -> u64 {
+        self.max_context_length_tokens
+    }
+
+    fn get_job_submission_duration_slots(&self) -> u64 {
+        self.job_submission_duration_slots
+    }
+
+    fn get_bid_commitment_amount_multiplier(&self) -> u64 {
+        self.bid_commitment_amount_multiplier
+    }
+
+    fn get_auction_credits_multiplier(&self) -> u64 {
+        self.auction_credits_multiplier
+    }
+
+    fn get_v2_settlement_window_slots(&self) -> u64 {
+        self.settlement_window_slots
+    }
+
+    fn get_v2_result_window_slots(&self) -> u64 {
+        self.result_window_slots
+    }
+
+    fn get_v2_verification_window_slots(&self) -> u64 {
+        self.verification_window_slots
+    }
+
+    fn get_v2_claim_window_slots(&self) -> u64 {
+        self.claim_window_slots
+    }
+}
+
+/// The patches from [`ConfigPolicyV2::diff`], split by how they reach the policy.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConfigPolicyV2Diff {
+    /// Patches for [`ConfigPolicyV2::apply`], in the order they have to be applied.
+    pub direct: Vec<ConfigPolicyV2Patch>,
+    /// Patches that [affect escrows in flight](ConfigPolicyV2Patch::affects_in_flight_escrows)
+    /// and have to go through a `ConfigPolicyV2Schedule`.
+    pub scheduled: Vec<ConfigPolicyV2Patch>,
+}
+
+impl ConfigPolicyV2Diff {
+    pub fn is_empty(&self) -> bool {
+        self.direct.is_empty() && self.scheduled.is_empty()
+    }
+}
+
 #[derive(Pod, Clone, Copy, Zeroable, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 #[repr(C)]
@@ -150,10 +333,29 @@ pub struct ConfigPolicyV2 {
     pub service_authorities: [Pubkey; CONFIG_POLICY_V2_SERVICE_CAPACITY],
     pub v2_verifiers_per_auction: u8,
     pub v2_verifier_quorum: u8,
-    pub _reserved1: [u8; 6],
+    /// Admin approvals a `ConfigPolicyV2Proposal` needs before it can execute. `0` predates
+    /// the field and behaves like `1`, which also allows direct `SetConfigPolicyV2` updates.
+    pub admin_approval_threshold: u8,
+    pub _reserved1: [u8; 5],
     pub tier_configs: [RequestTierConfigV2; CONFIG_POLICY_V2_TIER_CONFIG_COUNT],
+    /// Receives the protocol fee withheld from V2 winner payouts.
+    pub protocol_fee_recipient: Pubkey,
+    /// Protocol fee in basis points of the gross V2 winner payout.
+    pub protocol_fee_bps: u16,
+    pub _reserved3: [u8; 6],
+    /// Lower bound for the protocol fee. The fee never exceeds the winner payout itself.
+    pub protocol_min_fee_lamports: u64,
+    /// Node penalties in basis points of the award the escrow was opened for.
+    pub rejected_result_penalty_bps: u16,
+    pub missed_result_penalty_bps: u16,
+    pub missed_verification_penalty_bps: u16,
+    pub _reserved4: [u8; 10],
     pub reserved_words: [[u8; 32]; CONFIG_POLICY_V2_TYPED_RESERVED_WORDS],
     pub v2_account_layout_version: u8,
+    pub paused_instruction_groups: ConfigPolicyV2PauseGroups,
+    /// Layout of newly opened `BundleEscrowV2` accounts. `0` predates the field and opens
+    /// escrows in the `v2_account_layout_version` layout.
+    pub bundle_escrow_v2_layout_version: u8,
     pub _reserved2: [u8; CONFIG_POLICY_V2_TYPED_RESERVED_LAYOUT_PADDING_BYTES],
     pub reserved_tail: [u8; CONFIG_POLICY_V2_TYPED_RESERVED_TAIL_BYTES],
 }
@@ -177,7 +379,8 @@ impl ConfigPolicyV2 {
             service_authorities: [Pubkey::default(); CONFIG_POLICY_V2_SERVICE_CAPACITY],
             v2_verifiers_per_auction: MAX_VERIFIERS_PER_AUCTION as u8,
             v2_verifier_quorum: PRODUCTION_V2_VERIFIER_QUORUM,
-            _reserved1: [0; 6],
+            admin_approval_threshold: 1,
+            _reserved1: [0; 5],
             tier_configs: [
                 RequestTierConfigV2::production_default_for_tier(RequestTier::Eco),
                 RequestTierConfigV2::production_default_for_tier(RequestTier::Small),
@@ -185,27 +388,547 @@ impl ConfigPolicyV2 {
                 RequestTierConfigV2::production_default_for_tier(RequestTier::Pro),
                 RequestTierConfigV2::production_default_for_tier(RequestTier::Large),
             ],
+            protocol_fee_recipient: Pubkey::default(),
+            protocol_fee_bps: 0,
+            _reserved3: [0; 6],
+            protocol_min_fee_lamports: 0,
+            rejected_result_penalty_bps: 0,
+            missed_result_penalty_bps: 0,
+            missed_verification_penalty_bps: 0,
+            _reserved4: [0; 10],
             reserved_words: [[0; 32]; CONFIG_POLICY_V2_TYPED_RESERVED_WORDS],
             v2_account_layout_version: AccountLayoutVersion::V2 as u8,
+            paused_instruction_groups: ConfigPolicyV2PauseGroups::empty(),
+            bundle_escrow_v2_layout_version: AccountLayoutVersion::V5 as u8,
             _reserved2: [0; CONFIG_POLICY_V2_TYPED_RESERVED_LAYOUT_PADDING_BYTES],
             reserved_tail: [0; CONFIG_POLICY_V2_TYPED_RESERVED_TAIL_BYTES],
         }
     }
 
     pub fn tier_config(&self, tier: RequestTier) -> &RequestTierConfigV2 {
-        &self.tier_configs[match tier {
+        &self.tier_configs[Self::tier_config_index(tier)]
+    }
+
+    fn tier_config_index(tier: RequestTier) -> usize {
+        match tier {
             RequestTier::Eco => 0,
             RequestTier::Small => 1,
             RequestTier::Standard => 2,
             RequestTier::Pro => 3,
             RequestTier::Large => 4,
-        }]
+        }
+    }
+
+    pub fn is_admin(&self, key: &Pubkey) -> bool {
+        *key != Pubkey::default() && self.admin_authorities.contains(key)
+    }
+
+    pub fn is_service(&self, key: &Pubkey) -> bool {
+        *key != Pubkey::default() && self.service_authorities.contains(key)
+    }
+
+    /// Whether `signer` may perform `action`. Policy updates need an admin; service overrides
+    /// need a service authority and the matching [`ConfigPolicyV2Flag`] to be enabled.
+    pub fn can(&self, signer: &Pubkey, action: ConfigPolicyV2Action) -> bool {
+        match action.required_flag() {
+            None => self.is_admin(signer),
+            Some(flag) => self.is_service(signer) && self.policy_flags.contains(flag),
+        }
+    }
+
+    /// Occupied authority slots, admins first, each in slot order.
+    pub fn authority_slots(&self) -> impl Iterator<Item = ConfigPolicyV2AuthoritySlot> + '_ {
+        let admins = self
+            .admin_authorities
+            .iter()
+            .enumerate()
+            .map(|(index, key)| (ConfigPolicyV2AuthorityKind::ADMIN, index, key));
+        let services = self
+            .service_authorities
+            .iter()
+            .enumerate()
+            .map(|(index, key)| (ConfigPolicyV2AuthorityKind::SERVICE, index, key));
+
+        admins
+            .chain(services)
+            .filter(|(_, _, key)| **key != Pubkey::default())
+            .map(|(kind, index, key)| ConfigPolicyV2AuthoritySlot {
+                kind,
+                index: index as u8,
+                authority: *key,
+            })
+    }
+
+    /// Stores `key` in the first free slot of `kind` and returns that slot index.
+    pub fn add_authority(
+        &mut self,
+        kind: ConfigPolicyV2AuthorityKind,
+        key: Pubkey,
+    ) -> Result<u8, AuctionError> {
+        if key == Pubkey::default() {
+            return Err(AuctionError::InvalidConfigPolicyAuthority);
+        }
+
+        if self.holds_authority(&key, None) {
+            return Err(AuctionError::DuplicateConfigPolicyAuthority);
+        }
+        let slots = self.authority_slots_mut(kind)?;
+        let index = slots
+            .iter()
+            .position(|slot| *slot == Pubkey::default())
+            .ok_or(AuctionError::ConfigPolicyAuthoritySlotsFull)?;
+        slots[index] = key;
+        Ok(index as u8)
+    }
+
+    /// Clears the slot holding `key` and returns its index. The last admin can not be removed.
+    pub fn remove_authority(
+        &mut self,
+        kind: ConfigPolicyV2AuthorityKind,
+        key: &Pubkey,
+    ) -> Result<u8, AuctionError> {
+        if *key == Pubkey::default() {
+            return Err(AuctionError::InvalidConfigPolicyAuthority);
+        }
+
+        let slots = self.authority_slots_mut(kind)?;
+        let index = slots
+            .iter()
+            .position(|slot| slot == key)
+            .ok_or(AuctionError::ConfigPolicyAuthorityNotFound)?;
+        slots[index] = Pubkey::default();
+
+        if kind == ConfigPolicyV2AuthorityKind::ADMIN {
+            if let Err(err) = self.check_admin_set() {
+                self.admin_authorities[index] = *key;
+                return Err(err);
+            }
+        }
+        Ok(index as u8)
+    }
+
+    fn has_any_admin(&self) -> bool {
+        self.admin_count() != 0
+    }
+
+    pub fn admin_count(&self) -> u8 {
+        self.admin_authorities
+            .iter()
+            .filter(|key| **key != Pubkey::default())
+            .count() as u8
+    }
+
+    pub fn required_admin_approvals(&self) -> u8 {
+        self.admin_approval_threshold.max(1)
+    }
+
+    /// Slot index of `key` among the admin authorities.
+    pub fn admin_slot(&self, key: &Pubkey) -> Option<u8> {
+        if *key == Pubkey::default() {
+            return None;
+        }
+        self.admin_authorities
+            .iter()
+            .position(|admin| admin == key)
+            .map(|index| index as u8)
+    }
+
+    /// Whether `key` sits in any admin or service slot other than `except`. A key may hold
+    /// only one slot across both kinds.
+    fn holds_authority(
+        &self,
+        key: &Pubkey,
+        except: Option<(ConfigPolicyV2AuthorityKind, u8)>,
+    ) -> bool {
+        *key != Pubkey::default()
+            && self
+                .authority_slots()
+                .any(|slot| slot.authority == *key && Some((slot.kind, slot.index)) != except)
+    }
+
+    fn check_admin_set(&self) -> Result<(), AuctionError> {
+        if !self.has_any_admin() {
+            return Err(AuctionError::LastConfigPolicyAdmin);
+        }
+        if self.admin_count() < self.required_admin_approvals() {
+            return Err(AuctionError::InvalidAdminApprovalThreshold);
+        }
+        Ok(())
+    }
+
+    fn authority_slots_mut(
+        &mut self,
+        kind: ConfigPolicyV2AuthorityKind,
+    ) -> Result<&mut [Pubkey], AuctionError> {
+        match kind {
+            ConfigPolicyV2AuthorityKind::ADMIN => Ok(&mut self.admin_authorities[..]),
+            ConfigPolicyV2AuthorityKind::SERVICE => Ok(&mut self.service_authorities[..]),
+            _ => Err(AuctionError::InvalidConfigPolicyV2Patch),
+        }
+    }
+
+    /// Validates `patch` and writes it into the policy. `signer` must be an admin authority,
+    /// and the policy must not require more than one admin approval; otherwise the patch has
+    /// to go through a `ConfigPolicyV2Proposal`.
+    ///
+    /// Pausing more instruction groups is the exception: any single admin may do it, so an
+    /// incident can be contained without waiting for a quorum. Unpausing needs full approval.
+    ///
+    /// Patches that [affect escrows in flight](ConfigPolicyV2Patch::affects_in_flight_escrows)
+    /// are rejected; they have to go through a `ConfigPolicyV2Schedule`.
+    pub fn apply(
+        &mut self,
+        patch: &ConfigPolicyV2Patch,
+        signer: &Pubkey,
+    ) -> Result<(), AuctionError> {
+        if patch.affects_in_flight_escrows() {
+            return Err(AuctionError::ConfigPolicyV2PatchMustBeScheduled);
+        }
+        match *patch {
+            ConfigPolicyV2Patch::PausedInstructionGroups(groups)
+                if groups.contains_all(self.paused_instruction_groups) =>
+            {
+                if !self.is_admin(signer) {
+                    return Err(AuctionError::UnauthorizedConfigPolicyAuthority);
+                }
+            }
+            _ => self.check_direct_update(signer)?,
+        }
+        self.apply_approved(patch)
+    }
+
+    pub fn is_instruction_paused(&self, instruction: AuctionInstruction) -> bool {
+        instruction
+            .pause_group()
+            .is_some_and(|group| self.paused_instruction_groups.contains(group))
+    }
+
+    pub fn check_instruction_not_paused(
+        &self,
+        instruction: AuctionInstruction,
+    ) -> Result<(), AuctionError> {
+        if self.is_instruction_paused(instruction) {
+            return Err(AuctionError::ProtocolPaused);
+        }
+        Ok(())
+    }
+
+    /// The protocol fee withheld from a gross winner payout: `protocol_fee_bps` of the payout,
+    /// at least `protocol_min_fee_lamports`, and never more than the payout.
+    pub fn protocol_fee_lamports(&self, winner_payout_lamports: u64) -> u64 {
+        self.protocol_fee_bps_amount(winner_payout_lamports)
+            .max(self.protocol_min_fee_lamports)
+            .min(winner_payout_lamports)
+    }
+
+    /// The basis-point part of the protocol fee alone, for amounts not counted in lamports.
+    pub fn protocol_fee_bps_amount(&self, winner_payout_amount: u64) -> u64 {
+        (u128::from(winner_payout_amount) * u128::from(self.protocol_fee_bps)
+            / u128::from(PROTOCOL_FEE_BPS_DENOMINATOR)) as u64
+    }
+
+    pub fn node_penalty_bps(&self, kind: NodePenaltyKind) -> u16 {
+        match kind {
+            NodePenaltyKind::REJECTED_RESULT => self.rejected_result_penalty_bps,
+            NodePenaltyKind::MISSED_RESULT => self.missed_result_penalty_bps,
+            NodePenaltyKind::MISSED_VERIFICATION => self.missed_verification_penalty_bps,
+            _ => 0,
+        }
+    }
+
+    /// `node_penalty_bps` of `award_amount`.
+    pub fn node_penalty_amount(&self, kind: NodePenaltyKind, award_amount: u64) -> u64 {
+        (u128::from(award_amount) * u128::from(self.node_penalty_bps(kind))
+            / u128::from(PROTOCOL_FEE_BPS_DENOMINATOR)) as u64
+    }
+
+    pub fn charges_protocol_fee(&self) -> bool {
+        self.protocol_fee_bps != 0 || self.protocol_min_fee_lamports != 0
+    }
+
+    /// Checks the fee-recipient account passed to V2 finalize. Any account is accepted while
+    /// no fee is charged.
+    pub fn check_protocol_fee_recipient(&self, recipient: &Pubkey) -> Result<(), AuctionError> {
+        if self.charges_protocol_fee() && *recipient != self.protocol_fee_recipient {
+            return Err(AuctionError::InvalidProtocolFeeRecipient);
+        }
+        Ok(())
+    }
+
+    /// Checks that `signer` may change the policy without a proposal.
+    pub fn check_direct_update(&self, signer: &Pubkey) -> Result<(), AuctionError> {
+        if !self.is_admin(signer) {
+            return Err(AuctionError::UnauthorizedConfigPolicyAuthority);
+        }
+        if self.required_admin_approvals() > 1 {
+            return Err(AuctionError::ConfigPolicyV2ProposalRequired);
+        }
+        Ok(())
+    }
+
+    /// [`Self::apply`] without the signer checks, for patches whose approvals were already
+    /// counted.
+    pub(crate) fn apply_approved(
+        &mut self,
+        patch: &ConfigPolicyV2Patch,
+    ) -> Result<(), AuctionError> {
+        match *patch {
+            ConfigPolicyV2Patch::Flags(policy_flags) => self.policy_flags = policy_flags,
+            ConfigPolicyV2Patch::Authority {
+                kind,
+                index,
+                authority,
+            } => {
+                if self.holds_authority(&authority, Some((kind, index))) {
+                    return Err(AuctionError::DuplicateConfigPolicyAuthority);
+                }
+                let slot = self
+                    .authority_slots_mut(kind)?
+                    .get_mut(usize::from(index))
+                    .ok_or(AuctionError::InvalidConfigPolicyV2Patch)?;
+                let previous = std::mem::replace(slot, authority);
+
+                if kind == ConfigPolicyV2AuthorityKind::ADMIN {
+                    if let Err(err) = self.check_admin_set() {
+                        self.admin_authorities[usize::from(index)] = previous;
+                        return Err(err);
+                    }
+                }
+            }
+            ConfigPolicyV2Patch::VerifierSettings {
+                v2_verifiers_per_auction,
+                v2_verifier_quorum,
+            } => {
+                if v2_verifiers_per_auction == 0
+                    || usize::from(v2_verifiers_per_auction) > self.verifier_capacity()
+                {
+                    return Err(AuctionError::InvalidVerifierCount);
+                }
+                if v2_verifier_quorum == 0 || v2_verifier_quorum > v2_verifiers_per_auction {
+                    return Err(AuctionError::InvalidVerifierQuorum);
+                }
+                self.v2_verifiers_per_auction = v2_verifiers_per_auction;
+                self.v2_verifier_quorum = v2_verifier_quorum;
+            }
+            ConfigPolicyV2Patch::TierConfig { tier, tier_config } => {
+                if !tier_config.validate() {
+                    return Err(AuctionError::InvalidTierConfig);
+                }
+                self.tier_configs[Self::tier_config_index(tier)] = tier_config;
+            }
+            ConfigPolicyV2Patch::MaxAuctionCreditsPerUpdate(max_auction_credits_per_update) => {
+                self.max_auction_credits_per_update = max_auction_credits_per_update;
+            }
+            ConfigPolicyV2Patch::AdminApprovalThreshold(admin_approval_threshold) => {
+                if admin_approval_threshold == 0 || admin_approval_threshold > self.admin_count() {
+                    return Err(AuctionError::InvalidAdminApprovalThreshold);
+                }
+                self.admin_approval_threshold = admin_approval_threshold;
+            }
+            ConfigPolicyV2Patch::PausedInstructionGroups(groups) => {
+                let known = ConfigPolicyV2PauseGroup::ALL
+                    .into_iter()
+                    .fold(ConfigPolicyV2PauseGroups::empty(), |known, group| {
+                        known.union(group.mask())
+                    });
+                if !known.contains_all(groups) {
+                    return Err(AuctionError::InvalidConfigPolicyV2Patch);
+                }
+                self.paused_instruction_groups = groups;
+            }
+            ConfigPolicyV2Patch::ProtocolFee {
+                protocol_fee_bps,
+                protocol_min_fee_lamports,
+                protocol_fee_recipient,
+            } => {
+                let charges_fee = protocol_fee_bps != 0 || protocol_min_fee_lamports != 0;
+                if protocol_fee_bps > PROTOCOL_FEE_BPS_DENOMINATOR
+                    || (charges_fee && protocol_fee_recipient == Pubkey::default())
+                {
+                    return Err(AuctionError::InvalidProtocolFee);
+                }
+                self.protocol_fee_bps = protocol_fee_bps;
+                self.protocol_min_fee_lamports = protocol_min_fee_lamports;
+                self.protocol_fee_recipient = protocol_fee_recipient;
+            }
+            ConfigPolicyV2Patch::NodePenalties {
+                rejected_result_penalty_bps,
+                missed_result_penalty_bps,
+                missed_verification_penalty_bps,
+            } => {
+                if [
+                    rejected_result_penalty_bps,
+                    missed_result_penalty_bps,
+                    missed_verification_penalty_bps,
+                ]
+                .into_iter()
+                .any(|bps| bps > PROTOCOL_FEE_BPS_DENOMINATOR)
+                {
+                    return Err(AuctionError::InvalidNodePenalty);
+                }
+                self.rejected_result_penalty_bps = rejected_result_penalty_bps;
+                self.missed_result_penalty_bps = missed_result_penalty_bps;
+                self.missed_verification_penalty_bps = missed_verification_penalty_bps;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The patches that turn `old` into `new`, one per changed field group.
+    ///
+    /// Fields that `SetConfigPolicyV2` can not update (bump, bundle-auction pairs, layout
+    /// version and reserved bytes) are not compared. A lowered admin approval threshold is
+    /// emitted first and a raised one after the other settings, so admin slot changes never
+    /// strand the threshold. Patches that replace or clear an admin come last, so an admin
+    /// rotating itself out can still sign everything before them.
+    ///
+    /// Verifier settings and tier configs are returned in
+    /// [`ConfigPolicyV2Diff::scheduled`], since `apply` rejects them.
+    pub fn diff(old: &Self, new: &Self) -> ConfigPolicyV2Diff {
+        let mut patches = Vec::new();
+        let mut scheduled = Vec::new();
+        let mut admin_removals = Vec::new();
+        let threshold_patch = (old.required_admin_approvals() != new.required_admin_approvals())
+            .then(|| ConfigPolicyV2Patch::AdminApprovalThreshold(new.required_admin_approvals()));
+        let threshold_lowered = new.required_admin_approvals() < old.required_admin_approvals();
+
+        if threshold_lowered {
+            patches.extend(threshold_patch);
+        }
+
+        if old.policy_flags != new.policy_flags {
+            patches.push(ConfigPolicyV2Patch::Flags(new.policy_flags));
+        }
+
+        for (kind, old_slots, new_slots) in [
+            (
+                ConfigPolicyV2AuthorityKind::ADMIN,
+                &old.admin_authorities[..],
+                &new.admin_authorities[..],
+            ),
+            (
+                ConfigPolicyV2AuthorityKind::SERVICE,
+                &old.service_authorities[..],
+                &new.service_authorities[..],
+            ),
+        ] {
+            for (index, (old_key, new_key)) in old_slots.iter().zip(new_slots).enumerate() {
+                if old_key == new_key {
+                    continue;
+                }
+                let patch = ConfigPolicyV2Patch::Authority {
+                    kind,
+                    index: index as u8,
+                    authority: *new_key,
+                };
+                if kind == ConfigPolicyV2AuthorityKind::ADMIN && *old_key != Pubkey::default() {
+                    admin_removals.push(patch);
+                } else {
+                    patches.push(patch);
+                }
+            }
+        }
+
+        if old.v2_verifiers_per_auction != new.v2_verifiers_per_auction
+            || old.v2_verifier_quorum != new.v2_verifier_quorum
+        {
+            scheduled.push(ConfigPolicyV2Patch::VerifierSettings {
+                v2_verifiers_per_auction: new.v2_verifiers_per_auction,
+                v2_verifier_quorum: new.v2_verifier_quorum,
+            });
+        }
+
+        for tier in RequestTier::ALL {
+            if old.tier_config(tier) != new.tier_config(tier) {
+                scheduled.push(ConfigPolicyV2Patch::TierConfig {
+                    tier,
+                    tier_config: *new.tier_config(tier),
+                });
+            }
+        }
+
+        if old.max_auction_credits_per_update != new.max_auction_credits_per_update {
+            patches.push(ConfigPolicyV2Patch::MaxAuctionCreditsPerUpdate(
+                new.max_auction_credits_per_update,
+            ));
+        }
+
+        if !threshold_lowered {
+            patches.extend(threshold_patch);
+        }
+
+        if old.paused_instruction_groups != new.paused_instruction_groups {
+            patches.push(ConfigPolicyV2Patch::PausedInstructionGroups(
+                new.paused_instruction_groups,
+            ));
+        }
+
+        if old.protocol_fee_bps != new.protocol_fee_bps
+            || old.protocol_min_fee_lamports != new.protocol_min_fee_lamports
+            || old.protocol_fee_recipient != new.protocol_fee_recipient
+        {
+            patches.push(ConfigPolicyV2Patch::ProtocolFee {
+                protocol_fee_bps: new.protocol_fee_bps,
+                protocol_min_fee_lamports: new.protocol_min_fee_lamports,
+                protocol_fee_recipient: new.protocol_fee_recipient,
+            });
+        }
+
+        if old.rejected_result_penalty_bps != new.rejected_result_penalty_bps
+            || old.missed_result_penalty_bps != new.missed_result_penalty_bps
+            || old.missed_verification_penalty_bps != new.missed_verification_penalty_bps
+        {
+            patches.push(ConfigPolicyV2Patch::NodePenalties {
+                rejected_result_penalty_bps: new.rejected_result_penalty_bps,
+                missed_result_penalty_bps: new.missed_result_penalty_bps,
+                missed_verification_penalty_bps: new.missed_verification_penalty_bps,
+            });
+        }
+
+        patches.extend(admin_removals);
+        ConfigPolicyV2Diff {
+            direct: patches,
+            scheduled,
+        }
     }
 
     pub fn configured_v2_account_layout_version(&self) -> Result<AccountLayoutVersion, u8> {
         match AccountLayoutVersion::try_from(self.v2_account_layout_version) {
-            Ok(version @ (AccountLayoutVersion::V1 | AccountLayoutVersion::V2)) => Ok(version),
+            Ok(
+                version @ (AccountLayoutVersion::V1
+                | AccountLayoutVersion::V2
+                | AccountLayoutVersion::V6),
+            ) => Ok(version),
             _ => Err(self.v2_account_layout_version),
         }
     }
+
+    /// Layout for newly opened `BundleEscrowV2` accounts, from `bundle_escrow_v2_layout_version`.
+    /// V6 escrows keep their verifiers in V6 verifier pages, so they need the V6
+    /// `v2_account_layout_version` and every other escrow layout needs an earlier one.
+    pub fn configured_bundle_escrow_v2_layout_version(&self) -> Result<AccountLayoutVersion, u8> {
+        let page_version = self.configured_v2_account_layout_version()?;
+        let configured = self.bundle_escrow_v2_layout_version;
+        if configured == 0 {
+            return Ok(page_version);
+        }
+        match AccountLayoutVersion::try_from(configured) {
+            Ok(AccountLayoutVersion::LegacyV0) | Err(_) => Err(configured),
+            Ok(version)
+                if (version == AccountLayoutVersion::V6)
+                    != (page_version == AccountLayoutVersion::V6) =>
+            {
+                Err(configured)
+            }
+            Ok(version) => Ok(version),
+        }
+    }
+
+    /// Verifier capacity of the escrows and verifier pages opened under the configured layout,
+    /// the upper bound for `v2_verifiers_per_auction`.
+    pub fn verifier_capacity(&self) -> usize {
+        self.configured_v2_account_layout_version()
+            .map_or(MAX_VERIFIERS_PER_AUCTION, bundle_v2_verifier_capacity)
+    }
 }
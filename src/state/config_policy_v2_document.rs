@@ -0,0 +1,523 @@
+//! Human-editable representation of [`ConfigPolicyV2`] for keeping policies in version control.
+//!
+//! Flags are listed by name, tiers are keyed by [`RequestTier::name`] and authorities are
+//! base58 strings listed by slot, with an empty string for an empty slot. Bump and reserved
+//! bytes are not part of the document.
+
+use super::{
+    ConfigPolicyV2, ConfigPolicyV2Flag, ConfigPolicyV2Flags, ConfigPolicyV2PauseGroup,
+    ConfigPolicyV2PauseGroups, Pubkey, RequestTier, RequestTierConfigV2,
+    CONFIG_POLICY_V2_ADMIN_CAPACITY, CONFIG_POLICY_V2_SERVICE_CAPACITY,
+    PROTOCOL_FEE_BPS_DENOMINATOR,
+};
+use crate::{MAX_EXTENDED_VERIFIERS_PER_AUCTION, MAX_VERIFIERS_PER_AUCTION};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use thiserror::Error;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigPolicyV2Document {
+    pub minimum_bundle_auction_pairs: u64,
+    pub max_auction_credits_per_update: u64,
+    pub v2_verifiers_per_auction: u8,
+    pub v2_verifier_quorum: u8,
+    pub v2_account_layout_version: u8,
+    /// Omitted or `0` opens escrows in the `v2_account_layout_version` layout.
+    #[serde(default)]
+    pub bundle_escrow_v2_layout_version: u8,
+    #[serde(default)]
+    pub flags: Vec<ConfigPolicyV2Flag>,
+    #[serde(default)]
+    pub paused_instruction_groups: Vec<ConfigPolicyV2PauseGroup>,
+    /// Position `i` is admin slot `i`; an empty string leaves the slot empty.
+    #[serde(default)]
+    pub admin_authorities: Vec<String>,
+    /// Position `i` is service slot `i`; an empty string leaves the slot empty.
+    #[serde(default)]
+    pub service_authorities: Vec<String>,
+    /// Omitted or `0` means a single admin approval.
+    #[serde(default)]
+    pub admin_approval_threshold: u8,
+    #[serde(default)]
+    pub protocol_fee_bps: u16,
+    #[serde(default)]
+    pub protocol_min_fee_lamports: u64,
+    /// Required whenever a protocol fee is charged.
+    #[serde(default)]
+    pub protocol_fee_recipient: Option<String>,
+    #[serde(default)]
+    pub rejected_result_penalty_bps: u16,
+    #[serde(default)]
+    pub missed_result_penalty_bps: u16,
+    #[serde(default)]
+    pub missed_verification_penalty_bps: u16,
+    pub tiers: BTreeMap<String, RequestTierConfigV2Document>,
+}
+
+/// One entry of [`ConfigPolicyV2Document::tiers`], the fields of [`RequestTierConfigV2`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RequestTierConfigV2Document {
+    pub bid_reveal_duration: u64,
+    pub active_auction_duration: u64,
+    pub bundle_duration: u64,
+    pub requests_per_bundle: u64,
+    pub max_context_length_tokens: u64,
+    pub job_submission_duration_slots: u64,
+    pub bid_commitment_amount_multiplier: u64,
+    pub auction_credits_multiplier: u64,
+    pub settlement_window_slots: u64,
+    pub result_window_slots: u64,
+    pub verification_window_slots: u64,
+    pub claim_window_slots: u64,
+}
+
+impl From<RequestTierConfigV2> for RequestTierConfigV2Document {
+    fn from(config: RequestTierConfigV2) -> Self {
+        Self {
+            bid_reveal_duration: config.bid_reveal_duration,
+            active_auction_duration: config.active_auction_duration,
+            bundle_duration: config.bundle_duration,
+            requests_per_bundle: config.requests_per_bundle,
+            max_context_length_tokens: config.max_context_length_tokens,
+            job_submission_duration_slots: config.job_submission_duration_slots,
+            bid_commitment_amount_multiplier: config.bid_commitment_amount_multiplier,
+            auction_credits_multiplier: config.auction_credits_multiplier,
+            settlement_window_slots: config.settlement_window_slots,
+            result_window_slots: config.result_window_slots,
+            verification_window_slots: config.verification_window_slots,
+            claim_window_slots: config.claim_window_slots,
+        }
+    }
+}
+
+impl From<RequestTierConfigV2Document> for RequestTierConfigV2 {
+    fn from(document: RequestTierConfigV2Document) -> Self {
+        Self {
+            bid_reveal_duration: document.bid_reveal_duration,
+            active_auction_duration: document.active_auction_duration,
+            bundle_duration: document.bundle_duration,
+            requests_per_bundle: document.requests_per_bundle,
+            max_context_length_tokens: document.max_context_length_tokens,
+            job_submission_duration_slots: document.job_submission_duration_slots,
+            bid_commitment_amount_multiplier: document.bid_commitment_amount_multiplier,
+            auction_credits_multiplier: document.auction_credits_multiplier,
+            settlement_window_slots: document.settlement_window_slots,
+            result_window_slots: document.result_window_slots,
+            verification_window_slots: document.verification_window_slots,
+            claim_window_slots: document.claim_window_slots,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub enum ConfigPolicyV2DocumentErrorKind {
+    #[error("failed to parse document: {0}")]
+    Parse(String),
+    #[error("failed to serialize document: {0}")]
+    Serialize(String),
+    #[error("flag is listed more than once")]
+    DuplicateFlag,
+    #[error("unknown flag bits {0:#x}")]
+    UnknownFlagBits(u64),
+    #[error("pause group is listed more than once")]
+    DuplicatePauseGroup,
+    #[error("invalid base58 pubkey")]
+    InvalidPubkey,
+    #[error("the default pubkey can not be an authority")]
+    DefaultPubkey,
+    #[error("authority is listed more than once")]
+    DuplicateAuthority,
+    #[error("too many authorities, at most {0} are allowed")]
+    TooManyAuthorities(usize),
+    #[error("at least one admin authority is required")]
+    MissingAdminAuthority,
+    #[error("unknown tier name")]
+    UnknownTier,
+    #[error("tier is missing")]
+    MissingTier,
+    #[error("all tier config values must be non-zero")]
+    InvalidTierConfig,
+    #[error(
+        "verifier count must be between 1 and the capacity of the account layout \
+         ({MAX_VERIFIERS_PER_AUCTION}, or {MAX_EXTENDED_VERIFIERS_PER_AUCTION} for V6)"
+    )]
+    InvalidVerifierCount,
+    #[error("verifier quorum must be between 1 and the verifier count")]
+    InvalidVerifierQuorum,
+    #[error("admin approval threshold exceeds the number of admin authorities")]
+    InvalidAdminApprovalThreshold,
+    #[error("protocol fee must be at most {PROTOCOL_FEE_BPS_DENOMINATOR} bps")]
+    InvalidProtocolFeeBps,
+    #[error("a protocol fee recipient is required while a protocol fee is charged")]
+    MissingProtocolFeeRecipient,
+    #[error("node penalty must be at most {PROTOCOL_FEE_BPS_DENOMINATOR} bps")]
+    InvalidNodePenaltyBps,
+    #[error("unsupported account layout version")]
+    InvalidAccountLayoutVersion,
+}
+
+/// A validation or parse failure, located by a dotted path into the document
+/// (for example `tiers.Pro` or `admin_authorities[2]`).
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub struct ConfigPolicyV2DocumentError {
+    pub path: String,
+    pub kind: ConfigPolicyV2DocumentErrorKind,
+}
+
+impl ConfigPolicyV2DocumentError {
+    fn new(path: impl Into<String>, kind: ConfigPolicyV2DocumentErrorKind) -> Self {
+        Self {
+            path: path.into(),
+            kind,
+        }
+    }
+}
+
+impl Display for ConfigPolicyV2DocumentError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.kind)
+        } else {
+            write!(f, "{}: {}", self.path, self.kind)
+        }
+    }
+}
+
+type DocumentResult<T> = Result<T, ConfigPolicyV2DocumentError>;
+
+impl ConfigPolicyV2Document {
+    pub fn from_toml_str(text: &str) -> DocumentResult<Self> {
+        toml::from_str(text).map_err(|e| {
+            ConfigPolicyV2DocumentError::new(
+                "",
+                ConfigPolicyV2DocumentErrorKind::Parse(e.message().to_string()),
+            )
+        })
+    }
+
+    pub fn to_toml_string(&self) -> DocumentResult<String> {
+        toml::to_string(self).map_err(|e| {
+            ConfigPolicyV2DocumentError::new(
+                "",
+                ConfigPolicyV2DocumentErrorKind::Serialize(e.to_string()),
+            )
+        })
+    }
+
+    pub fn from_json_str(text: &str) -> DocumentResult<Self> {
+        serde_json::from_str(text).map_err(|e| {
+            ConfigPolicyV2DocumentError::new(
+                "",
+                ConfigPolicyV2DocumentErrorKind::Parse(e.to_string()),
+            )
+        })
+    }
+
+    pub fn to_json_string(&self) -> DocumentResult<String> {
+        serde_json::to_string_pretty(self).map_err(|e| {
+            ConfigPolicyV2DocumentError::new(
+                "",
+                ConfigPolicyV2DocumentErrorKind::Serialize(e.to_string()),
+            )
+        })
+    }
+}
+
+fn parse_flags(flags: &[ConfigPolicyV2Flag]) -> DocumentResult<ConfigPolicyV2Flags> {
+    let mut parsed = ConfigPolicyV2Flags::empty();
+    for (index, flag) in flags.iter().enumerate() {
+        if parsed.contains(*flag) {
+            return Err(ConfigPolicyV2DocumentError::new(
+                format!("flags[{index}]"),
+                ConfigPolicyV2DocumentErrorKind::DuplicateFlag,
+            ));
+        }
+        parsed = parsed.union(flag.mask());
+    }
+    Ok(parsed)
+}
+
+fn parse_pause_groups(
+    groups: &[ConfigPolicyV2PauseGroup],
+) -> DocumentResult<ConfigPolicyV2PauseGroups> {
+    let mut parsed = ConfigPolicyV2PauseGroups::empty();
+    for (index, group) in groups.iter().enumerate() {
+        if parsed.contains(*group) {
+            return Err(ConfigPolicyV2DocumentError::new(
+                format!("paused_instruction_groups[{index}]"),
+                ConfigPolicyV2DocumentErrorKind::DuplicatePauseGroup,
+            ));
+        }
+        parsed = parsed.union(group.mask());
+    }
+    Ok(parsed)
+}
+
+fn parse_pubkey(path: impl Fn() -> String, text: &str) -> DocumentResult<Pubkey> {
+    let mut key = [0u8; 32];
+    match bs58::decode(text).onto(&mut key) {
+        Ok(32) => {}
+        _ => {
+            return Err(ConfigPolicyV2DocumentError::new(
+                path(),
+                ConfigPolicyV2DocumentErrorKind::InvalidPubkey,
+            ))
+        }
+    }
+    let key = Pubkey::from(key);
+    if key == Pubkey::default() {
+        return Err(ConfigPolicyV2DocumentError::new(
+            path(),
+            ConfigPolicyV2DocumentErrorKind::DefaultPubkey,
+        ));
+    }
+    Ok(key)
+}
+
+fn parse_authorities<const N: usize>(
+    field: &str,
+    authorities: &[String],
+) -> DocumentResult<[Pubkey; N]> {
+    if authorities.len() > N {
+        return Err(ConfigPolicyV2DocumentError::new(
+            field,
+            ConfigPolicyV2DocumentErrorKind::TooManyAuthorities(N),
+        ));
+    }
+
+    let mut parsed = [Pubkey::default(); N];
+    for (index, authority) in authorities.iter().enumerate() {
+        if authority.is_empty() {
+            continue;
+        }
+        let path = || format!("{field}[{index}]");
+        let key = parse_pubkey(path, authority)?;
+        if parsed[..index].contains(&key) {
+            return Err(ConfigPolicyV2DocumentError::new(
+                path(),
+                ConfigPolicyV2DocumentErrorKind::DuplicateAuthority,
+            ));
+        }
+        parsed[index] = key;
+    }
+    Ok(parsed)
+}
+
+/// Lists the slots up to the last occupied one, with an empty string for each empty slot.
+fn format_authorities(authorities: &[Pubkey]) -> Vec<String> {
+    let len = authorities
+        .iter()
+        .rposition(|authority| *authority != Pubkey::default())
+        .map_or(0, |last| last + 1);
+    authorities[..len]
+        .iter()
+        .map(|authority| {
+            if *authority == Pubkey::default() {
+                String::new()
+            } else {
+                bs58::encode(authority).into_string()
+            }
+        })
+        .collect()
+}
+
+impl ConfigPolicyV2 {
+    /// Builds a policy from `document`, rejecting anything `SetConfigPolicyV2` would reject.
+    ///
+    /// Authorities keep the slot of their position in the document, so pending proposals
+    /// that target a slot stay valid across an export and import. The bump is left at zero
+    /// since it is not policy content.
+    pub fn from_document(document: &ConfigPolicyV2Document) -> DocumentResult<Self> {
+        let mut policy = Self::production_default();
+
+        policy.minimum_bundle_auction_pairs = document.minimum_bundle_auction_pairs;
+        policy.max_auction_credits_per_update = document.max_auction_credits_per_update;
+        policy.policy_flags = parse_flags(&document.flags)?;
+        policy.paused_instruction_groups = parse_pause_groups(&document.paused_instruction_groups)?;
+
+        policy.admin_authorities = parse_authorities::<CONFIG_POLICY_V2_ADMIN_CAPACITY>(
+            "admin_authorities",
+            &document.admin_authorities,
+        )?;
+        if policy.admin_count() == 0 {
+            return Err(ConfigPolicyV2DocumentError::new(
+                "admin_authorities",
+                ConfigPolicyV2DocumentErrorKind::MissingAdminAuthority,
+            ));
+        }
+        policy.service_authorities = parse_authorities::<CONFIG_POLICY_V2_SERVICE_CAPACITY>(
+            "service_authorities",
+            &document.service_authorities,
+        )?;
+
+        policy.admin_approval_threshold = document.admin_approval_threshold.max(1);
+        if policy.admin_approval_threshold > policy.admin_count() {
+            return Err(ConfigPolicyV2DocumentError::new(
+                "admin_approval_threshold",
+                ConfigPolicyV2DocumentErrorKind::InvalidAdminApprovalThreshold,
+            ));
+        }
+
+        if document.protocol_fee_bps > PROTOCOL_FEE_BPS_DENOMINATOR {
+            return Err(ConfigPolicyV2DocumentError::new(
+                "protocol_fee_bps",
+                ConfigPolicyV2DocumentErrorKind::InvalidProtocolFeeBps,
+            ));
+        }
+        policy.protocol_fee_bps = document.protocol_fee_bps;
+        policy.protocol_min_fee_lamports = document.protocol_min_fee_lamports;
+        if let Some(recipient) = &document.protocol_fee_recipient {
+            policy.protocol_fee_recipient =
+                parse_pubkey(|| "protocol_fee_recipient".to_string(), recipient)?;
+        } else if policy.charges_protocol_fee() {
+            return Err(ConfigPolicyV2DocumentError::new(
+                "protocol_fee_recipient",
+                ConfigPolicyV2DocumentErrorKind::MissingProtocolFeeRecipient,
+            ));
+        }
+
+        for (path, bps) in [
+            (
+                "rejected_result_penalty_bps",
+                document.rejected_result_penalty_bps,
+            ),
+            (
+                "missed_result_penalty_bps",
+                document.missed_result_penalty_bps,
+            ),
+            (
+                "missed_verification_penalty_bps",
+                document.missed_verification_penalty_bps,
+            ),
+        ] {
+            if bps > PROTOCOL_FEE_BPS_DENOMINATOR {
+                return Err(ConfigPolicyV2DocumentError::new(
+                    path,
+                    ConfigPolicyV2DocumentErrorKind::InvalidNodePenaltyBps,
+                ));
+            }
+        }
+        policy.rejected_result_penalty_bps = document.rejected_result_penalty_bps;
+        policy.missed_result_penalty_bps = document.missed_result_penalty_bps;
+        policy.missed_verification_penalty_bps = document.missed_verification_penalty_bps;
+
+        policy.v2_account_layout_version = document.v2_account_layout_version;
+        if policy.configured_v2_account_layout_version().is_err() {
+            return Err(ConfigPolicyV2DocumentError::new(
+                "v2_account_layout_version",
+                ConfigPolicyV2DocumentErrorKind::InvalidAccountLayoutVersion,
+            ));
+        }
+        policy.bundle_escrow_v2_layout_version = document.bundle_escrow_v2_layout_version;
+        if policy.configured_bundle_escrow_v2_layout_version().is_err() {
+            return Err(ConfigPolicyV2DocumentError::new(
+                "bundle_escrow_v2_layout_version",
+                ConfigPolicyV2DocumentErrorKind::InvalidAccountLayoutVersion,
+            ));
+        }
+
+        let verifiers = document.v2_verifiers_per_auction;
+        if verifiers == 0 || usize::from(verifiers) > policy.verifier_capacity() {
+            return Err(ConfigPolicyV2DocumentError::new(
+                "v2_verifiers_per_auction",
+                ConfigPolicyV2DocumentErrorKind::InvalidVerifierCount,
+            ));
+        }
+        let quorum = document.v2_verifier_quorum;
+        if quorum == 0 || quorum > verifiers {
+            return Err(ConfigPolicyV2DocumentError::new(
+                "v2_verifier_quorum",
+                ConfigPolicyV2DocumentErrorKind::InvalidVerifierQuorum,
+            ));
+        }
+        policy.v2_verifiers_per_auction = verifiers;
+        policy.v2_verifier_quorum = quorum;
+
+        if let Some(name) = document
+            .tiers
+            .keys()
+            .find(|name| RequestTier::from_name(name).is_none())
+        {
+            return Err(ConfigPolicyV2DocumentError::new(
+                format!("tiers.{name}"),
+                ConfigPolicyV2DocumentErrorKind::UnknownTier,
+            ));
+        }
+        for (index, tier) in RequestTier::ALL.into_iter().enumerate() {
+            let path = || format!("tiers.{}", tier.name());
+            let tier_config = document.tiers.get(tier.name()).ok_or_else(|| {
+                ConfigPolicyV2DocumentError::new(
+                    path(),
+                    ConfigPolicyV2DocumentErrorKind::MissingTier,
+                )
+            })?;
+            let tier_config = RequestTierConfigV2::from(*tier_config);
+            if !tier_config.validate() {
+                return Err(ConfigPolicyV2DocumentError::new(
+                    path(),
+                    ConfigPolicyV2DocumentErrorKind::InvalidTierConfig,
+                ));
+            }
+            policy.tier_configs[index] = tier_config;
+        }
+
+        Ok(policy)
+    }
+
+    /// Exports the policy. Fails only if `policy_flags` or `paused_instruction_groups` hold
+    /// bits with no name, since those could not be written back.
+    pub fn to_document(&self) -> DocumentResult<ConfigPolicyV2Document> {
+        let flags = ConfigPolicyV2Flag::ALL
+            .into_iter()
+            .filter(|flag| self.policy_flags.contains(*flag))
+            .collect::<Vec<_>>();
+        let unknown_bits = self.policy_flags.bits() & !parse_flags(&flags)?.bits();
+        if unknown_bits != 0 {
+            return Err(ConfigPolicyV2DocumentError::new(
+                "policy_flags",
+                ConfigPolicyV2DocumentErrorKind::UnknownFlagBits(unknown_bits),
+            ));
+        }
+
+        let paused_instruction_groups = ConfigPolicyV2PauseGroup::ALL
+            .into_iter()
+            .filter(|group| self.paused_instruction_groups.contains(*group))
+            .collect::<Vec<_>>();
+        let unknown_bits = self.paused_instruction_groups.bits()
+            & !parse_pause_groups(&paused_instruction_groups)?.bits();
+        if unknown_bits != 0 {
+            return Err(ConfigPolicyV2DocumentError::new(
+                "paused_instruction_groups",
+                ConfigPolicyV2DocumentErrorKind::UnknownFlagBits(u64::from(unknown_bits)),
+            ));
+        }
+
+        Ok(ConfigPolicyV2Document {
+            minimum_bundle_auction_pairs: self.minimum_bundle_auction_pairs,
+            max_auction_credits_per_update: self.max_auction_credits_per_update,
+            v2_verifiers_per_auction: self.v2_verifiers_per_auction,
+            v2_verifier_quorum: self.v2_verifier_quorum,
+            v2_account_layout_version: self.v2_account_layout_version,
+            bundle_escrow_v2_layout_version: self.bundle_escrow_v2_layout_version,
+            flags,
+            paused_instruction_groups,
+            admin_authorities: format_authorities(&self.admin_authorities),
+            service_authorities: format_authorities(&self.service_authorities),
+            admin_approval_threshold: self.required_admin_approvals(),
+            protocol_fee_bps: self.protocol_fee_bps,
+            protocol_min_fee_lamports: self.protocol_min_fee_lamports,
+            protocol_fee_recipient: (self.protocol_fee_recipient != Pubkey::default())
+                .then(|| bs58::encode(self.protocol_fee_recipient).into_string()),
+            rejected_result_penalty_bps: self.rejected_result_penalty_bps,
+            missed_result_penalty_bps: self.missed_result_penalty_bps,
+            missed_verification_penalty_bps: self.missed_verification_penalty_bps,
+            tiers: RequestTier::ALL
+                .into_iter()
+                .map(|tier| (tier.name().to_string(), (*self.tier_config(tier)).into()))
+                .collect(),
+        })
+    }
+}
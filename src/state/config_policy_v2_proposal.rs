@@ -0,0 +1,320 @@
+use super::{
+    AccountDiscriminator, AccountHeaderV1, AccountLayoutVersion, ConfigPolicyV2,
+    ParsedAccountLayout, Pubkey, CONFIG_POLICY_V2_ADMIN_CAPACITY,
+};
+use crate::error::AuctionError;
+use crate::{ConfigPolicyV2Patch, ConfigPolicyV2Schedule, SetConfigPolicyV2Args};
+use bytemuck::{Pod, Zeroable};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::ops::{Deref, DerefMut};
+
+#[derive(Pod, Clone, Copy, Zeroable, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[repr(transparent)]
+pub struct ConfigPolicyV2ProposalStatus(pub u8);
+
+impl ConfigPolicyV2ProposalStatus {
+    pub const PENDING: Self = Self(0);
+    pub const EXECUTED: Self = Self(1);
+    pub const CANCELED: Self = Self(2);
+}
+
+/// A pending `ConfigPolicyV2` patch collecting admin approvals.
+///
+/// `approvals` is a bitmap over admin slot indices. The admin set is snapshotted when the
+/// proposal is created, and an approval only counts while its slot still holds the same
+/// admin, so swapping out an admin also withdraws their approvals.
+#[derive(Pod, Clone, Copy, Zeroable, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct RawConfigPolicyV2ProposalData {
+    pub config_policy: Pubkey,
+    pub proposer: Pubkey,
+    pub proposal_id: u64,
+    pub bump: u64,
+    pub created_slot: u64,
+    /// First slot at which the proposal can no longer be approved or executed.
+    pub expiry_slot: u64,
+    pub patch: SetConfigPolicyV2Args,
+    pub admin_snapshot: [Pubkey; CONFIG_POLICY_V2_ADMIN_CAPACITY],
+    pub status: ConfigPolicyV2ProposalStatus,
+    /// `ConfigPolicyV2::required_admin_approvals` at creation time.
+    pub threshold: u8,
+    pub approvals: u8,
+    pub _reserved0: [u8; 5],
+}
+
+pub type ConfigPolicyV2Proposal = RawConfigPolicyV2ProposalData;
+
+#[derive(Debug)]
+pub struct ConfigPolicyV2ProposalRef<'a> {
+    header: &'a AccountHeaderV1,
+    raw: &'a RawConfigPolicyV2ProposalData,
+}
+
+#[derive(Debug)]
+pub struct ConfigPolicyV2ProposalMut<'a> {
+    header: &'a mut AccountHeaderV1,
+    raw: &'a mut RawConfigPolicyV2ProposalData,
+}
+
+impl<'a> ConfigPolicyV2ProposalRef<'a> {
+    pub fn header(&self) -> &AccountHeaderV1 {
+        self.header
+    }
+
+    pub fn layout(&self) -> ParsedAccountLayout {
+        self.header.layout().unwrap()
+    }
+
+    pub fn as_raw(&self) -> &RawConfigPolicyV2ProposalData {
+        self.raw
+    }
+}
+
+impl Deref for ConfigPolicyV2ProposalRef<'_> {
+    type Target = RawConfigPolicyV2ProposalData;
+
+    fn deref(&self) -> &Self::Target {
+        self.raw
+    }
+}
+
+impl<'a> ConfigPolicyV2ProposalMut<'a> {
+    pub fn header(&self) -> &AccountHeaderV1 {
+        self.header
+    }
+
+    pub fn layout(&self) -> ParsedAccountLayout {
+        self.header.layout().unwrap()
+    }
+
+    pub fn as_raw(&self) -> &RawConfigPolicyV2ProposalData {
+        self.raw
+    }
+
+    pub fn as_raw_mut(&mut self) -> &mut RawConfigPolicyV2ProposalData {
+        self.raw
+    }
+}
+
+impl Deref for ConfigPolicyV2ProposalMut<'_> {
+    type Target = RawConfigPolicyV2ProposalData;
+
+    fn deref(&self) -> &Self::Target {
+        self.raw
+    }
+}
+
+impl DerefMut for ConfigPolicyV2ProposalMut<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.raw
+    }
+}
+
+impl RawConfigPolicyV2ProposalData {
+    pub const PAYLOAD_LEN: usize = std::mem::size_of::<RawConfigPolicyV2ProposalData>();
+    pub const LEN: usize = AccountHeaderV1::LEN + Self::PAYLOAD_LEN;
+
+    /// Creates a proposal for `patch`, counting the proposer's approval.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        config_policy: Pubkey,
+        policy: &ConfigPolicyV2,
+        proposer: Pubkey,
+        proposal_id: u64,
+        bump: u64,
+        patch: &ConfigPolicyV2Patch,
+        current_slot: u64,
+        expiry_slot: u64,
+    ) -> Result<Self, AuctionError> {
+        let proposer_slot = policy
+            .admin_slot(&proposer)
+            .ok_or(AuctionError::UnauthorizedConfigPolicyAuthority)?;
+        if expiry_slot <= current_slot {
+            return Err(AuctionError::ConfigPolicyV2ProposalExpired);
+        }
+
+        Ok(Self {
+            config_policy,
+            proposer,
+            proposal_id,
+            bump,
+            created_slot: current_slot,
+            expiry_slot,
+            patch: patch.to_args(),
+            admin_snapshot: policy.admin_authorities,
+            status: ConfigPolicyV2ProposalStatus::PENDING,
+            threshold: policy.required_admin_approvals(),
+            approvals: 1 << proposer_slot,
+            _reserved0: [0; 5],
+        })
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<ConfigPolicyV2ProposalRef<'_>> {
+        if bytes.len() != Self::LEN {
+            return None;
+        }
+
+        let (header_bytes, raw_bytes) = bytes.split_at(AccountHeaderV1::LEN);
+        let header = bytemuck::try_from_bytes::<AccountHeaderV1>(header_bytes).ok()?;
+        if !Self::is_supported_layout(header.layout()?) {
+            return None;
+        }
+
+        let raw = bytemuck::try_from_bytes::<RawConfigPolicyV2ProposalData>(raw_bytes).ok()?;
+        Some(ConfigPolicyV2ProposalRef { header, raw })
+    }
+
+    pub fn from_bytes_mut(bytes: &mut [u8]) -> Option<ConfigPolicyV2ProposalMut<'_>> {
+        if bytes.len() != Self::LEN {
+            return None;
+        }
+
+        let (header_bytes, raw_bytes) = bytes.split_at_mut(AccountHeaderV1::LEN);
+        let header = bytemuck::try_from_bytes_mut::<AccountHeaderV1>(header_bytes).ok()?;
+        if !Self::is_supported_layout(header.layout()?) {
+            return None;
+        }
+
+        let raw = bytemuck::try_from_bytes_mut::<RawConfigPolicyV2ProposalData>(raw_bytes).ok()?;
+        Some(ConfigPolicyV2ProposalMut { header, raw })
+    }
+
+    pub fn write_bytes(&self, bytes: &mut [u8]) -> bool {
+        if bytes.len() != Self::LEN {
+            return false;
+        }
+
+        let (header_bytes, raw_bytes) = bytes.split_at_mut(AccountHeaderV1::LEN);
+        header_bytes.copy_from_slice(bytemuck::bytes_of(&AccountHeaderV1::new(
+            AccountDiscriminator::ConfigPolicyV2Proposal,
+        )));
+        raw_bytes.copy_from_slice(bytemuck::bytes_of(self));
+        true
+    }
+
+    fn is_supported_layout(layout: ParsedAccountLayout) -> bool {
+        layout
+            == ParsedAccountLayout::new(
+                AccountDiscriminator::ConfigPolicyV2Proposal,
+                AccountLayoutVersion::V1,
+            )
+    }
+
+    pub fn decoded_patch(&self) -> Result<ConfigPolicyV2Patch, AuctionError> {
+        ConfigPolicyV2Patch::try_from(&self.patch)
+    }
+
+    pub fn is_expired(&self, current_slot: u64) -> bool {
+        current_slot >= self.expiry_slot
+    }
+
+    /// Approvals whose admin slot still holds the admin that was snapshotted.
+    pub fn approval_count(&self, policy: &ConfigPolicyV2) -> u8 {
+        (0..CONFIG_POLICY_V2_ADMIN_CAPACITY)
+            .filter(|index| self.approvals & (1 << index) != 0)
+            .filter(|index| {
+                let admin = self.admin_snapshot[*index];
+                admin != Pubkey::default() && policy.admin_authorities[*index] == admin
+            })
+            .count() as u8
+    }
+
+    /// Approvals needed to execute: the snapshotted threshold, or the policy's current one if
+    /// it was raised since.
+    pub fn required_approvals(&self, policy: &ConfigPolicyV2) -> u8 {
+        self.threshold.max(policy.required_admin_approvals())
+    }
+
+    fn check_open(&self, current_slot: u64) -> Result<(), AuctionError> {
+        if self.status != ConfigPolicyV2ProposalStatus::PENDING {
+            return Err(AuctionError::ConfigPolicyV2ProposalNotPending);
+        }
+        if self.is_expired(current_slot) {
+            return Err(AuctionError::ConfigPolicyV2ProposalExpired);
+        }
+        Ok(())
+    }
+
+    pub fn approve(
+        &mut self,
+        policy: &ConfigPolicyV2,
+        signer: &Pubkey,
+        current_slot: u64,
+    ) -> Result<(), AuctionError> {
+        self.check_open(current_slot)?;
+
+        let slot = policy
+            .admin_slot(signer)
+            .filter(|slot| self.admin_snapshot[usize::from(*slot)] == *signer)
+            .ok_or(AuctionError::UnauthorizedConfigPolicyAuthority)?;
+        if self.approvals & (1 << slot) != 0 {
+            return Err(AuctionError::ConfigPolicyV2ProposalAlreadyApproved);
+        }
+
+        self.approvals |= 1 << slot;
+        Ok(())
+    }
+
+    fn check_executable(
+        &self,
+        policy: &ConfigPolicyV2,
+        current_slot: u64,
+    ) -> Result<ConfigPolicyV2Patch, AuctionError> {
+        self.check_open(current_slot)?;
+        if self.approval_count(policy) < self.required_approvals(policy) {
+            return Err(AuctionError::ConfigPolicyV2ProposalThresholdNotMet);
+        }
+        self.decoded_patch()
+    }
+
+    /// Applies the patch to `policy` once enough approvals are in. Anyone may execute.
+    ///
+    /// Patches that affect escrows in flight are rejected; execute those with
+    /// [`Self::execute_scheduled`].
+    pub fn execute(
+        &mut self,
+        policy: &mut ConfigPolicyV2,
+        current_slot: u64,
+    ) -> Result<(), AuctionError> {
+        let patch = self.check_executable(policy, current_slot)?;
+        if patch.affects_in_flight_escrows() {
+            return Err(AuctionError::ConfigPolicyV2PatchMustBeScheduled);
+        }
+
+        policy.apply_approved(&patch)?;
+        self.status = ConfigPolicyV2ProposalStatus::EXECUTED;
+        Ok(())
+    }
+
+    /// Queues the patch in `schedule` at the earliest activation slot it allows, once enough
+    /// approvals are in, and returns its schedule id. Anyone may execute.
+    pub fn execute_scheduled(
+        &mut self,
+        policy: &ConfigPolicyV2,
+        schedule: &mut ConfigPolicyV2Schedule,
+        current_slot: u64,
+    ) -> Result<u64, AuctionError> {
+        let patch = self.check_executable(policy, current_slot)?;
+        let activation_slot = current_slot.saturating_add(schedule.min_activation_delay_slots);
+        let schedule_id =
+            schedule.schedule_approved(policy, &patch, current_slot, activation_slot)?;
+        self.status = ConfigPolicyV2ProposalStatus::EXECUTED;
+        Ok(schedule_id)
+    }
+
+    /// The proposer or any current admin can cancel a pending proposal.
+    pub fn cancel(&mut self, policy: &ConfigPolicyV2, signer: &Pubkey) -> Result<(), AuctionError> {
+        if self.status != ConfigPolicyV2ProposalStatus::PENDING {
+            return Err(AuctionError::ConfigPolicyV2ProposalNotPending);
+        }
+        if *signer != self.proposer && !policy.is_admin(signer) {
+            return Err(AuctionError::UnauthorizedConfigPolicyAuthority);
+        }
+
+        self.status = ConfigPolicyV2ProposalStatus::CANCELED;
+        Ok(())
+    }
+}
@@ -0,0 +1,321 @@
+use super::{
+    AccountDiscriminator, AccountHeaderV1, AccountLayoutVersion, ConfigPolicyV2,
+    ParsedAccountLayout, Pubkey,
+};
+use crate::error::AuctionError;
+use crate::{ConfigPolicyV2Patch, SetConfigPolicyV2Args};
+use bytemuck::{Pod, Zeroable};
+use std::ops::{Deref, DerefMut};
+
+pub const MAX_SCHEDULED_CONFIG_POLICY_V2_PATCHES: usize = 8;
+
+/// Smallest `min_activation_delay_slots` a schedule accepts. A zero delay would let a patch
+/// activate in the slot it is scheduled, bypassing the rule that `apply` rejects it.
+pub const MIN_CONFIG_POLICY_V2_ACTIVATION_DELAY_SLOTS: u64 = 1;
+
+#[derive(Pod, Clone, Copy, Zeroable, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct ScheduledConfigPolicyV2Patch {
+    pub schedule_id: u64,
+    /// First slot at which the patch is part of the effective policy.
+    pub activation_slot: u64,
+    pub patch: SetConfigPolicyV2Args,
+}
+
+impl ScheduledConfigPolicyV2Patch {
+    pub fn decoded_patch(&self) -> Result<ConfigPolicyV2Patch, AuctionError> {
+        ConfigPolicyV2Patch::try_from(&self.patch)
+    }
+}
+
+/// Config policy patches waiting for their activation slot.
+///
+/// Entries are kept sorted by `(activation_slot, schedule_id)`, so the due patches are always
+/// a prefix of [`Self::pending_patches`].
+#[derive(Pod, Clone, Copy, Zeroable, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct RawConfigPolicyV2ScheduleData {
+    pub config_policy: Pubkey,
+    pub bump: u64,
+    /// Minimum distance between the scheduling slot and `activation_slot`, at least
+    /// [`MIN_CONFIG_POLICY_V2_ACTIVATION_DELAY_SLOTS`].
+    pub min_activation_delay_slots: u64,
+    pub next_schedule_id: u64,
+    pub entry_count: u8,
+    pub _reserved0: [u8; 7],
+    pub entries: [ScheduledConfigPolicyV2Patch; MAX_SCHEDULED_CONFIG_POLICY_V2_PATCHES],
+}
+
+pub type ConfigPolicyV2Schedule = RawConfigPolicyV2ScheduleData;
+
+#[derive(Debug)]
+pub struct ConfigPolicyV2ScheduleRef<'a> {
+    header: &'a AccountHeaderV1,
+    raw: &'a RawConfigPolicyV2ScheduleData,
+}
+
+#[derive(Debug)]
+pub struct ConfigPolicyV2ScheduleMut<'a> {
+    header: &'a mut AccountHeaderV1,
+    raw: &'a mut RawConfigPolicyV2ScheduleData,
+}
+
+impl<'a> ConfigPolicyV2ScheduleRef<'a> {
+    pub fn header(&self) -> &AccountHeaderV1 {
+        self.header
+    }
+
+    pub fn layout(&self) -> ParsedAccountLayout {
+        self.header.layout().unwrap()
+    }
+
+    pub fn as_raw(&self) -> &RawConfigPolicyV2ScheduleData {
+        self.raw
+    }
+}
+
+impl Deref for ConfigPolicyV2ScheduleRef<'_> {
+    type Target = RawConfigPolicyV2ScheduleData;
+
+    fn deref(&self) -> &Self::Target {
+        self.raw
+    }
+}
+
+impl<'a> ConfigPolicyV2ScheduleMut<'a> {
+    pub fn header(&self) -> &AccountHeaderV1 {
+        self.header
+    }
+
+    pub fn layout(&self) -> ParsedAccountLayout {
+        self.header.layout().unwrap()
+    }
+
+    pub fn as_raw(&self) -> &RawConfigPolicyV2ScheduleData {
+        self.raw
+    }
+
+    pub fn as_raw_mut(&mut self) -> &mut RawConfigPolicyV2ScheduleData {
+        self.raw
+    }
+}
+
+impl Deref for ConfigPolicyV2ScheduleMut<'_> {
+    type Target = RawConfigPolicyV2ScheduleData;
+
+    fn deref(&self) -> &Self::Target {
+        self.raw
+    }
+}
+
+impl DerefMut for ConfigPolicyV2ScheduleMut<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.raw
+    }
+}
+
+impl RawConfigPolicyV2ScheduleData {
+    pub const PAYLOAD_LEN: usize = std::mem::size_of::<RawConfigPolicyV2ScheduleData>();
+    pub const LEN: usize = AccountHeaderV1::LEN + Self::PAYLOAD_LEN;
+
+    pub fn new(config_policy: Pubkey, bump: u64, min_activation_delay_slots: u64) -> Self {
+        Self {
+            config_policy,
+            bump,
+            min_activation_delay_slots,
+            ..Self::zeroed()
+        }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<ConfigPolicyV2ScheduleRef<'_>> {
+        if bytes.len() != Self::LEN {
+            return None;
+        }
+
+        let (header_bytes, raw_bytes) = bytes.split_at(AccountHeaderV1::LEN);
+        let header = bytemuck::try_from_bytes::<AccountHeaderV1>(header_bytes).ok()?;
+        if !Self::is_supported_layout(header.layout()?) {
+            return None;
+        }
+
+        let raw = bytemuck::try_from_bytes::<RawConfigPolicyV2ScheduleData>(raw_bytes).ok()?;
+        if usize::from(raw.entry_count) > MAX_SCHEDULED_CONFIG_POLICY_V2_PATCHES {
+            return None;
+        }
+        Some(ConfigPolicyV2ScheduleRef { header, raw })
+    }
+
+    pub fn from_bytes_mut(bytes: &mut [u8]) -> Option<ConfigPolicyV2ScheduleMut<'_>> {
+        if bytes.len() != Self::LEN {
+            return None;
+        }
+
+        let (header_bytes, raw_bytes) = bytes.split_at_mut(AccountHeaderV1::LEN);
+        let header = bytemuck::try_from_bytes_mut::<AccountHeaderV1>(header_bytes).ok()?;
+        if !Self::is_supported_layout(header.layout()?) {
+            return None;
+        }
+
+        let raw = bytemuck::try_from_bytes_mut::<RawConfigPolicyV2ScheduleData>(raw_bytes).ok()?;
+        if usize::from(raw.entry_count) > MAX_SCHEDULED_CONFIG_POLICY_V2_PATCHES {
+            return None;
+        }
+        Some(ConfigPolicyV2ScheduleMut { header, raw })
+    }
+
+    pub fn write_bytes(&self, bytes: &mut [u8]) -> bool {
+        if bytes.len() != Self::LEN {
+            return false;
+        }
+
+        let (header_bytes, raw_bytes) = bytes.split_at_mut(AccountHeaderV1::LEN);
+        header_bytes.copy_from_slice(bytemuck::bytes_of(&AccountHeaderV1::new(
+            AccountDiscriminator::ConfigPolicyV2Schedule,
+        )));
+        raw_bytes.copy_from_slice(bytemuck::bytes_of(self));
+        true
+    }
+
+    fn is_supported_layout(layout: ParsedAccountLayout) -> bool {
+        layout
+            == ParsedAccountLayout::new(
+                AccountDiscriminator::ConfigPolicyV2Schedule,
+                AccountLayoutVersion::V1,
+            )
+    }
+
+    pub fn pending_patches(&self) -> &[ScheduledConfigPolicyV2Patch] {
+        &self.entries[..usize::from(self.entry_count)]
+    }
+
+    fn due_count(&self, slot: u64) -> usize {
+        self.pending_patches()
+            .iter()
+            .take_while(|entry| entry.activation_slot <= slot)
+            .count()
+    }
+
+    /// Queues `patch` for `activation_slot` and returns its schedule id.
+    ///
+    /// `signer` needs the same rights as for a direct `SetConfigPolicyV2` update, so a policy
+    /// requiring more than one admin approval schedules through
+    /// [`ConfigPolicyV2Proposal::execute_scheduled`](crate::ConfigPolicyV2Proposal::execute_scheduled)
+    /// instead. The patch is validated against the policy as it will look at `activation_slot`.
+    pub fn schedule(
+        &mut self,
+        policy: &ConfigPolicyV2,
+        signer: &Pubkey,
+        patch: &ConfigPolicyV2Patch,
+        current_slot: u64,
+        activation_slot: u64,
+    ) -> Result<u64, AuctionError> {
+        policy.check_direct_update(signer)?;
+        self.schedule_approved(policy, patch, current_slot, activation_slot)
+    }
+
+    /// [`Self::schedule`] without the signer check, for patches whose approvals were already
+    /// counted.
+    pub(crate) fn schedule_approved(
+        &mut self,
+        policy: &ConfigPolicyV2,
+        patch: &ConfigPolicyV2Patch,
+        current_slot: u64,
+        activation_slot: u64,
+    ) -> Result<u64, AuctionError> {
+        if self.min_activation_delay_slots < MIN_CONFIG_POLICY_V2_ACTIVATION_DELAY_SLOTS {
+            return Err(AuctionError::InvalidActivationDelay);
+        }
+        if activation_slot < current_slot.saturating_add(self.min_activation_delay_slots) {
+            return Err(AuctionError::ActivationSlotTooEarly);
+        }
+        let entry_count = usize::from(self.entry_count);
+        if entry_count >= MAX_SCHEDULED_CONFIG_POLICY_V2_PATCHES {
+            return Err(AuctionError::ConfigPolicyV2ScheduleFull);
+        }
+
+        let mut preview = policy.effective_policy_at(self, activation_slot)?;
+        preview.apply_approved(patch)?;
+
+        let schedule_id = self.next_schedule_id;
+        let position = self.due_count(activation_slot);
+        self.entries
+            .copy_within(position..entry_count, position + 1);
+        self.entries[position] = ScheduledConfigPolicyV2Patch {
+            schedule_id,
+            activation_slot,
+            patch: patch.to_args(),
+        };
+        self.entry_count += 1;
+        self.next_schedule_id += 1;
+        Ok(schedule_id)
+    }
+
+    /// Drops a pending patch. Any single admin may cancel, whatever the approval threshold,
+    /// the same way any admin can cancel a pending proposal.
+    pub fn cancel(
+        &mut self,
+        policy: &ConfigPolicyV2,
+        signer: &Pubkey,
+        schedule_id: u64,
+    ) -> Result<ScheduledConfigPolicyV2Patch, AuctionError> {
+        if !policy.is_admin(signer) {
+            return Err(AuctionError::UnauthorizedConfigPolicyAuthority);
+        }
+
+        let entry_count = usize::from(self.entry_count);
+        let index = self
+            .pending_patches()
+            .iter()
+            .position(|entry| entry.schedule_id == schedule_id)
+            .ok_or(AuctionError::ScheduledConfigPolicyV2PatchNotFound)?;
+        let removed = self.entries[index];
+
+        self.entries.copy_within(index + 1..entry_count, index);
+        self.entries[entry_count - 1] = ScheduledConfigPolicyV2Patch::zeroed();
+        self.entry_count -= 1;
+        Ok(removed)
+    }
+
+    /// Writes every patch due at `current_slot` into `policy` and drops it from the schedule.
+    /// Returns how many patches were activated.
+    ///
+    /// If a due patch no longer applies, the patches before it stay activated and the failing
+    /// one is left at the head of the schedule for an admin to cancel.
+    pub fn activate_due(
+        &mut self,
+        policy: &mut ConfigPolicyV2,
+        current_slot: u64,
+    ) -> Result<usize, AuctionError> {
+        let entry_count = usize::from(self.entry_count);
+        let mut activated = 0;
+        let result = self.entries[..self.due_count(current_slot)]
+            .iter()
+            .try_for_each(|entry| {
+                policy.apply_approved(&entry.decoded_patch()?)?;
+                activated += 1;
+                Ok(())
+            });
+
+        self.entries.copy_within(activated..entry_count, 0);
+        self.entries[entry_count - activated..entry_count]
+            .fill(ScheduledConfigPolicyV2Patch::zeroed());
+        self.entry_count -= activated as u8;
+        result.map(|()| activated)
+    }
+}
+
+impl ConfigPolicyV2 {
+    /// The policy as it will read at `slot` once every patch in `schedule` due by then is
+    /// activated. Slots before the next activation return the stored policy unchanged.
+    pub fn effective_policy_at(
+        &self,
+        schedule: &ConfigPolicyV2Schedule,
+        slot: u64,
+    ) -> Result<ConfigPolicyV2, AuctionError> {
+        let mut policy = *self;
+        for entry in &schedule.pending_patches()[..schedule.due_count(slot)] {
+            policy.apply_approved(&entry.decoded_patch()?)?;
+        }
+        Ok(policy)
+    }
+}
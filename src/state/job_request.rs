@@ -1,6 +1,7 @@
 use super::Pubkey;
+use crate::cid::{Cid, CidCodec};
 use crate::state::request_tier::RequestTier;
-use crate::state::verification::VerificationState;
+use crate::state::verification::{is_encrypted, VerificationState};
 use crate::{constant::PUBKEY_BYTES, MaybePubkey};
 use bytemuck::{Pod, Zeroable};
 #[cfg(feature = "serde")]
@@ -40,12 +41,32 @@ pub struct JobRequest {
     /// pending verification, or already completed.
     pub status: JobRequestStatus,
     pub verification: VerificationState,
+    /// Data account holding the input, or the head of its linked data accounts
     pub input_data_account: MaybePubkey,
     pub output_data_account: MaybePubkey,
 }
 
 impl JobRequest {
     pub const LEN: usize = std::mem::size_of::<JobRequest>();
+
+    pub fn is_input_hash_encrypted(&self) -> bool {
+        is_encrypted(&self.input_hash_iv)
+    }
+
+    /// The raw-codec CIDv1 of the input, `None` while `input_hash` is encrypted.
+    pub fn input_cid(&self) -> Option<Cid> {
+        self.input_cid_with_codec(CidCodec::Raw)
+    }
+
+    /// The CID of the input if it was stored under `codec`, which the account does not
+    /// record. A dag-pb input is shown as its CIDv0. `None` while `input_hash` is encrypted.
+    pub fn input_cid_with_codec(&self, codec: CidCodec) -> Option<Cid> {
+        let digest = self.input_hash.inner();
+        (!self.is_input_hash_encrypted()).then(|| match codec {
+            CidCodec::Raw => Cid::v1(codec, digest),
+            CidCodec::DagPb => Cid::v0(digest),
+        })
+    }
 }
 
 #[derive(Clone, Copy, Zeroable, Debug, PartialEq, Default)]
@@ -22,6 +22,13 @@ pub enum AccountDiscriminator {
     Metadata = 7,
     BundleEscrowV2 = 8,
     BundleVerifierPageV2 = 9,
+    TierRegistryV2 = 10,
+    ConfigPolicyV2Proposal = 11,
+    ConfigPolicyV2Schedule = 12,
+    BundleRefundPageV2 = 13,
+    NodePenaltyRecord = 14,
+    NodeReputationV2 = 15,
+    NodeRegistration = 16,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, TryFromPrimitive, IntoPrimitive, Zeroable)]
@@ -30,6 +37,10 @@ pub enum AccountLayoutVersion {
     LegacyV0 = 0,
     V1 = 1,
     V2 = 2,
+    V3 = 3,
+    V4 = 4,
+    V5 = 5,
+    V6 = 6,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -1,4 +1,7 @@
-use crate::{MaybePubkey, Pubkey, PUBKEY_BYTES};
+use crate::{
+    AccountDiscriminator, AccountHeaderV1, AccountLayoutVersion, MaybePubkey, ParsedAccountLayout,
+    Pubkey, PUBKEY_BYTES,
+};
 use bytemuck::{Pod, Zeroable};
 use std::num::NonZeroU64;
 
@@ -21,3 +24,92 @@ pub struct Metadata {
 impl Metadata {
     pub const LEN: usize = std::mem::size_of::<Metadata>();
 }
+
+/// Metadata of a V1 data account: an [`AccountHeaderV1`], the [`Metadata`] fields and the
+/// link to the next account of a payload too large for one account. The job request's
+/// `input_data_account` points at the head and each account points at the next one.
+#[derive(Clone, Copy, Zeroable, Debug, PartialEq, Pod)]
+#[repr(C)]
+pub struct MetadataV1 {
+    pub header: AccountHeaderV1,
+    /// authority to update this data account
+    pub authority: Pubkey,
+    /// None if no compression is used
+    pub decompressed_len: Option<NonZeroU64>,
+    pub job_request_key: MaybePubkey,
+    /// Seed used to create the data_account.
+    /// Must match seed used in `CreateAccountWithSeed` system program instruction.
+    pub seed: [u8; PUBKEY_BYTES],
+    pub seed_len: u64,
+    /// length of the text stored in the account
+    pub payload_len: u64,
+    /// The account holding the rest of the payload, `None` in the last account
+    pub next_data_account: MaybePubkey,
+}
+
+impl MetadataV1 {
+    pub const LEN: usize = std::mem::size_of::<MetadataV1>();
+
+    pub fn new(metadata: &Metadata, next_data_account: Option<Pubkey>) -> Self {
+        Self {
+            header: AccountHeaderV1::new(AccountDiscriminator::Metadata),
+            authority: metadata.authority,
+            decompressed_len: metadata.decompressed_len,
+            job_request_key: metadata.job_request_key,
+            seed: metadata.seed,
+            seed_len: metadata.seed_len,
+            payload_len: metadata.payload_len,
+            next_data_account: next_data_account.into(),
+        }
+    }
+
+    /// The fields shared with a legacy account.
+    pub fn metadata(&self) -> Metadata {
+        Metadata {
+            authority: self.authority,
+            decompressed_len: self.decompressed_len,
+            job_request_key: self.job_request_key,
+            seed: self.seed,
+            seed_len: self.seed_len,
+            payload_len: self.payload_len,
+        }
+    }
+}
+
+/// Offset of the payload in a data account with the given layout, `None` for versions data
+/// accounts do not use.
+pub const fn metadata_payload_offset(version: AccountLayoutVersion) -> Option<usize> {
+    match version {
+        AccountLayoutVersion::LegacyV0 => Some(Metadata::LEN),
+        AccountLayoutVersion::V1 => Some(MetadataV1::LEN),
+        AccountLayoutVersion::V2
+        | AccountLayoutVersion::V3
+        | AccountLayoutVersion::V4
+        | AccountLayoutVersion::V5
+        | AccountLayoutVersion::V6 => None,
+    }
+}
+
+/// A V1 data account starts with a metadata [`AccountHeaderV1`]; anything else is a legacy
+/// account, whose first bytes are its authority. `None` for a metadata header of another
+/// version or an account too short for its metadata.
+///
+/// A legacy account is only misread if its authority starts with the exact eight header
+/// bytes.
+pub fn parse_metadata_layout(bytes: &[u8]) -> Option<ParsedAccountLayout> {
+    let header =
+        bytemuck::try_pod_read_unaligned::<AccountHeaderV1>(bytes.get(..AccountHeaderV1::LEN)?)
+            .ok()?;
+    let layout = if header.discriminator == AccountDiscriminator::Metadata as u8
+        && header.reserved == [0; 6]
+        && header.version != AccountLayoutVersion::LegacyV0 as u8
+    {
+        let layout = header.layout()?;
+        (layout.version == AccountLayoutVersion::V1).then_some(layout)?
+    } else {
+        ParsedAccountLayout::legacy_v0(AccountDiscriminator::Metadata)
+    };
+
+    let metadata_len = metadata_payload_offset(layout.version)?;
+    (bytes.len() >= metadata_len).then_some(layout)
+}
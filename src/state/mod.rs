@@ -2,28 +2,46 @@ pub mod auction;
 pub mod bid;
 pub mod bundle;
 pub mod bundle_escrow_v2;
+pub mod bundle_refund_page_v2;
 pub mod bundle_registry;
 pub mod bundle_verifier_page_v2;
 pub mod config;
 pub mod config_policy_v2;
+#[cfg(feature = "policy-document")]
+pub mod config_policy_v2_document;
+pub mod config_policy_v2_proposal;
+pub mod config_policy_v2_schedule;
 pub mod layout;
 pub use auction::*;
 pub use bid::*;
 pub mod job_request;
 pub mod metadata;
+pub mod node_penalty_record;
+pub mod node_registration;
+pub mod node_reputation_v2;
 pub mod request_tier;
+pub mod tier_registry_v2;
 mod verification;
 
 pub use bundle::*;
 pub use bundle_escrow_v2::*;
+pub use bundle_refund_page_v2::*;
 pub use bundle_registry::*;
 pub use bundle_verifier_page_v2::*;
 pub use config::*;
 pub use config_policy_v2::*;
+#[cfg(feature = "policy-document")]
+pub use config_policy_v2_document::*;
+pub use config_policy_v2_proposal::*;
+pub use config_policy_v2_schedule::*;
 pub use job_request::*;
 pub use layout::*;
 pub use metadata::*;
+pub use node_penalty_record::*;
+pub use node_registration::*;
+pub use node_reputation_v2::*;
 pub use request_tier::*;
+pub use tier_registry_v2::*;
 pub use verification::*;
 
 #[cfg(feature = "serde")]
@@ -0,0 +1,232 @@
+use super::{
+    AccountDiscriminator, AccountHeaderV1, AccountLayoutVersion, ParsedAccountLayout, Pubkey,
+};
+use crate::error::AuctionError;
+use bytemuck::{Pod, Zeroable};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::ops::{Deref, DerefMut};
+
+/// Why a node is penalised.
+#[derive(Pod, Clone, Copy, Zeroable, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[repr(transparent)]
+pub struct NodePenaltyKind(pub u8);
+
+impl NodePenaltyKind {
+    /// The winner's result was rejected at finalize.
+    pub const REJECTED_RESULT: Self = Self(0);
+    /// The winner let the result deadline pass, before a runner-up took over or the escrow
+    /// expired.
+    pub const MISSED_RESULT: Self = Self(1);
+    /// A selected verifier did not count towards the quorum.
+    pub const MISSED_VERIFICATION: Self = Self(2);
+}
+
+/// Penalties assessed against one node, at the PDA of `NODE_PENALTY_RECORD_SEED` and the node
+/// pubkey, followed by `mint` for penalties from token escrows.
+///
+/// Amounts are in lamports, or in base units of `mint`. `ClaimWinnerLstakeV2` and
+/// `ClaimVerifierLstakeV2` passed the record as a trailing penalty account withhold the
+/// outstanding penalty from the node's payout in the same denomination and pay it to the
+/// protocol fee recipient.
+#[derive(Pod, Clone, Copy, Zeroable, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[repr(C)]
+pub struct RawNodePenaltyRecordData {
+    pub node_pubkey: Pubkey,
+    pub rejected_result_count: u32,
+    pub missed_result_count: u32,
+    pub missed_verification_count: u32,
+    pub _reserved0: [u8; 4],
+    pub assessed_penalty_lamports: u64,
+    /// The part of `assessed_penalty_lamports` already withheld from payouts.
+    pub slashed_lamports: u64,
+    pub last_penalized_slot: u64,
+    pub last_bundle_escrow: Pubkey,
+    /// Mint of the token escrows this record penalises, default for lamport escrows.
+    pub mint: Pubkey,
+}
+
+pub type NodePenaltyRecord = RawNodePenaltyRecordData;
+
+#[derive(Debug)]
+pub struct NodePenaltyRecordRef<'a> {
+    header: &'a AccountHeaderV1,
+    raw: &'a RawNodePenaltyRecordData,
+}
+
+#[derive(Debug)]
+pub struct NodePenaltyRecordMut<'a> {
+    header: &'a mut AccountHeaderV1,
+    raw: &'a mut RawNodePenaltyRecordData,
+}
+
+impl<'a> NodePenaltyRecordRef<'a> {
+    pub fn header(&self) -> &AccountHeaderV1 {
+        self.header
+    }
+
+    pub fn layout(&self) -> ParsedAccountLayout {
+        self.header.layout().unwrap()
+    }
+
+    pub fn as_raw(&self) -> &RawNodePenaltyRecordData {
+        self.raw
+    }
+}
+
+impl Deref for NodePenaltyRecordRef<'_> {
+    type Target = RawNodePenaltyRecordData;
+
+    fn deref(&self) -> &Self::Target {
+        self.raw
+    }
+}
+
+impl<'a> NodePenaltyRecordMut<'a> {
+    pub fn header(&self) -> &AccountHeaderV1 {
+        self.header
+    }
+
+    pub fn layout(&self) -> ParsedAccountLayout {
+        self.header.layout().unwrap()
+    }
+
+    pub fn as_raw(&self) -> &RawNodePenaltyRecordData {
+        self.raw
+    }
+
+    pub fn as_raw_mut(&mut self) -> &mut RawNodePenaltyRecordData {
+        self.raw
+    }
+}
+
+impl Deref for NodePenaltyRecordMut<'_> {
+    type Target = RawNodePenaltyRecordData;
+
+    fn deref(&self) -> &Self::Target {
+        self.raw
+    }
+}
+
+impl DerefMut for NodePenaltyRecordMut<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.raw
+    }
+}
+
+impl RawNodePenaltyRecordData {
+    pub const PAYLOAD_LEN: usize = std::mem::size_of::<RawNodePenaltyRecordData>();
+    pub const LEN: usize = AccountHeaderV1::LEN + Self::PAYLOAD_LEN;
+
+    pub fn new(node_pubkey: Pubkey, mint: Pubkey) -> Self {
+        Self {
+            node_pubkey,
+            mint,
+            ..Self::zeroed()
+        }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<NodePenaltyRecordRef<'_>> {
+        if bytes.len() != Self::LEN {
+            return None;
+        }
+
+        let (header_bytes, raw_bytes) = bytes.split_at(AccountHeaderV1::LEN);
+        let header = bytemuck::try_from_bytes::<AccountHeaderV1>(header_bytes).ok()?;
+        if !Self::is_supported_layout(header.layout()?) {
+            return None;
+        }
+
+        let raw = bytemuck::try_from_bytes::<RawNodePenaltyRecordData>(raw_bytes).ok()?;
+        Some(NodePenaltyRecordRef { header, raw })
+    }
+
+    pub fn from_bytes_mut(bytes: &mut [u8]) -> Option<NodePenaltyRecordMut<'_>> {
+        if bytes.len() != Self::LEN {
+            return None;
+        }
+
+        let (header_bytes, raw_bytes) = bytes.split_at_mut(AccountHeaderV1::LEN);
+        let header = bytemuck::try_from_bytes_mut::<AccountHeaderV1>(header_bytes).ok()?;
+        if !Self::is_supported_layout(header.layout()?) {
+            return None;
+        }
+
+        let raw = bytemuck::try_from_bytes_mut::<RawNodePenaltyRecordData>(raw_bytes).ok()?;
+        Some(NodePenaltyRecordMut { header, raw })
+    }
+
+    pub fn read(bytes: &[u8]) -> Option<Self> {
+        Self::from_bytes(bytes).map(|account| *account.as_raw())
+    }
+
+    pub fn write_bytes(&self, bytes: &mut [u8]) -> bool {
+        if bytes.len() != Self::LEN {
+            return false;
+        }
+
+        let (header_bytes, raw_bytes) = bytes.split_at_mut(AccountHeaderV1::LEN);
+        header_bytes.copy_from_slice(bytemuck::bytes_of(&AccountHeaderV1::new(
+            AccountDiscriminator::NodePenaltyRecord,
+        )));
+        raw_bytes.copy_from_slice(bytemuck::bytes_of(self));
+        true
+    }
+
+    fn is_supported_layout(layout: ParsedAccountLayout) -> bool {
+        layout
+            == ParsedAccountLayout::new(
+                AccountDiscriminator::NodePenaltyRecord,
+                AccountLayoutVersion::V1,
+            )
+    }
+
+    /// Checks that the record belongs to `node_pubkey` and counts amounts of `mint`, the
+    /// [`BundleEscrowV2Denomination::mint`](crate::BundleEscrowV2Denomination::mint) of the
+    /// escrow being penalised or claimed from.
+    pub fn check_node(&self, node_pubkey: &Pubkey, mint: &Pubkey) -> Result<(), AuctionError> {
+        if self.node_pubkey != *node_pubkey || self.mint != *mint {
+            return Err(AuctionError::InvalidNodePenaltyRecord);
+        }
+        Ok(())
+    }
+
+    pub fn outstanding_penalty_lamports(&self) -> u64 {
+        self.assessed_penalty_lamports
+            .saturating_sub(self.slashed_lamports)
+    }
+
+    /// Counts a penalty of `kind` from `bundle_escrow` against the node. The escrow keeps
+    /// track of which of its nodes were already penalised.
+    pub fn apply_penalty(
+        &mut self,
+        kind: NodePenaltyKind,
+        bundle_escrow: Pubkey,
+        penalty_lamports: u64,
+        current_slot: u64,
+    ) -> Result<(), AuctionError> {
+        let count = match kind {
+            NodePenaltyKind::REJECTED_RESULT => &mut self.rejected_result_count,
+            NodePenaltyKind::MISSED_RESULT => &mut self.missed_result_count,
+            NodePenaltyKind::MISSED_VERIFICATION => &mut self.missed_verification_count,
+            _ => return Err(AuctionError::NodeNotPenalizable),
+        };
+        *count = count.saturating_add(1);
+        self.assessed_penalty_lamports = self
+            .assessed_penalty_lamports
+            .saturating_add(penalty_lamports);
+        self.last_penalized_slot = current_slot;
+        self.last_bundle_escrow = bundle_escrow;
+        Ok(())
+    }
+
+    /// Withholds the outstanding penalty from a payout of `payout_lamports` and returns the
+    /// withheld amount.
+    pub fn withhold(&mut self, payout_lamports: u64) -> u64 {
+        let withheld = self.outstanding_penalty_lamports().min(payout_lamports);
+        self.slashed_lamports += withheld;
+        withheld
+    }
+}
@@ -0,0 +1,280 @@
+use super::{
+    AccountDiscriminator, AccountHeaderV1, AccountLayoutVersion, ParsedAccountLayout, Pubkey,
+    RequestTier,
+};
+use crate::error::AuctionError;
+use crate::instruction::IpAddr;
+use bytemuck::{Pod, Zeroable};
+use std::net;
+use std::ops::{Deref, DerefMut};
+
+/// Longest hostname a [`NodeRegistrationInfo`] can hold.
+pub const MAX_NODE_HOSTNAME_LEN: usize = 64;
+
+/// How a node is reached and what it serves, as set by `RegisterNode` and
+/// `UpdateNodeRegistration`.
+#[derive(Pod, Clone, Copy, Zeroable, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct NodeRegistrationInfo {
+    /// Key requesters encrypt commitments to, all zeroes if the node takes no encrypted jobs
+    pub encryption_public_key: [u8; 32],
+    pub ip: IpAddr,
+    pub port: u16,
+    /// Length of `hostname`, 0 if the node is only reachable by `ip`
+    pub hostname_len: u8,
+    /// Bit `tier as u64` is set for every [`RequestTier`] the node serves
+    pub supported_tiers_bitmap: u8,
+    pub hostname: [u8; MAX_NODE_HOSTNAME_LEN],
+}
+
+impl NodeRegistrationInfo {
+    pub fn new(
+        ip: net::IpAddr,
+        port: u16,
+        hostname: Option<&str>,
+        encryption_public_key: Option<[u8; 32]>,
+        supported_tiers: &[RequestTier],
+    ) -> Result<Self, AuctionError> {
+        let hostname = hostname.unwrap_or_default().as_bytes();
+        let mut info = Self {
+            encryption_public_key: encryption_public_key.unwrap_or_default(),
+            ip: ip.into(),
+            port,
+            hostname_len: u8::try_from(hostname.len())
+                .map_err(|_| AuctionError::InvalidNodeRegistration)?,
+            supported_tiers_bitmap: supported_tiers
+                .iter()
+                .fold(0, |bitmap, tier| bitmap | Self::tier_bit(*tier)),
+            hostname: [0; MAX_NODE_HOSTNAME_LEN],
+        };
+        info.hostname
+            .get_mut(..hostname.len())
+            .ok_or(AuctionError::InvalidNodeRegistration)?
+            .copy_from_slice(hostname);
+        info.validate()?;
+        Ok(info)
+    }
+
+    /// Checks instruction input: a non-zero port, at least one known tier, and a hostname
+    /// of ASCII letters, digits, `-` and `.` with nothing stored past it.
+    pub fn validate(&self) -> Result<(), AuctionError> {
+        let all_tiers = RequestTier::ALL
+            .into_iter()
+            .fold(0, |bitmap, tier| bitmap | Self::tier_bit(tier));
+        let hostname_len = usize::from(self.hostname_len);
+        let valid = self.port != 0
+            && self.supported_tiers_bitmap != 0
+            && self.supported_tiers_bitmap & !all_tiers == 0
+            && hostname_len <= MAX_NODE_HOSTNAME_LEN
+            && self.hostname[..hostname_len]
+                .iter()
+                .all(|byte| byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.'))
+            && self.hostname[hostname_len..].iter().all(|byte| *byte == 0);
+        if !valid {
+            return Err(AuctionError::InvalidNodeRegistration);
+        }
+        Ok(())
+    }
+
+    pub fn hostname(&self) -> Option<&str> {
+        let hostname = self.hostname.get(..usize::from(self.hostname_len))?;
+        std::str::from_utf8(hostname)
+            .ok()
+            .filter(|hostname| !hostname.is_empty())
+    }
+
+    pub fn socket_addr(&self) -> net::SocketAddr {
+        net::SocketAddr::new(self.ip.into(), self.port)
+    }
+
+    pub fn encryption_public_key(&self) -> Option<[u8; 32]> {
+        (self.encryption_public_key != [0; 32]).then_some(self.encryption_public_key)
+    }
+
+    pub fn supports_tier(&self, tier: RequestTier) -> bool {
+        self.supported_tiers_bitmap & Self::tier_bit(tier) != 0
+    }
+
+    const fn tier_bit(tier: RequestTier) -> u8 {
+        1 << tier as u64
+    }
+}
+
+/// A node's endpoint, at the PDA of `NODE_REGISTRATION_SEED` and the node authority.
+///
+/// Bids placed with the registration leave their own endpoint fields zeroed, so rotating the
+/// endpoint takes one `UpdateNodeRegistration` instead of an update per outstanding bid.
+#[derive(Pod, Clone, Copy, Zeroable, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct RawNodeRegistrationData {
+    pub node_authority: Pubkey,
+    pub info: NodeRegistrationInfo,
+    pub registered_slot: u64,
+    /// Slot of the last `RegisterNode` or `UpdateNodeRegistration`.
+    pub heartbeat_slot: u64,
+    pub _reserved0: [u8; 32],
+}
+
+pub type NodeRegistration = RawNodeRegistrationData;
+
+#[derive(Debug)]
+pub struct NodeRegistrationRef<'a> {
+    header: &'a AccountHeaderV1,
+    raw: &'a RawNodeRegistrationData,
+}
+
+#[derive(Debug)]
+pub struct NodeRegistrationMut<'a> {
+    header: &'a mut AccountHeaderV1,
+    raw: &'a mut RawNodeRegistrationData,
+}
+
+impl<'a> NodeRegistrationRef<'a> {
+    pub fn header(&self) -> &AccountHeaderV1 {
+        self.header
+    }
+
+    pub fn layout(&self) -> ParsedAccountLayout {
+        self.header.layout().unwrap()
+    }
+
+    pub fn as_raw(&self) -> &RawNodeRegistrationData {
+        self.raw
+    }
+}
+
+impl Deref for NodeRegistrationRef<'_> {
+    type Target = RawNodeRegistrationData;
+
+    fn deref(&self) -> &Self::Target {
+        self.raw
+    }
+}
+
+impl<'a> NodeRegistrationMut<'a> {
+    pub fn header(&self) -> &AccountHeaderV1 {
+        self.header
+    }
+
+    pub fn layout(&self) -> ParsedAccountLayout {
+        self.header.layout().unwrap()
+    }
+
+    pub fn as_raw(&self) -> &RawNodeRegistrationData {
+        self.raw
+    }
+
+    pub fn as_raw_mut(&mut self) -> &mut RawNodeRegistrationData {
+        self.raw
+    }
+}
+
+impl Deref for NodeRegistrationMut<'_> {
+    type Target = RawNodeRegistrationData;
+
+    fn deref(&self) -> &Self::Target {
+        self.raw
+    }
+}
+
+impl DerefMut for NodeRegistrationMut<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.raw
+    }
+}
+
+impl RawNodeRegistrationData {
+    pub const PAYLOAD_LEN: usize = std::mem::size_of::<RawNodeRegistrationData>();
+    pub const LEN: usize = AccountHeaderV1::LEN + Self::PAYLOAD_LEN;
+
+    pub fn new(
+        node_authority: Pubkey,
+        info: NodeRegistrationInfo,
+        current_slot: u64,
+    ) -> Result<Self, AuctionError> {
+        info.validate()?;
+        Ok(Self {
+            node_authority,
+            info,
+            registered_slot: current_slot,
+            heartbeat_slot: current_slot,
+            _reserved0: [0; 32],
+        })
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<NodeRegistrationRef<'_>> {
+        if bytes.len() != Self::LEN {
+            return None;
+        }
+
+        let (header_bytes, raw_bytes) = bytes.split_at(AccountHeaderV1::LEN);
+        let header = bytemuck::try_from_bytes::<AccountHeaderV1>(header_bytes).ok()?;
+        if !Self::is_supported_layout(header.layout()?) {
+            return None;
+        }
+
+        let raw = bytemuck::try_from_bytes::<RawNodeRegistrationData>(raw_bytes).ok()?;
+        Some(NodeRegistrationRef { header, raw })
+    }
+
+    pub fn from_bytes_mut(bytes: &mut [u8]) -> Option<NodeRegistrationMut<'_>> {
+        if bytes.len() != Self::LEN {
+            return None;
+        }
+
+        let (header_bytes, raw_bytes) = bytes.split_at_mut(AccountHeaderV1::LEN);
+        let header = bytemuck::try_from_bytes_mut::<AccountHeaderV1>(header_bytes).ok()?;
+        if !Self::is_supported_layout(header.layout()?) {
+            return None;
+        }
+
+        let raw = bytemuck::try_from_bytes_mut::<RawNodeRegistrationData>(raw_bytes).ok()?;
+        Some(NodeRegistrationMut { header, raw })
+    }
+
+    pub fn read(bytes: &[u8]) -> Option<Self> {
+        Self::from_bytes(bytes).map(|account| *account.as_raw())
+    }
+
+    pub fn write_bytes(&self, bytes: &mut [u8]) -> bool {
+        if bytes.len() != Self::LEN {
+            return false;
+        }
+
+        let (header_bytes, raw_bytes) = bytes.split_at_mut(AccountHeaderV1::LEN);
+        header_bytes.copy_from_slice(bytemuck::bytes_of(&AccountHeaderV1::new(
+            AccountDiscriminator::NodeRegistration,
+        )));
+        raw_bytes.copy_from_slice(bytemuck::bytes_of(self));
+        true
+    }
+
+    fn is_supported_layout(layout: ParsedAccountLayout) -> bool {
+        layout
+            == ParsedAccountLayout::new(
+                AccountDiscriminator::NodeRegistration,
+                AccountLayoutVersion::V1,
+            )
+    }
+
+    /// Replaces the registration info and records a heartbeat at `current_slot`, which must
+    /// not be older than the last one.
+    pub fn update(
+        &mut self,
+        info: NodeRegistrationInfo,
+        current_slot: u64,
+    ) -> Result<(), AuctionError> {
+        info.validate()?;
+        if current_slot < self.heartbeat_slot {
+            return Err(AuctionError::InvalidNodeRegistration);
+        }
+        self.info = info;
+        self.heartbeat_slot = current_slot;
+        Ok(())
+    }
+
+    /// Whether the node sent a heartbeat within the last `max_heartbeat_age_slots` slots.
+    pub fn is_live(&self, current_slot: u64, max_heartbeat_age_slots: u64) -> bool {
+        current_slot.saturating_sub(self.heartbeat_slot) <= max_heartbeat_age_slots
+    }
+}
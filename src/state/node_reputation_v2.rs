@@ -0,0 +1,246 @@
+use super::{
+    AccountDiscriminator, AccountHeaderV1, AccountLayoutVersion, BundleEscrowV2Status,
+    BundleEscrowV2VerifierSet, ParsedAccountLayout, Pubkey, RawBundleEscrowV2Data,
+};
+use crate::error::AuctionError;
+use bytemuck::{Pod, Zeroable};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::ops::{Deref, DerefMut};
+
+/// Basis points of a perfect reliability score.
+pub const NODE_REPUTATION_V2_MAX_SCORE_BPS: u16 = 10_000;
+
+/// One node's settled history, at the PDA of `NODE_REPUTATION_V2_SEED` and the node pubkey.
+///
+/// `FinalizeBundleVerificationV2` records the winner and the selected verifiers,
+/// `ExpireBundleEscrowV2` records a winner that never got finalized.
+#[derive(Pod, Clone, Copy, Zeroable, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[repr(C)]
+pub struct RawNodeReputationV2Data {
+    pub node_pubkey: Pubkey,
+    /// Escrows the node won and that were since finalized or expired.
+    pub wins: u32,
+    pub verified_results: u32,
+    pub rejected_results: u32,
+    pub expiries: u32,
+    /// Finalized escrows that selected the node as a verifier.
+    pub verifications_assigned: u32,
+    /// The part of `verifications_assigned` where the node counted towards the quorum.
+    pub verifications_participated: u32,
+    pub accepted_output_tokens: u64,
+    pub last_updated_slot: u64,
+    pub _reserved0: [u8; 32],
+}
+
+pub type NodeReputationV2 = RawNodeReputationV2Data;
+
+#[derive(Debug)]
+pub struct NodeReputationV2Ref<'a> {
+    header: &'a AccountHeaderV1,
+    raw: &'a RawNodeReputationV2Data,
+}
+
+#[derive(Debug)]
+pub struct NodeReputationV2Mut<'a> {
+    header: &'a mut AccountHeaderV1,
+    raw: &'a mut RawNodeReputationV2Data,
+}
+
+impl<'a> NodeReputationV2Ref<'a> {
+    pub fn header(&self) -> &AccountHeaderV1 {
+        self.header
+    }
+
+    pub fn layout(&self) -> ParsedAccountLayout {
+        self.header.layout().unwrap()
+    }
+
+    pub fn as_raw(&self) -> &RawNodeReputationV2Data {
+        self.raw
+    }
+}
+
+impl Deref for NodeReputationV2Ref<'_> {
+    type Target = RawNodeReputationV2Data;
+
+    fn deref(&self) -> &Self::Target {
+        self.raw
+    }
+}
+
+impl<'a> NodeReputationV2Mut<'a> {
+    pub fn header(&self) -> &AccountHeaderV1 {
+        self.header
+    }
+
+    pub fn layout(&self) -> ParsedAccountLayout {
+        self.header.layout().unwrap()
+    }
+
+    pub fn as_raw(&self) -> &RawNodeReputationV2Data {
+        self.raw
+    }
+
+    pub fn as_raw_mut(&mut self) -> &mut RawNodeReputationV2Data {
+        self.raw
+    }
+}
+
+impl Deref for NodeReputationV2Mut<'_> {
+    type Target = RawNodeReputationV2Data;
+
+    fn deref(&self) -> &Self::Target {
+        self.raw
+    }
+}
+
+impl DerefMut for NodeReputationV2Mut<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.raw
+    }
+}
+
+impl RawNodeReputationV2Data {
+    pub const PAYLOAD_LEN: usize = std::mem::size_of::<RawNodeReputationV2Data>();
+    pub const LEN: usize = AccountHeaderV1::LEN + Self::PAYLOAD_LEN;
+
+    pub fn new(node_pubkey: Pubkey) -> Self {
+        Self {
+            node_pubkey,
+            ..Self::zeroed()
+        }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<NodeReputationV2Ref<'_>> {
+        if bytes.len() != Self::LEN {
+            return None;
+        }
+
+        let (header_bytes, raw_bytes) = bytes.split_at(AccountHeaderV1::LEN);
+        let header = bytemuck::try_from_bytes::<AccountHeaderV1>(header_bytes).ok()?;
+        if !Self::is_supported_layout(header.layout()?) {
+            return None;
+        }
+
+        let raw = bytemuck::try_from_bytes::<RawNodeReputationV2Data>(raw_bytes).ok()?;
+        Some(NodeReputationV2Ref { header, raw })
+    }
+
+    pub fn from_bytes_mut(bytes: &mut [u8]) -> Option<NodeReputationV2Mut<'_>> {
+        if bytes.len() != Self::LEN {
+            return None;
+        }
+
+        let (header_bytes, raw_bytes) = bytes.split_at_mut(AccountHeaderV1::LEN);
+        let header = bytemuck::try_from_bytes_mut::<AccountHeaderV1>(header_bytes).ok()?;
+        if !Self::is_supported_layout(header.layout()?) {
+            return None;
+        }
+
+        let raw = bytemuck::try_from_bytes_mut::<RawNodeReputationV2Data>(raw_bytes).ok()?;
+        Some(NodeReputationV2Mut { header, raw })
+    }
+
+    pub fn read(bytes: &[u8]) -> Option<Self> {
+        Self::from_bytes(bytes).map(|account| *account.as_raw())
+    }
+
+    pub fn write_bytes(&self, bytes: &mut [u8]) -> bool {
+        if bytes.len() != Self::LEN {
+            return false;
+        }
+
+        let (header_bytes, raw_bytes) = bytes.split_at_mut(AccountHeaderV1::LEN);
+        header_bytes.copy_from_slice(bytemuck::bytes_of(&AccountHeaderV1::new(
+            AccountDiscriminator::NodeReputationV2,
+        )));
+        raw_bytes.copy_from_slice(bytemuck::bytes_of(self));
+        true
+    }
+
+    fn is_supported_layout(layout: ParsedAccountLayout) -> bool {
+        layout
+            == ParsedAccountLayout::new(
+                AccountDiscriminator::NodeReputationV2,
+                AccountLayoutVersion::V1,
+            )
+    }
+
+    /// Records the node's part in a finalized or expired escrow, as its winner or as one of
+    /// its selected verifiers. Verifiers of expired escrows are not recorded.
+    ///
+    /// `verifier_set` is the escrow's [`BundleEscrowV2VerifierSet`], as returned by
+    /// `verifier_set()` on the account.
+    pub fn record_escrow(
+        &mut self,
+        escrow: &RawBundleEscrowV2Data,
+        verifier_set: &BundleEscrowV2VerifierSet,
+        current_slot: u64,
+    ) -> Result<(), AuctionError> {
+        let status = escrow.status;
+        if self.node_pubkey == escrow.winner_node_pubkey {
+            match status {
+                BundleEscrowV2Status::FinalizedVerified => {
+                    self.verified_results = self.verified_results.saturating_add(1);
+                    self.accepted_output_tokens = self
+                        .accepted_output_tokens
+                        .saturating_add(escrow.accepted_output_tokens);
+                }
+                BundleEscrowV2Status::FinalizedRejected => {
+                    self.rejected_results = self.rejected_results.saturating_add(1);
+                }
+                BundleEscrowV2Status::Expired => {
+                    self.expiries = self.expiries.saturating_add(1);
+                }
+                _ => return Err(AuctionError::InvalidNodeReputationV2),
+            }
+            self.wins = self.wins.saturating_add(1);
+        } else {
+            let verifier_index = verifier_set
+                .position(&self.node_pubkey)
+                .filter(|_| {
+                    matches!(
+                        status,
+                        BundleEscrowV2Status::FinalizedVerified
+                            | BundleEscrowV2Status::FinalizedRejected
+                    )
+                })
+                .ok_or(AuctionError::InvalidNodeReputationV2)?;
+            self.verifications_assigned = self.verifications_assigned.saturating_add(1);
+            if verifier_set.in_quorum(verifier_index) {
+                self.verifications_participated = self.verifications_participated.saturating_add(1);
+            }
+        }
+
+        self.last_updated_slot = current_slot;
+        Ok(())
+    }
+
+    /// Share of won escrows that ended with a verified result. `None` before the first win.
+    pub fn result_reliability_bps(&self) -> Option<u16> {
+        ratio_bps(self.verified_results, self.wins)
+    }
+
+    /// Share of verifier assignments where the node counted towards the quorum. `None`
+    /// before the first assignment.
+    pub fn verification_participation_bps(&self) -> Option<u16> {
+        ratio_bps(self.verifications_participated, self.verifications_assigned)
+    }
+
+    /// Kept commitments over all commitments, verified results and quorum participations
+    /// against wins and verifier assignments, smoothed so a node without history scores half.
+    pub fn reliability_score_bps(&self) -> u16 {
+        let kept = u64::from(self.verified_results) + u64::from(self.verifications_participated);
+        let total = u64::from(self.wins) + u64::from(self.verifications_assigned);
+        ((kept + 1) * u64::from(NODE_REPUTATION_V2_MAX_SCORE_BPS) / (total + 2)) as u16
+    }
+}
+
+fn ratio_bps(numerator: u32, denominator: u32) -> Option<u16> {
+    (denominator != 0).then(|| {
+        (u64::from(numerator) * u64::from(NODE_REPUTATION_V2_MAX_SCORE_BPS)
+            / u64::from(denominator)) as u16
+    })
+}
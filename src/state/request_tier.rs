@@ -15,6 +15,28 @@ pub enum RequestTier {
 }
 unsafe impl Pod for RequestTier {}
 
+/// Per-tier timing and sizing parameters.
+///
+/// Implemented by [`RequestTier`] for the compiled-in defaults and by
+/// [`RequestTierConfigV2`](crate::RequestTierConfigV2) for the values configured on-chain
+/// in [`ConfigPolicyV2`](crate::ConfigPolicyV2), so helpers can be written once against either.
+pub trait TierParams {
+    fn get_bid_reveal_duration(&self) -> u64;
+    fn get_active_auction_duration(&self) -> u64;
+    fn get_bundle_duration(&self) -> u64;
+    /// The maximum number of requests per bundle
+    fn get_request_per_bundle(&self) -> u64;
+    /// Maximum allowed context length (in tokens) per tier
+    fn get_max_context_length_tokens(&self) -> u64;
+    fn get_job_submission_duration_slots(&self) -> u64;
+    fn get_bid_commitment_amount_multiplier(&self) -> u64;
+    fn get_auction_credits_multiplier(&self) -> u64;
+    fn get_v2_settlement_window_slots(&self) -> u64;
+    fn get_v2_result_window_slots(&self) -> u64;
+    fn get_v2_verification_window_slots(&self) -> u64;
+    fn get_v2_claim_window_slots(&self) -> u64;
+}
+
 impl RequestTier {
     pub const ALL: [RequestTier; 5] = [
         RequestTier::Eco,
@@ -146,6 +168,20 @@ impl RequestTier {
         self.production_default_v2_claim_window_slots()
     }
 
+    pub const fn name(&self) -> &'static str {
+        match self {
+            RequestTier::Eco => "Eco",
+            RequestTier::Small => "Small",
+            RequestTier::Standard => "Standard",
+            RequestTier::Pro => "Pro",
+            RequestTier::Large => "Large",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|tier| tier.name() == name)
+    }
+
     pub fn context_tier_for_tokens(tokens: u64) -> Option<Self> {
         Self::ALL
             .iter()
@@ -153,3 +189,53 @@ impl RequestTier {
             .copied()
     }
 }
+
+impl TierParams for RequestTier {
+    fn get_bid_reveal_duration(&self) -> u64 {
+        RequestTier::get_bid_reveal_duration(self)
+    }
+
+    fn get_active_auction_duration(&self) -> u64 {
+        RequestTier::get_active_auction_duration(self)
+    }
+
+    fn get_bundle_duration(&self) -> u64 {
+        RequestTier::get_bundle_duration(self)
+    }
+
+    fn get_request_per_bundle(&self) -> u64 {
+        RequestTier::get_request_per_bundle(self)
+    }
+
+    fn get_max_context_length_tokens(&self) -> u64 {
+        RequestTier::get_max_context_length_tokens(self)
+    }
+
+    fn get_job_submission_duration_slots(&self) -> u64 {
+        RequestTier::get_job_submission_duration_slots(self)
+    }
+
+    fn get_bid_commitment_amount_multiplier(&self) -> u64 {
+        RequestTier::get_bid_commitment_amount_multiplier(self)
+    }
+
+    fn get_auction_credits_multiplier(&self) -> u64 {
+        RequestTier::get_auction_credits_multiplier(self)
+    }
+
+    fn get_v2_settlement_window_slots(&self) -> u64 {
+        RequestTier::get_v2_settlement_window_slots(self)
+    }
+
+    fn get_v2_result_window_slots(&self) -> u64 {
+        RequestTier::get_v2_result_window_slots(self)
+    }
+
+    fn get_v2_verification_window_slots(&self) -> u64 {
+        RequestTier::get_v2_verification_window_slots(self)
+    }
+
+    fn get_v2_claim_window_slots(&self) -> u64 {
+        RequestTier::get_v2_claim_window_slots(self)
+    }
+}
@@ -0,0 +1,286 @@
+use super::{
+    AccountDiscriminator, AccountHeaderV1, AccountLayoutVersion, ConfigPolicyV2,
+    ParsedAccountLayout, Pubkey, RequestTier, RequestTierConfigV2,
+};
+use crate::error::AuctionError;
+use bytemuck::{Pod, Zeroable};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::ops::Deref;
+
+/// A single configured tier. Built-in tiers use their `RequestTier` discriminant as `tier_id`.
+#[derive(Pod, Clone, Copy, Zeroable, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[repr(C)]
+pub struct TierRegistryV2Entry {
+    pub tier_id: u64,
+    pub config: RequestTierConfigV2,
+}
+
+impl TierRegistryV2Entry {
+    pub const LEN: usize = std::mem::size_of::<TierRegistryV2Entry>();
+}
+
+/// Fixed-size prefix of a `TierRegistryV2` account.
+///
+/// The account holds `capacity` entry slots after this prefix, of which the first
+/// `tier_count` are in use.
+///
+/// The registry is only a data structure so far: escrows, bids and the policy snapshot still
+/// read tiers through [`ConfigPolicyV2::tier_config`] and [`RequestTier`], so a tier added
+/// here is not yet usable by any instruction.
+#[derive(Pod, Clone, Copy, Zeroable, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[repr(C)]
+pub struct RawTierRegistryV2Data {
+    pub config_policy: Pubkey,
+    pub bump: u64,
+    pub tier_count: u16,
+    pub capacity: u16,
+    pub _reserved0: [u8; 4],
+}
+
+pub type TierRegistryV2 = RawTierRegistryV2Data;
+
+#[derive(Debug)]
+pub struct TierRegistryV2Ref<'a> {
+    header: &'a AccountHeaderV1,
+    raw: &'a RawTierRegistryV2Data,
+    slots: &'a [TierRegistryV2Entry],
+}
+
+#[derive(Debug)]
+pub struct TierRegistryV2Mut<'a> {
+    header: &'a mut AccountHeaderV1,
+    raw: &'a mut RawTierRegistryV2Data,
+    slots: &'a mut [TierRegistryV2Entry],
+}
+
+impl RawTierRegistryV2Data {
+    pub const PAYLOAD_LEN: usize = std::mem::size_of::<RawTierRegistryV2Data>();
+    pub const PREFIX_LEN: usize = AccountHeaderV1::LEN + Self::PAYLOAD_LEN;
+
+    pub const fn account_len(capacity: u16) -> usize {
+        Self::PREFIX_LEN + capacity as usize * TierRegistryV2Entry::LEN
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<TierRegistryV2Ref<'_>> {
+        if bytes.len() < Self::PREFIX_LEN {
+            return None;
+        }
+
+        let (header_bytes, rest) = bytes.split_at(AccountHeaderV1::LEN);
+        let header = bytemuck::try_from_bytes::<AccountHeaderV1>(header_bytes).ok()?;
+        if !Self::is_supported_layout(header.layout()?) {
+            return None;
+        }
+
+        let (raw_bytes, slot_bytes) = rest.split_at(Self::PAYLOAD_LEN);
+        let raw = bytemuck::try_from_bytes::<RawTierRegistryV2Data>(raw_bytes).ok()?;
+        if bytes.len() != Self::account_len(raw.capacity) || raw.tier_count > raw.capacity {
+            return None;
+        }
+
+        let slots = bytemuck::try_cast_slice::<u8, TierRegistryV2Entry>(slot_bytes).ok()?;
+        Some(TierRegistryV2Ref { header, raw, slots })
+    }
+
+    pub fn from_bytes_mut(bytes: &mut [u8]) -> Option<TierRegistryV2Mut<'_>> {
+        let bytes_len = bytes.len();
+        if bytes_len < Self::PREFIX_LEN {
+            return None;
+        }
+
+        let (header_bytes, rest) = bytes.split_at_mut(AccountHeaderV1::LEN);
+        let header = bytemuck::try_from_bytes_mut::<AccountHeaderV1>(header_bytes).ok()?;
+        if !Self::is_supported_layout(header.layout()?) {
+            return None;
+        }
+
+        let (raw_bytes, slot_bytes) = rest.split_at_mut(Self::PAYLOAD_LEN);
+        let raw = bytemuck::try_from_bytes_mut::<RawTierRegistryV2Data>(raw_bytes).ok()?;
+        if bytes_len != Self::account_len(raw.capacity) || raw.tier_count > raw.capacity {
+            return None;
+        }
+
+        let slots = bytemuck::try_cast_slice_mut::<u8, TierRegistryV2Entry>(slot_bytes).ok()?;
+        Some(TierRegistryV2Mut { header, raw, slots })
+    }
+
+    /// Writes an empty registry with room for `capacity` tiers.
+    pub fn init_bytes(
+        bytes: &mut [u8],
+        config_policy: Pubkey,
+        bump: u64,
+        capacity: u16,
+    ) -> Option<TierRegistryV2Mut<'_>> {
+        if bytes.len() != Self::account_len(capacity) {
+            return None;
+        }
+
+        let (header_bytes, rest) = bytes.split_at_mut(AccountHeaderV1::LEN);
+        header_bytes.copy_from_slice(bytemuck::bytes_of(&AccountHeaderV1::new(
+            AccountDiscriminator::TierRegistryV2,
+        )));
+        let (raw_bytes, slot_bytes) = rest.split_at_mut(Self::PAYLOAD_LEN);
+        raw_bytes.copy_from_slice(bytemuck::bytes_of(&RawTierRegistryV2Data {
+            config_policy,
+            bump,
+            tier_count: 0,
+            capacity,
+            _reserved0: [0; 4],
+        }));
+        slot_bytes.fill(0);
+
+        Self::from_bytes_mut(bytes)
+    }
+
+    fn is_supported_layout(layout: ParsedAccountLayout) -> bool {
+        layout
+            == ParsedAccountLayout::new(
+                AccountDiscriminator::TierRegistryV2,
+                AccountLayoutVersion::V1,
+            )
+    }
+}
+
+fn find_entry(entries: &[TierRegistryV2Entry], tier_id: u64) -> Option<usize> {
+    entries.iter().position(|entry| entry.tier_id == tier_id)
+}
+
+/// Picks the tier with the smallest context length that still fits `tokens`, which is the
+/// first match when walking the entries in ascending context-length order. Ties are broken
+/// by the lower tier id.
+fn context_tier_for_tokens(entries: &[TierRegistryV2Entry], tokens: u64) -> Option<u64> {
+    entries
+        .iter()
+        .filter(|entry| tokens <= entry.config.max_context_length_tokens)
+        .min_by_key(|entry| (entry.config.max_context_length_tokens, entry.tier_id))
+        .map(|entry| entry.tier_id)
+}
+
+impl<'a> TierRegistryV2Ref<'a> {
+    pub fn header(&self) -> &AccountHeaderV1 {
+        self.header
+    }
+
+    pub fn layout(&self) -> ParsedAccountLayout {
+        self.header.layout().unwrap()
+    }
+
+    pub fn as_raw(&self) -> &RawTierRegistryV2Data {
+        self.raw
+    }
+
+    pub fn entries(&self) -> &'a [TierRegistryV2Entry] {
+        &self.slots[..usize::from(self.raw.tier_count)]
+    }
+
+    pub fn tier_config(&self, tier_id: u64) -> Option<&'a RequestTierConfigV2> {
+        let entries = self.entries();
+        find_entry(entries, tier_id).map(|index| &entries[index].config)
+    }
+
+    pub fn contains(&self, tier_id: u64) -> bool {
+        find_entry(self.entries(), tier_id).is_some()
+    }
+
+    /// Entries sorted by ascending `max_context_length_tokens`, then by tier id.
+    pub fn entries_by_context_length(&self) -> Vec<&'a TierRegistryV2Entry> {
+        let mut entries = self.entries().iter().collect::<Vec<_>>();
+        entries.sort_by_key(|entry| (entry.config.max_context_length_tokens, entry.tier_id));
+        entries
+    }
+
+    pub fn context_tier_for_tokens(&self, tokens: u64) -> Option<u64> {
+        context_tier_for_tokens(self.entries(), tokens)
+    }
+}
+
+impl Deref for TierRegistryV2Ref<'_> {
+    type Target = RawTierRegistryV2Data;
+
+    fn deref(&self) -> &Self::Target {
+        self.raw
+    }
+}
+
+impl<'a> TierRegistryV2Mut<'a> {
+    pub fn header(&self) -> &AccountHeaderV1 {
+        self.header
+    }
+
+    pub fn layout(&self) -> ParsedAccountLayout {
+        self.header.layout().unwrap()
+    }
+
+    pub fn as_raw(&self) -> &RawTierRegistryV2Data {
+        self.raw
+    }
+
+    pub fn entries(&self) -> &[TierRegistryV2Entry] {
+        &self.slots[..usize::from(self.raw.tier_count)]
+    }
+
+    pub fn tier_config(&self, tier_id: u64) -> Option<&RequestTierConfigV2> {
+        let entries = self.entries();
+        find_entry(entries, tier_id).map(|index| &entries[index].config)
+    }
+
+    pub fn context_tier_for_tokens(&self, tokens: u64) -> Option<u64> {
+        context_tier_for_tokens(self.entries(), tokens)
+    }
+
+    /// Inserts a new tier or replaces the config of an existing one.
+    pub fn upsert(
+        &mut self,
+        tier_id: u64,
+        config: RequestTierConfigV2,
+    ) -> Result<(), AuctionError> {
+        if !config.validate() {
+            return Err(AuctionError::InvalidTierConfig);
+        }
+
+        let tier_count = usize::from(self.raw.tier_count);
+        if let Some(index) = find_entry(&self.slots[..tier_count], tier_id) {
+            self.slots[index].config = config;
+            return Ok(());
+        }
+
+        if self.raw.tier_count >= self.raw.capacity {
+            return Err(AuctionError::TierRegistryFull);
+        }
+        self.slots[tier_count] = TierRegistryV2Entry { tier_id, config };
+        self.raw.tier_count += 1;
+        Ok(())
+    }
+
+    /// Removes a tier, moving the last entry into its slot.
+    pub fn remove(&mut self, tier_id: u64) -> Result<RequestTierConfigV2, AuctionError> {
+        let tier_count = usize::from(self.raw.tier_count);
+        let index =
+            find_entry(&self.slots[..tier_count], tier_id).ok_or(AuctionError::TierNotFound)?;
+        let removed = self.slots[index].config;
+
+        self.slots.swap(index, tier_count - 1);
+        self.slots[tier_count - 1] = TierRegistryV2Entry::zeroed();
+        self.raw.tier_count -= 1;
+        Ok(removed)
+    }
+
+    /// Seeds the registry with the five built-in tiers as configured in `policy`.
+    pub fn import_policy_tiers(&mut self, policy: &ConfigPolicyV2) -> Result<(), AuctionError> {
+        for tier in RequestTier::ALL {
+            self.upsert(u64::from(tier), *policy.tier_config(tier))?;
+        }
+        Ok(())
+    }
+}
+
+impl Deref for TierRegistryV2Mut<'_> {
+    type Target = RawTierRegistryV2Data;
+
+    fn deref(&self) -> &Self::Target {
+        self.raw
+    }
+}
@@ -32,6 +32,21 @@ pub struct VerificationState {
     pub merkle_root_iv: [u8; 16],
 }
 
+/// Whether a commitment stored next to `iv` is encrypted. An all-zero IV marks plaintext.
+pub fn is_encrypted(iv: &[u8; 16]) -> bool {
+    *iv != [0; 16]
+}
+
+impl VerificationState {
+    pub fn is_output_hash_encrypted(&self) -> bool {
+        is_encrypted(&self.output_hash_iv)
+    }
+
+    pub fn is_merkle_root_encrypted(&self) -> bool {
+        is_encrypted(&self.merkle_root_iv)
+    }
+}
+
 #[derive(Clone, Copy, Zeroable, Debug, PartialEq, Default, IntoPrimitive, TryFromPrimitive)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 #[repr(u64)]
@@ -0,0 +1,136 @@
+//! Output token ranges checked by each verifier, stored as the `[start, end)` pairs of
+//! `assigned_verifiers_token_ranges`.
+//!
+//! Ranges are computed from the posted output token count alone, so the coordinator, the
+//! verifiers and the program all derive the same assignment.
+
+use crate::error::AuctionError;
+use crate::verifier_selection::SelectionRng;
+use crate::MAX_EXTENDED_VERIFIERS_PER_AUCTION;
+use sha2::{Digest, Sha256};
+
+const TOKEN_RANGE_DOMAIN: &[u8] = b"ambient.verifier.ranges.v2";
+
+/// Flattened `[start, end)` pairs, one per verifier slot. Unused slots are `[0, 0)`.
+pub type TokenRanges = [u64; MAX_EXTENDED_VERIFIERS_PER_AUCTION * 2];
+
+/// How [`assign_token_ranges`] spreads the output tokens over the verifiers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenRangeStrategy {
+    /// Contiguous ranges of equal length that cover every token once. The first
+    /// `output_tokens % verifier_count` verifiers get one token more.
+    EqualSplit,
+    /// Every token is checked by exactly `redundancy` verifiers. The tokens are split into
+    /// `verifier_count - redundancy + 1` equal segments and verifier `i` checks the
+    /// `redundancy` consecutive segments ending at segment `i`, so verifiers at either end
+    /// check fewer segments.
+    Overlapping { redundancy: u8 },
+    /// Each verifier checks one window of `window_tokens` at a position drawn from the auction
+    /// hash. Windows may overlap and leave tokens unchecked.
+    SampledWindows {
+        window_tokens: u64,
+        auction_hash: [u8; 32],
+    },
+}
+
+/// Assigns a `[start, end)` range of the `output_tokens` to each of `verifier_count`
+/// verifiers.
+pub fn assign_token_ranges(
+    output_tokens: u64,
+    verifier_count: usize,
+    strategy: TokenRangeStrategy,
+) -> Result<TokenRanges, AuctionError> {
+    if verifier_count == 0 || verifier_count > MAX_EXTENDED_VERIFIERS_PER_AUCTION {
+        return Err(AuctionError::InvalidVerifierCount);
+    }
+    if output_tokens == 0 {
+        return Err(AuctionError::InvalidTokenRanges);
+    }
+
+    let mut ranges = [0; MAX_EXTENDED_VERIFIERS_PER_AUCTION * 2];
+    let pairs = ranges[..verifier_count * 2].chunks_exact_mut(2);
+    match strategy {
+        TokenRangeStrategy::EqualSplit => {
+            for (index, pair) in pairs.enumerate() {
+                pair[0] = segment_boundary(output_tokens, verifier_count, index);
+                pair[1] = segment_boundary(output_tokens, verifier_count, index + 1);
+            }
+        }
+        TokenRangeStrategy::Overlapping { redundancy } => {
+            let redundancy = usize::from(redundancy);
+            if redundancy == 0 || redundancy > verifier_count {
+                return Err(AuctionError::InvalidTokenRanges);
+            }
+            let segments = verifier_count - redundancy + 1;
+            for (index, pair) in pairs.enumerate() {
+                let first_segment = (index + 1).saturating_sub(redundancy);
+                let last_segment = index.min(segments - 1);
+                pair[0] = segment_boundary(output_tokens, segments, first_segment);
+                pair[1] = segment_boundary(output_tokens, segments, last_segment + 1);
+            }
+        }
+        TokenRangeStrategy::SampledWindows {
+            window_tokens,
+            auction_hash,
+        } => {
+            if window_tokens == 0 {
+                return Err(AuctionError::InvalidTokenRanges);
+            }
+            let window_tokens = window_tokens.min(output_tokens);
+            let seed = Sha256::new()
+                .chain_update(TOKEN_RANGE_DOMAIN)
+                .chain_update(auction_hash)
+                .chain_update(output_tokens.to_le_bytes())
+                .finalize()
+                .into();
+            let mut rng = SelectionRng::new(seed);
+            for pair in pairs {
+                pair[0] = rng.below(output_tokens - window_tokens + 1);
+                pair[1] = pair[0] + window_tokens;
+            }
+        }
+    }
+    Ok(ranges)
+}
+
+/// Start of segment `index` when `output_tokens` is split into `segments` near-equal parts,
+/// the longer parts first.
+fn segment_boundary(output_tokens: u64, segments: usize, index: usize) -> u64 {
+    let segments = segments as u64;
+    let index = index as u64;
+    let base = output_tokens / segments;
+    let longer = output_tokens % segments;
+    index * base + index.min(longer)
+}
+
+/// Checks that every `[start, end)` pair is ordered and ends within `posted_output_tokens`.
+pub fn validate_token_ranges(
+    ranges: &[u64],
+    posted_output_tokens: u64,
+) -> Result<(), AuctionError> {
+    if !ranges.len().is_multiple_of(2)
+        || ranges
+            .chunks_exact(2)
+            .any(|pair| pair[0] > pair[1] || pair[1] > posted_output_tokens)
+    {
+        return Err(AuctionError::InvalidTokenRanges);
+    }
+    Ok(())
+}
+
+/// Each verifier's `verifier_reward_tokens`: the number of tokens in its validated range.
+pub fn verifier_reward_tokens(
+    ranges: &[u64],
+    posted_output_tokens: u64,
+) -> Result<[u64; MAX_EXTENDED_VERIFIERS_PER_AUCTION], AuctionError> {
+    validate_token_ranges(ranges, posted_output_tokens)?;
+    if ranges.len() > MAX_EXTENDED_VERIFIERS_PER_AUCTION * 2 {
+        return Err(AuctionError::InvalidTokenRanges);
+    }
+
+    let mut rewards = [0; MAX_EXTENDED_VERIFIERS_PER_AUCTION];
+    for (reward, pair) in rewards.iter_mut().zip(ranges.chunks_exact(2)) {
+        *reward = pair[1] - pair[0];
+    }
+    Ok(rewards)
+}
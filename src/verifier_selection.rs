@@ -0,0 +1,131 @@
+//! Stake-weighted verifier selection for `CommitAuctionSettlementV2`.
+//!
+//! The seed mixes the auction hash with the hash of the slot the escrow was opened in, see
+//! [`BundleEscrowV2Ref::verifier_seed_slot`](crate::BundleEscrowV2Ref::verifier_seed_slot). That slot is fixed by escrow state before bids are
+//! revealed, so the coordinator can not grind the seed by choosing which slot hash to commit
+//! to. Given the same candidates and seed, anyone can recompute the committed
+//! `selected_verifiers` with [`verify_selected_verifiers`].
+
+use crate::error::AuctionError;
+use crate::{Pubkey, MAX_EXTENDED_VERIFIERS_PER_AUCTION};
+use sha2::{Digest, Sha256};
+
+const VERIFIER_SELECTION_DOMAIN: &[u8] = b"ambient.verifier.select.v2";
+
+/// Derives the selection seed from the auction hash and the hash of `slot`.
+pub fn derive_verifier_selection_seed(
+    auction_hash: &[u8; 32],
+    slot: u64,
+    slot_hash: &[u8; 32],
+) -> [u8; 32] {
+    Sha256::new()
+        .chain_update(VERIFIER_SELECTION_DOMAIN)
+        .chain_update(auction_hash)
+        .chain_update(slot.to_le_bytes())
+        .chain_update(slot_hash)
+        .finalize()
+        .into()
+}
+
+/// A stream of `u64` draws, each the first eight bytes of `sha256(seed || counter)`.
+pub(crate) struct SelectionRng {
+    seed: [u8; 32],
+    counter: u64,
+}
+
+impl SelectionRng {
+    pub(crate) const fn new(seed: [u8; 32]) -> Self {
+        Self { seed, counter: 0 }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let digest = Sha256::new()
+            .chain_update(self.seed)
+            .chain_update(self.counter.to_le_bytes())
+            .finalize();
+        self.counter += 1;
+        u64::from_le_bytes(digest[..8].try_into().unwrap())
+    }
+
+    /// Uniform in `0..bound`, rejecting the draws that would bias a plain modulo.
+    pub(crate) fn below(&mut self, bound: u64) -> u64 {
+        let limit = u64::MAX - u64::MAX % bound;
+        loop {
+            let draw = self.next_u64();
+            if draw < limit {
+                return draw % bound;
+            }
+        }
+    }
+}
+
+/// Picks `count` verifiers without replacement, each with probability proportional to its
+/// stake among the candidates not yet picked.
+///
+/// `exclude` (the winner) and zero-stake candidates are skipped. Candidates are sorted by
+/// pubkey first, so their order does not change the result. Slots past `count` are left as
+/// the default pubkey, matching `BundleEscrowV2VerifierSet::selected_verifiers`. Whether
+/// `count` fits the escrow layout is checked against the policy, not here.
+pub fn select_verifiers(
+    candidates: &[(Pubkey, u64)],
+    seed: [u8; 32],
+    count: usize,
+    exclude: &Pubkey,
+) -> Result<[Pubkey; MAX_EXTENDED_VERIFIERS_PER_AUCTION], AuctionError> {
+    let mut eligible: Vec<(Pubkey, u64)> = candidates
+        .iter()
+        .filter(|(node, stake)| node != exclude && *stake != 0)
+        .copied()
+        .collect();
+    eligible.sort_unstable_by(|(a, _), (b, _)| a.as_ref().cmp(b.as_ref()));
+    if eligible.windows(2).any(|pair| pair[0].0 == pair[1].0) {
+        return Err(AuctionError::InvalidVerifierCandidates);
+    }
+    if count == 0 || count > MAX_EXTENDED_VERIFIERS_PER_AUCTION || count > eligible.len() {
+        return Err(AuctionError::InvalidVerifierCount);
+    }
+
+    let mut total_stake = eligible
+        .iter()
+        .try_fold(0u64, |total, (_, stake)| total.checked_add(*stake))
+        .ok_or(AuctionError::InvalidVerifierCandidates)?;
+    let mut rng = SelectionRng::new(seed);
+    let mut selected = [Pubkey::default(); MAX_EXTENDED_VERIFIERS_PER_AUCTION];
+    for slot in &mut selected[..count] {
+        let mut target = rng.below(total_stake);
+        let index = eligible
+            .iter()
+            .position(|(_, stake)| {
+                if target < *stake {
+                    true
+                } else {
+                    target -= stake;
+                    false
+                }
+            })
+            .expect("target is below the total stake");
+        let (node, stake) = eligible.remove(index);
+        total_stake -= stake;
+        *slot = node;
+    }
+    Ok(selected)
+}
+
+/// Checks that `committed` is the set [`select_verifiers`] picks for these inputs.
+///
+/// `committed` may be the three `selected_verifiers` of an earlier escrow layout or the
+/// sixteen of a V6 verifier set; slots past its length must be unused.
+pub fn verify_selected_verifiers(
+    candidates: &[(Pubkey, u64)],
+    seed: [u8; 32],
+    count: usize,
+    exclude: &Pubkey,
+    committed: &[Pubkey],
+) -> Result<(), AuctionError> {
+    let selected = select_verifiers(candidates, seed, count, exclude)?;
+    let (prefix, rest) = selected.split_at(committed.len().min(selected.len()));
+    if prefix != committed || rest.iter().any(|verifier| *verifier != Pubkey::default()) {
+        return Err(AuctionError::VerifierSelectionMismatch);
+    }
+    Ok(())
+}
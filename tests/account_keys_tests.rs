@@ -23,6 +23,7 @@ fn place_bid_account_keys_round_trip_and_order() {
         bid: 2,
         auction: 3,
         system_program: 4,
+        node_registration: None,
     };
 
     assert_eq!(
@@ -30,6 +31,16 @@ fn place_bid_account_keys_round_trip_and_order() {
         vec![1, 2, 3, 4]
     );
     assert_eq!(keys.as_accounts().to_account_keys(), keys);
+
+    let registered = PlaceBidAccountKeys {
+        node_registration: Some(5),
+        ..keys
+    };
+    assert_eq!(
+        registered.as_accounts().iter_owned().collect::<Vec<_>>(),
+        vec![1, 2, 3, 4, 5]
+    );
+    assert_eq!(registered.as_accounts().to_account_keys(), registered);
 }
 
 #[test]
@@ -75,6 +86,10 @@ fn open_bundle_escrow_v2_account_keys_round_trip_and_order() {
         bundle_escrow: 2,
         config_policy: 3,
         system_program: 4,
+        token_program: None,
+        token_mint: None,
+        escrow_vault: None,
+        payer_token_account: None,
     };
 
     assert_eq!(
@@ -82,6 +97,19 @@ fn open_bundle_escrow_v2_account_keys_round_trip_and_order() {
         vec![1, 2, 3, 4]
     );
     assert_eq!(keys.as_accounts().to_account_keys(), keys);
+
+    let keys = OpenBundleEscrowV2AccountKeys {
+        token_program: Some(5),
+        token_mint: Some(6),
+        escrow_vault: Some(7),
+        payer_token_account: Some(8),
+        ..keys
+    };
+    assert_eq!(
+        keys.as_accounts().iter_owned().collect::<Vec<_>>(),
+        vec![1, 2, 3, 4, 5, 6, 7, 8]
+    );
+    assert_eq!(keys.as_accounts().to_account_keys(), keys);
 }
 
 #[cfg(feature = "global-config")]
@@ -31,6 +31,26 @@ fn layout_sizes() {
     let _: u64 = request.requests_len;
 }
 
+#[test]
+fn bundle_account_len_covers_only_bundle_layouts() {
+    assert_eq!(
+        bundle_account_len(AccountLayoutVersion::LegacyV0),
+        Some(RawBundleData::LEGACY_LEN)
+    );
+    assert_eq!(
+        bundle_account_len(AccountLayoutVersion::V2),
+        Some(RawBundleData::LEGACY_LEN + BundleLayoutTrailerV1::LEN)
+    );
+    for version in [
+        AccountLayoutVersion::V3,
+        AccountLayoutVersion::V4,
+        AccountLayoutVersion::V5,
+        AccountLayoutVersion::V6,
+    ] {
+        assert_eq!(bundle_account_len(version), None);
+    }
+}
+
 #[test]
 fn request_bundle_alias_matches_legacy_layout() {
     assert_eq!(RequestBundle::LEN, RawBundleData::LEGACY_LEN);
@@ -221,7 +241,7 @@ fn write_legacy_bytes_preserves_v1_trailer() {
         ..Default::default()
     };
     let trailer = BundleLayoutTrailerV1::new();
-    let mut bytes = vec![0_u8; bundle_account_len(AccountLayoutVersion::V1)];
+    let mut bytes = vec![0_u8; bundle_account_len(AccountLayoutVersion::V1).unwrap()];
     bytes[RawBundleData::LEGACY_LEN..].copy_from_slice(bytemuck::bytes_of(&trailer));
 
     assert!(raw.write_legacy_bytes(&mut bytes));
@@ -249,7 +269,7 @@ fn legacy_helpers_support_v1_bundle_bytes() {
         requests_len: 0,
         ..Default::default()
     };
-    let mut bytes = vec![0_u8; bundle_account_len(AccountLayoutVersion::V1)];
+    let mut bytes = vec![0_u8; bundle_account_len(AccountLayoutVersion::V1).unwrap()];
     bytes[..RawBundleData::LEGACY_LEN].copy_from_slice(bytemuck::bytes_of(&raw));
     bytes[RawBundleData::LEGACY_LEN..]
         .copy_from_slice(bytemuck::bytes_of(&BundleLayoutTrailerV1::new()));
@@ -0,0 +1,131 @@
+use ambient_auction_api::{
+    error::AuctionError, AccountLayoutVersion, AuctionInstruction, BundleRefundPageV2,
+    BundleRefundPageV2Entry, BundleVerifierPageV2, BundleVerifierPageV2Entry,
+    ClaimRequesterRefundV2Accounts, ConfigPolicyV2PauseGroup, InstructionAccounts, Pubkey,
+    MAX_BUNDLE_REFUND_PAGE_V2_ENTRIES,
+};
+
+fn test_pubkey(byte: u8) -> Pubkey {
+    [byte; 32].into()
+}
+
+fn verifier_page(accepted: &[(u8, u64)]) -> BundleVerifierPageV2 {
+    let mut page = BundleVerifierPageV2::default();
+    for (index, (job, accepted_output_tokens)) in accepted.iter().enumerate() {
+        page.entries[index] = BundleVerifierPageV2Entry {
+            job_id: test_pubkey(*job),
+            accepted_output_tokens: *accepted_output_tokens,
+            ..Default::default()
+        };
+    }
+    page.entry_count = accepted.len() as u16;
+    page
+}
+
+#[test]
+fn bundle_refund_page_v2_round_trips_through_bytes() {
+    let page = BundleRefundPageV2::new(
+        test_pubkey(1),
+        2,
+        &[BundleRefundPageV2Entry::new(
+            test_pubkey(10),
+            test_pubkey(20),
+            100,
+        )],
+    )
+    .unwrap();
+    let mut bytes = vec![0; BundleRefundPageV2::LEN];
+    assert!(page.write_bytes(&mut bytes));
+
+    let parsed = BundleRefundPageV2::from_bytes(&bytes).unwrap();
+    assert_eq!(parsed.layout().version, AccountLayoutVersion::V1);
+    assert_eq!(parsed.page_index, 2);
+    assert_eq!(parsed.active_entries().len(), 1);
+    assert_eq!(parsed.active_entries()[0].refund_recipient, test_pubkey(20));
+
+    let too_many = vec![BundleRefundPageV2Entry::default(); MAX_BUNDLE_REFUND_PAGE_V2_ENTRIES + 1];
+    assert_eq!(
+        BundleRefundPageV2::new(test_pubkey(1), 0, &too_many),
+        Err(AuctionError::InvalidRefundPageV2Input)
+    );
+}
+
+#[test]
+fn bundle_refund_page_v2_refunds_unaccepted_tokens_per_job() {
+    let mut page = BundleRefundPageV2::new(
+        test_pubkey(1),
+        0,
+        &[
+            BundleRefundPageV2Entry::new(test_pubkey(10), test_pubkey(20), 100),
+            BundleRefundPageV2Entry::new(test_pubkey(11), test_pubkey(21), 50),
+        ],
+    )
+    .unwrap();
+    let verifier_pages = [verifier_page(&[(11, 50)]), verifier_page(&[(10, 40)])];
+
+    assert_eq!(page.settle(&verifier_pages, 3), Ok(180));
+    assert_eq!(page.entries[0].accepted_output_tokens, 40);
+    assert_eq!(page.entries[0].refund_amount, 180);
+    assert_eq!(page.entries[1].refund_amount, 0);
+    assert_eq!(page.total_refund_amount(), Some(180));
+
+    assert_eq!(
+        page.settle(&verifier_pages[..1], 3),
+        Err(AuctionError::InvalidRefundPageV2Input)
+    );
+    assert_eq!(
+        page.settle(&[verifier_page(&[(10, 101), (11, 0)])], 3),
+        Err(AuctionError::InvalidRefundPageV2Input)
+    );
+}
+
+#[test]
+fn bundle_refund_page_v2_claims_each_refund_once() {
+    let mut page = BundleRefundPageV2::new(
+        test_pubkey(1),
+        0,
+        &[
+            BundleRefundPageV2Entry::new(test_pubkey(10), test_pubkey(20), 100),
+            BundleRefundPageV2Entry::new(test_pubkey(11), test_pubkey(21), 50),
+        ],
+    )
+    .unwrap();
+    page.settle(&[verifier_page(&[(10, 0), (11, 25)])], 2)
+        .unwrap();
+
+    assert_eq!(
+        page.claim_refund(0, &test_pubkey(21)),
+        Err(AuctionError::InvalidRefundRecipient)
+    );
+    assert_eq!(page.claim_refund(0, &test_pubkey(20)), Ok(200));
+    assert_eq!(
+        page.claim_refund(0, &test_pubkey(20)),
+        Err(AuctionError::RequesterRefundAlreadyClaimed)
+    );
+    assert!(!page.all_refunds_claimed());
+    assert_eq!(page.claim_refund(1, &test_pubkey(21)), Ok(50));
+    assert!(page.all_refunds_claimed());
+    assert_eq!(
+        page.claim_refund(2, &test_pubkey(21)),
+        Err(AuctionError::InvalidRefundPageV2Input)
+    );
+}
+
+#[test]
+fn claim_requester_refund_v2_is_a_payout_instruction() {
+    assert_eq!(
+        AuctionInstruction::ClaimRequesterRefundV2.pause_group(),
+        Some(ConfigPolicyV2PauseGroup::Payout)
+    );
+    assert_eq!(
+        AuctionInstruction::InitBundleRefundPageV2.pause_group(),
+        Some(ConfigPolicyV2PauseGroup::Settlement)
+    );
+
+    let accounts = [1_u8, 2, 3, 4, 5, 6, 7];
+    let native = ClaimRequesterRefundV2Accounts::try_from(&accounts[..]).unwrap();
+    assert_eq!(native.iter().count(), 4);
+    let token = ClaimRequesterRefundV2Accounts::try_from_token_escrow(&accounts[..]).unwrap();
+    assert_eq!(token.refund_token_account, Some(&7));
+    assert_eq!(token.iter().copied().collect::<Vec<_>>(), accounts);
+}
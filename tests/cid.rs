@@ -0,0 +1,96 @@
+use ambient_auction_api::{
+    data_account_content_hash, error::AuctionError, Cid, CidCodec, CidVersion, JobRequest,
+    MAX_IPFS_CID_LENGTH,
+};
+
+const CIDV0: &str = "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
+const CIDV1_DAG_PB: &str = "bafybeie5nqv6kd3qnfjupgvz34woh3oksc3iau6abmyajn7qvtf6d2ho34";
+const CIDV1_DAG_PB_BASE58: &str = "zdj7Wg2Qkk4mYgAkVU1kppfQ2sMGz5zPwERVpeWmxCQLDxVoC";
+// Raw CIDv1 of the bytes `abc`.
+const CIDV1_RAW: &str = "bafkreif2pall7dybz7vecqka3zo24irdwabwdi4wc55jznaq75q7eaavvu";
+const CIDV1_RAW_BASE16: &str =
+    "f01551220ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+#[test]
+fn cidv0_and_cidv1_of_the_same_node_share_a_digest() {
+    let v0 = Cid::parse(CIDV0).unwrap();
+    assert_eq!(v0.version(), CidVersion::V0);
+    assert_eq!(v0.codec(), CidCodec::DagPb);
+    assert_eq!(v0.to_string(), CIDV0);
+
+    let v1 = Cid::parse(CIDV1_DAG_PB).unwrap();
+    assert_eq!(v1, Cid::v1(CidCodec::DagPb, v0.digest()));
+    assert_eq!(v1.to_string(), CIDV1_DAG_PB);
+    assert_eq!(Cid::parse(CIDV1_DAG_PB_BASE58), Ok(v1));
+    assert_eq!(
+        Cid::v1(CidCodec::DagPb, v0.digest()).to_string(),
+        CIDV1_DAG_PB
+    );
+}
+
+#[test]
+fn raw_cid_digest_is_the_data_account_content_hash() {
+    let cid: Cid = CIDV1_RAW.parse().unwrap();
+    assert_eq!(cid.codec(), CidCodec::Raw);
+    assert_eq!(cid.digest(), data_account_content_hash(b"abc"));
+    assert_eq!(Cid::parse(CIDV1_RAW_BASE16), Ok(cid));
+
+    let request = JobRequest {
+        input_hash: cid.digest().into(),
+        ..Default::default()
+    };
+    assert_eq!(request.input_cid().unwrap().to_string(), CIDV1_RAW);
+    let encrypted = JobRequest {
+        input_hash_iv: [1; 16],
+        ..request
+    };
+    assert_eq!(encrypted.input_cid(), None);
+}
+
+#[test]
+fn dag_pb_inputs_display_as_cidv0() {
+    let request = JobRequest {
+        input_hash: Cid::parse(CIDV0).unwrap().digest().into(),
+        ..Default::default()
+    };
+    assert_eq!(
+        request
+            .input_cid_with_codec(CidCodec::DagPb)
+            .unwrap()
+            .to_string(),
+        CIDV0
+    );
+    assert_eq!(
+        request.input_cid_with_codec(CidCodec::Raw),
+        request.input_cid()
+    );
+}
+
+#[test]
+fn malformed_cids_are_rejected() {
+    for cid in [
+        "",
+        "Qm",
+        &CIDV0[..45],
+        &CIDV1_RAW[..CIDV1_RAW.len() - 1],
+        "bafkreif2pall7dybz7vecqka3zo24irdwabwdi4wc55jznaq75q7eaavv1",
+        "xafkreif2pall7dybz7vecqka3zo24irdwabwdi4wc55jznaq75q7eaavvu",
+        &CIDV1_RAW_BASE16[..CIDV1_RAW_BASE16.len() - 1],
+        &"b".repeat(MAX_IPFS_CID_LENGTH + 1),
+    ] {
+        assert_eq!(Cid::parse(cid), Err(AuctionError::InvalidCid), "{cid}");
+    }
+}
+
+#[test]
+fn unsupported_codecs_and_hashes_are_rejected() {
+    // dag-cbor codec.
+    let dag_cbor = "f01711220ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+    // sha2-512 multihash code with a 32-byte digest.
+    let sha2_512 = "f01551320ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+    // CID version 2.
+    let version_2 = "f02551220ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+    for cid in [dag_cbor, sha2_512, version_2] {
+        assert_eq!(Cid::parse(cid), Err(AuctionError::UnsupportedCid), "{cid}");
+    }
+}
@@ -0,0 +1,66 @@
+#![cfg(feature = "unverified-commitment-cipher")]
+
+use ambient_auction_api::{
+    decrypt_commitment, encrypt_commitment, error::AuctionError, CommitmentKey,
+};
+
+// RFC 7748, section 6.1.
+const ALICE_SECRET: &str = "77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a";
+const ALICE_PUBLIC: &str = "8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a";
+const BOB_SECRET: &str = "5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb";
+const BOB_PUBLIC: &str = "de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f";
+const SHARED_SECRET: &str = "4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742";
+// HKDF-SHA256 of the shared secret, and `[0x42; 32]` under IV `01 02 .. 10`, both computed
+// with Python's `cryptography` package from the same draft parameters, not by node code.
+const COMMITMENT_KEY: &str = "b0d7f36fdc0de5eb481c0ca55d3bd87b76606b5f9015a5538ad0d443691629c4";
+const CIPHERTEXT: &str = "d77679a5feba21237d54cef3ab41ff1163d1bf8a8f8427698f3f0704ba22897a";
+
+fn bytes<const N: usize>(hex: &str) -> [u8; N] {
+    let mut out = [0; N];
+    for (index, byte) in out.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[index * 2..index * 2 + 2], 16).unwrap();
+    }
+    out
+}
+
+fn test_iv() -> [u8; 16] {
+    std::array::from_fn(|index| index as u8 + 1)
+}
+
+#[test]
+fn both_sides_derive_the_same_commitment_key() {
+    let alice = CommitmentKey::derive(&bytes(ALICE_SECRET), &bytes(BOB_PUBLIC)).unwrap();
+    let bob = CommitmentKey::derive(&bytes(BOB_SECRET), &bytes(ALICE_PUBLIC)).unwrap();
+    assert_eq!(alice, bob);
+    assert_eq!(
+        alice,
+        CommitmentKey::from_shared_secret(&bytes(SHARED_SECRET))
+    );
+    assert_eq!(alice.as_bytes(), &bytes(COMMITMENT_KEY));
+
+    assert_eq!(
+        CommitmentKey::derive(&bytes(ALICE_SECRET), &[0; 32]),
+        Err(AuctionError::InvalidEncryptionKey)
+    );
+}
+
+#[test]
+fn commitments_round_trip_and_match_the_vector() {
+    let key = CommitmentKey::from_shared_secret(&bytes(SHARED_SECRET));
+    let ciphertext = encrypt_commitment(&key, &test_iv(), &[0x42; 32]).unwrap();
+    assert_eq!(ciphertext, bytes(CIPHERTEXT));
+    assert_eq!(
+        decrypt_commitment(&key, &test_iv(), &ciphertext),
+        [0x42; 32]
+    );
+
+    let mut other_iv = test_iv();
+    other_iv[15] ^= 1;
+    assert_ne!(decrypt_commitment(&key, &other_iv, &ciphertext), [0x42; 32]);
+
+    assert_eq!(
+        encrypt_commitment(&key, &[0; 16], &[0x42; 32]),
+        Err(AuctionError::InvalidEncryptionIv)
+    );
+    assert_eq!(decrypt_commitment(&key, &[0; 16], &[0x42; 32]), [0x42; 32]);
+}
@@ -1,4 +1,7 @@
-use ambient_auction_api::{AccountLayoutVersion, ConfigPolicyV2, RequestTier};
+use ambient_auction_api::{
+    AccountLayoutVersion, ConfigPolicyV2, RawBundleData, RequestTier, RequestTierConfigV2,
+    TierParams,
+};
 use memoffset::offset_of;
 use std::mem::size_of;
 
@@ -46,3 +49,102 @@ fn config_policy_v2_round_trips_through_bytes() {
         Ok(AccountLayoutVersion::V2)
     );
 }
+
+#[test]
+fn bundle_escrow_v2_layout_version_is_an_explicit_setting() {
+    let mut policy = ConfigPolicyV2::production_default();
+    assert_eq!(
+        policy.configured_bundle_escrow_v2_layout_version(),
+        Ok(AccountLayoutVersion::V5)
+    );
+
+    // Policies stored before the setting keep opening escrows in their own layout.
+    policy.bundle_escrow_v2_layout_version = 0;
+    assert_eq!(
+        policy.configured_bundle_escrow_v2_layout_version(),
+        Ok(AccountLayoutVersion::V2)
+    );
+
+    for version in [0xff, AccountLayoutVersion::V6 as u8] {
+        policy.bundle_escrow_v2_layout_version = version;
+        assert_eq!(
+            policy.configured_bundle_escrow_v2_layout_version(),
+            Err(version)
+        );
+    }
+    policy.v2_account_layout_version = AccountLayoutVersion::V6 as u8;
+    assert_eq!(
+        policy.configured_bundle_escrow_v2_layout_version(),
+        Ok(AccountLayoutVersion::V6)
+    );
+}
+
+fn assert_same_params(left: &impl TierParams, right: &impl TierParams) {
+    assert_eq!(
+        left.get_bid_reveal_duration(),
+        right.get_bid_reveal_duration()
+    );
+    assert_eq!(left.get_bundle_duration(), right.get_bundle_duration());
+    assert_eq!(
+        left.get_request_per_bundle(),
+        right.get_request_per_bundle()
+    );
+    assert_eq!(
+        left.get_max_context_length_tokens(),
+        right.get_max_context_length_tokens()
+    );
+    assert_eq!(
+        left.get_v2_claim_window_slots(),
+        right.get_v2_claim_window_slots()
+    );
+}
+
+#[test]
+fn production_default_tier_configs_match_static_tier_params() {
+    let policy = ConfigPolicyV2::production_default();
+
+    for tier in RequestTier::ALL {
+        assert_same_params(policy.tier_config(tier), &tier);
+    }
+}
+
+#[test]
+fn policy_aware_bundle_helpers_read_configured_tier_params() {
+    let mut policy = ConfigPolicyV2::production_default();
+    policy.tier_configs[0] = RequestTierConfigV2 {
+        bundle_duration: 40,
+        requests_per_bundle: 2,
+        max_context_length_tokens: 1_000,
+        ..*policy.tier_config(RequestTier::Eco)
+    };
+
+    let bundle = RawBundleData::new_with_policy(
+        [1; 32],
+        [2; 32],
+        0,
+        100,
+        RequestTier::Eco,
+        RequestTier::Eco,
+        &policy,
+    );
+    assert_eq!(bundle.expiry_slot, 140);
+    assert_eq!(bundle.max_context_length, 1_000);
+
+    let static_bundle =
+        RawBundleData::new([1; 32], [2; 32], 0, 100, RequestTier::Eco, RequestTier::Eco);
+    assert_eq!(static_bundle.expiry_slot, 125);
+
+    let bundle = RawBundleData {
+        requests_len: 2,
+        ..bundle
+    };
+    assert!(bundle.is_expired(140));
+    assert!(!bundle.is_expired_with_policy(140, &policy));
+
+    let bytes = bytemuck::bytes_of(&bundle);
+    assert_eq!(RawBundleData::is_expired_from_bytes(bytes, 140), Some(true));
+    assert_eq!(
+        RawBundleData::is_expired_from_bytes_with_policy(bytes, 140, &policy),
+        Some(false)
+    );
+}
@@ -0,0 +1,228 @@
+#![cfg(feature = "policy-document")]
+
+use ambient_auction_api::{
+    ConfigPolicyV2, ConfigPolicyV2Document, ConfigPolicyV2DocumentErrorKind, ConfigPolicyV2Flag,
+    ConfigPolicyV2Patch, Pubkey, RequestTier, RequestTierConfigV2,
+};
+
+fn test_pubkey(byte: u8) -> Pubkey {
+    [byte; 32].into()
+}
+
+fn sample_policy() -> ConfigPolicyV2 {
+    let mut policy = ConfigPolicyV2::production_default();
+    policy.admin_authorities[0] = test_pubkey(1);
+    policy.admin_authorities[1] = test_pubkey(2);
+    policy.service_authorities[0] = test_pubkey(3);
+    policy.policy_flags = ConfigPolicyV2Flag::AllowServiceCommitOverride.mask();
+    policy.v2_verifier_quorum = 2;
+    policy.tier_configs[3].requests_per_bundle = 7;
+    policy
+}
+
+fn assert_error(
+    document: &ConfigPolicyV2Document,
+    path: &str,
+    kind: ConfigPolicyV2DocumentErrorKind,
+) {
+    let err = ConfigPolicyV2::from_document(document).unwrap_err();
+    assert_eq!(err.path, path);
+    assert_eq!(err.kind, kind);
+}
+
+#[test]
+fn document_round_trips_through_toml_and_json() {
+    let policy = sample_policy();
+    let document = policy.to_document().unwrap();
+
+    let toml = document.to_toml_string().unwrap();
+    assert!(toml.contains("[tiers.Pro]"));
+    assert_eq!(
+        ConfigPolicyV2Document::from_toml_str(&toml).unwrap(),
+        document
+    );
+
+    let json = document.to_json_string().unwrap();
+    assert_eq!(
+        ConfigPolicyV2Document::from_json_str(&json).unwrap(),
+        document
+    );
+
+    assert_eq!(ConfigPolicyV2::from_document(&document).unwrap(), policy);
+}
+
+#[test]
+fn document_round_trip_keeps_authority_slots() {
+    let mut policy = sample_policy();
+    policy.admin_authorities[0] = Pubkey::default();
+    policy.admin_authorities[3] = test_pubkey(4);
+    policy.service_authorities[0] = Pubkey::default();
+    policy.service_authorities[2] = test_pubkey(3);
+
+    let document = policy.to_document().unwrap();
+    assert_eq!(document.admin_authorities.len(), 4);
+    assert_eq!(document.admin_authorities[0], "");
+    assert_eq!(document.service_authorities.len(), 3);
+
+    let imported = ConfigPolicyV2::from_document(&document).unwrap();
+    assert_eq!(imported, policy);
+    assert!(ConfigPolicyV2::diff(&policy, &imported).is_empty());
+
+    let mut document = document;
+    document.admin_authorities = vec![String::new(); 2];
+    assert_error(
+        &document,
+        "admin_authorities",
+        ConfigPolicyV2DocumentErrorKind::MissingAdminAuthority,
+    );
+}
+
+#[test]
+fn diff_against_on_chain_policy_yields_reviewable_patches() {
+    let on_chain = sample_policy();
+    let mut document = on_chain.to_document().unwrap();
+    document.v2_verifier_quorum = 3;
+    document
+        .tiers
+        .get_mut(RequestTier::Large.name())
+        .unwrap()
+        .claim_window_slots = 99;
+
+    let desired = ConfigPolicyV2::from_document(&document).unwrap();
+    let diff = ConfigPolicyV2::diff(&on_chain, &desired);
+    assert!(diff.direct.is_empty());
+    let patches = diff.scheduled;
+    assert_eq!(patches.len(), 2);
+    assert!(matches!(
+        patches[0],
+        ConfigPolicyV2Patch::VerifierSettings {
+            v2_verifier_quorum: 3,
+            ..
+        }
+    ));
+    assert!(matches!(
+        patches[1],
+        ConfigPolicyV2Patch::TierConfig {
+            tier: RequestTier::Large,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn validation_errors_point_at_the_offending_field() {
+    let base = sample_policy().to_document().unwrap();
+
+    let mut document = base.clone();
+    document.admin_authorities[1] = "not-a-key".to_string();
+    assert_error(
+        &document,
+        "admin_authorities[1]",
+        ConfigPolicyV2DocumentErrorKind::InvalidPubkey,
+    );
+
+    let mut document = base.clone();
+    document
+        .service_authorities
+        .push(document.service_authorities[0].clone());
+    assert_error(
+        &document,
+        "service_authorities[1]",
+        ConfigPolicyV2DocumentErrorKind::DuplicateAuthority,
+    );
+
+    let mut document = base.clone();
+    document.admin_authorities.clear();
+    assert_error(
+        &document,
+        "admin_authorities",
+        ConfigPolicyV2DocumentErrorKind::MissingAdminAuthority,
+    );
+
+    let mut document = base.clone();
+    document.v2_verifier_quorum = 4;
+    assert_error(
+        &document,
+        "v2_verifier_quorum",
+        ConfigPolicyV2DocumentErrorKind::InvalidVerifierQuorum,
+    );
+
+    let mut document = base.clone();
+    document.v2_verifiers_per_auction = 8;
+    assert_error(
+        &document,
+        "v2_verifiers_per_auction",
+        ConfigPolicyV2DocumentErrorKind::InvalidVerifierCount,
+    );
+    document.v2_account_layout_version = 6;
+    assert_error(
+        &document,
+        "bundle_escrow_v2_layout_version",
+        ConfigPolicyV2DocumentErrorKind::InvalidAccountLayoutVersion,
+    );
+    document.bundle_escrow_v2_layout_version = 6;
+    assert_eq!(
+        ConfigPolicyV2::from_document(&document)
+            .unwrap()
+            .verifier_capacity(),
+        16
+    );
+
+    let mut document = base.clone();
+    document.tiers.remove("Small");
+    assert_error(
+        &document,
+        "tiers.Small",
+        ConfigPolicyV2DocumentErrorKind::MissingTier,
+    );
+
+    let mut document = base.clone();
+    document.tiers.get_mut("Eco").unwrap().bundle_duration = 0;
+    assert_error(
+        &document,
+        "tiers.Eco",
+        ConfigPolicyV2DocumentErrorKind::InvalidTierConfig,
+    );
+
+    let mut document = base;
+    let eco = document.tiers["Eco"];
+    document.tiers.insert("Huge".to_string(), eco);
+    assert_error(
+        &document,
+        "tiers.Huge",
+        ConfigPolicyV2DocumentErrorKind::UnknownTier,
+    );
+}
+
+#[test]
+fn unknown_fields_are_rejected() {
+    let toml = sample_policy()
+        .to_document()
+        .unwrap()
+        .to_toml_string()
+        .unwrap();
+    let err =
+        ConfigPolicyV2Document::from_toml_str(&format!("typo_field = 1\n{toml}")).unwrap_err();
+    assert!(matches!(
+        err.kind,
+        ConfigPolicyV2DocumentErrorKind::Parse(_)
+    ));
+
+    let toml = toml.replace("[tiers.Pro]\n", "[tiers.Pro]\ntypo_window_slots = 1\n");
+    let err = ConfigPolicyV2Document::from_toml_str(&toml).unwrap_err();
+    assert!(matches!(
+        err.kind,
+        ConfigPolicyV2DocumentErrorKind::Parse(_)
+    ));
+}
+
+#[test]
+fn plain_tier_config_serde_ignores_unknown_fields() {
+    let config = RequestTierConfigV2::production_default_for_tier(RequestTier::Pro);
+    let mut json = serde_json::to_value(config).unwrap();
+    json["future_field"] = 1.into();
+    assert_eq!(
+        serde_json::from_value::<RequestTierConfigV2>(json).unwrap(),
+        config
+    );
+}
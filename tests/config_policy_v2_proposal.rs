@@ -0,0 +1,160 @@
+use ambient_auction_api::{
+    error::AuctionError, ConfigPolicyV2, ConfigPolicyV2AuthorityKind, ConfigPolicyV2Flag,
+    ConfigPolicyV2Patch, ConfigPolicyV2Proposal, ConfigPolicyV2ProposalStatus, Pubkey,
+    RawConfigPolicyV2ProposalData,
+};
+
+fn test_pubkey(byte: u8) -> Pubkey {
+    [byte; 32].into()
+}
+
+/// A policy with admins 1, 2 and 3 and a 2-of-3 approval threshold.
+fn council_policy() -> ConfigPolicyV2 {
+    let mut policy = ConfigPolicyV2::production_default();
+    policy.admin_authorities[0] = test_pubkey(1);
+    for byte in [2, 3] {
+        policy
+            .apply(
+                &ConfigPolicyV2Patch::Authority {
+                    kind: ConfigPolicyV2AuthorityKind::ADMIN,
+                    index: byte - 1,
+                    authority: test_pubkey(byte),
+                },
+                &test_pubkey(1),
+            )
+            .unwrap();
+    }
+    policy
+        .apply(
+            &ConfigPolicyV2Patch::AdminApprovalThreshold(2),
+            &test_pubkey(1),
+        )
+        .unwrap();
+    policy
+}
+
+fn flags_patch() -> ConfigPolicyV2Patch {
+    ConfigPolicyV2Patch::Flags(ConfigPolicyV2Flag::AllowServicePageBackedFinalizePayout.mask())
+}
+
+fn propose(policy: &ConfigPolicyV2) -> ConfigPolicyV2Proposal {
+    RawConfigPolicyV2ProposalData::new(
+        test_pubkey(50),
+        policy,
+        test_pubkey(1),
+        7,
+        254,
+        &flags_patch(),
+        100,
+        200,
+    )
+    .unwrap()
+}
+
+#[test]
+fn threshold_blocks_single_admin_updates() {
+    let mut policy = council_policy();
+    assert_eq!(policy.required_admin_approvals(), 2);
+    assert_eq!(
+        policy.apply(&flags_patch(), &test_pubkey(1)),
+        Err(AuctionError::ConfigPolicyV2ProposalRequired)
+    );
+}
+
+#[test]
+fn proposal_executes_once_threshold_is_met() {
+    let mut policy = council_policy();
+    let mut proposal = propose(&policy);
+    assert_eq!(proposal.approval_count(&policy), 1);
+
+    assert_eq!(
+        proposal.execute(&mut policy, 150),
+        Err(AuctionError::ConfigPolicyV2ProposalThresholdNotMet)
+    );
+    assert_eq!(
+        proposal.approve(&policy, &test_pubkey(1), 150),
+        Err(AuctionError::ConfigPolicyV2ProposalAlreadyApproved)
+    );
+    assert_eq!(
+        proposal.approve(&policy, &test_pubkey(9), 150),
+        Err(AuctionError::UnauthorizedConfigPolicyAuthority)
+    );
+
+    proposal.approve(&policy, &test_pubkey(3), 150).unwrap();
+    proposal.execute(&mut policy, 151).unwrap();
+    assert_eq!(proposal.status, ConfigPolicyV2ProposalStatus::EXECUTED);
+    assert!(policy
+        .policy_flags
+        .contains(ConfigPolicyV2Flag::AllowServicePageBackedFinalizePayout));
+
+    assert_eq!(
+        proposal.execute(&mut policy, 152),
+        Err(AuctionError::ConfigPolicyV2ProposalNotPending)
+    );
+}
+
+#[test]
+fn proposal_rejects_expired_canceled_and_replaced_admin_approvals() {
+    let mut policy = council_policy();
+
+    let mut proposal = propose(&policy);
+    assert_eq!(
+        proposal.approve(&policy, &test_pubkey(2), 200),
+        Err(AuctionError::ConfigPolicyV2ProposalExpired)
+    );
+
+    proposal.approve(&policy, &test_pubkey(2), 150).unwrap();
+    // Replacing admin 2 withdraws their approval.
+    let mut replacement = propose(&policy);
+    replacement.patch = ConfigPolicyV2Patch::Authority {
+        kind: ConfigPolicyV2AuthorityKind::ADMIN,
+        index: 1,
+        authority: test_pubkey(4),
+    }
+    .to_args();
+    replacement.approve(&policy, &test_pubkey(3), 150).unwrap();
+    replacement.execute(&mut policy, 150).unwrap();
+    assert_eq!(proposal.approval_count(&policy), 1);
+    assert_eq!(
+        proposal.execute(&mut policy, 150),
+        Err(AuctionError::ConfigPolicyV2ProposalThresholdNotMet)
+    );
+
+    assert_eq!(
+        proposal.cancel(&policy, &test_pubkey(9)),
+        Err(AuctionError::UnauthorizedConfigPolicyAuthority)
+    );
+    proposal.cancel(&policy, &test_pubkey(3)).unwrap();
+    assert_eq!(proposal.status, ConfigPolicyV2ProposalStatus::CANCELED);
+}
+
+#[test]
+fn threshold_must_stay_reachable() {
+    let mut policy = council_policy();
+    let mut proposal = propose(&policy);
+    proposal.patch = ConfigPolicyV2Patch::AdminApprovalThreshold(4).to_args();
+    proposal.approve(&policy, &test_pubkey(2), 150).unwrap();
+    assert_eq!(
+        proposal.execute(&mut policy, 150),
+        Err(AuctionError::InvalidAdminApprovalThreshold)
+    );
+    assert_eq!(proposal.status, ConfigPolicyV2ProposalStatus::PENDING);
+
+    policy.admin_approval_threshold = 3;
+    assert_eq!(
+        policy.remove_authority(ConfigPolicyV2AuthorityKind::ADMIN, &test_pubkey(3)),
+        Err(AuctionError::InvalidAdminApprovalThreshold)
+    );
+}
+
+#[test]
+fn proposal_account_bytes_round_trip() {
+    let proposal = propose(&council_policy());
+    let mut bytes = vec![0; RawConfigPolicyV2ProposalData::LEN];
+    assert!(proposal.write_bytes(&mut bytes));
+
+    let parsed = RawConfigPolicyV2ProposalData::from_bytes(&bytes).unwrap();
+    assert_eq!(parsed.as_raw(), &proposal);
+    assert_eq!(parsed.decoded_patch(), Ok(flags_patch()));
+    assert!(RawConfigPolicyV2ProposalData::from_bytes(&bytes[1..]).is_none());
+}
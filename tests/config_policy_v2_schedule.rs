@@ -0,0 +1,265 @@
+use ambient_auction_api::{
+    error::AuctionError, AccountLayoutVersion, BundleEscrowV2, BundleEscrowV2PolicySnapshot,
+    ConfigPolicyV2, ConfigPolicyV2Patch, ConfigPolicyV2Proposal, ConfigPolicyV2ProposalStatus,
+    ConfigPolicyV2Schedule, InitConfigPolicyV2ScheduleArgs, Pubkey, RawBundleEscrowV2Data,
+    RawConfigPolicyV2ScheduleData, RequestTier,
+};
+
+fn test_pubkey(byte: u8) -> Pubkey {
+    [byte; 32].into()
+}
+
+fn admin_policy() -> ConfigPolicyV2 {
+    let mut policy = ConfigPolicyV2::production_default();
+    policy.admin_authorities[0] = test_pubkey(1);
+    policy
+}
+
+fn quorum_patch(quorum: u8) -> ConfigPolicyV2Patch {
+    ConfigPolicyV2Patch::VerifierSettings {
+        v2_verifiers_per_auction: 3,
+        v2_verifier_quorum: quorum,
+    }
+}
+
+fn schedule_with_delay(delay: u64) -> ConfigPolicyV2Schedule {
+    RawConfigPolicyV2ScheduleData::new(test_pubkey(50), 255, delay)
+}
+
+#[test]
+fn schedules_reject_a_zero_activation_delay() {
+    let args = InitConfigPolicyV2ScheduleArgs {
+        schedule_lamports: 1,
+        min_activation_delay_slots: 0,
+    };
+    assert_eq!(args.validate(), Err(AuctionError::InvalidActivationDelay));
+    assert_eq!(
+        InitConfigPolicyV2ScheduleArgs {
+            min_activation_delay_slots: 1,
+            ..args
+        }
+        .validate(),
+        Ok(())
+    );
+
+    let policy = admin_policy();
+    let mut schedule = schedule_with_delay(0);
+    assert_eq!(
+        schedule.schedule(&policy, &test_pubkey(1), &quorum_patch(3), 100, 100),
+        Err(AuctionError::InvalidActivationDelay)
+    );
+    assert!(schedule.pending_patches().is_empty());
+}
+
+#[test]
+fn effective_policy_resolves_pending_patches_by_activation_slot() {
+    let policy = admin_policy();
+    let mut schedule = schedule_with_delay(10);
+    let admin = test_pubkey(1);
+
+    assert_eq!(
+        schedule.schedule(&policy, &admin, &quorum_patch(3), 100, 105),
+        Err(AuctionError::ActivationSlotTooEarly)
+    );
+    assert_eq!(
+        schedule.schedule(&policy, &test_pubkey(2), &quorum_patch(3), 100, 200),
+        Err(AuctionError::UnauthorizedConfigPolicyAuthority)
+    );
+
+    // Scheduled out of order; entries stay sorted by activation slot.
+    assert_eq!(
+        schedule.schedule(&policy, &admin, &quorum_patch(1), 100, 300),
+        Ok(0)
+    );
+    assert_eq!(
+        schedule.schedule(&policy, &admin, &quorum_patch(3), 100, 200),
+        Ok(1)
+    );
+    assert_eq!(
+        schedule
+            .pending_patches()
+            .iter()
+            .map(|entry| entry.schedule_id)
+            .collect::<Vec<_>>(),
+        vec![1, 0]
+    );
+
+    assert_eq!(policy.effective_policy_at(&schedule, 199).unwrap(), policy);
+    assert_eq!(
+        policy
+            .effective_policy_at(&schedule, 200)
+            .unwrap()
+            .v2_verifier_quorum,
+        3
+    );
+    assert_eq!(
+        policy
+            .effective_policy_at(&schedule, 300)
+            .unwrap()
+            .v2_verifier_quorum,
+        1
+    );
+}
+
+#[test]
+fn activate_due_applies_and_drains_due_patches() {
+    let mut policy = admin_policy();
+    let mut schedule = schedule_with_delay(1);
+    let admin = test_pubkey(1);
+    let original_tier_config = *policy.tier_config(RequestTier::Pro);
+    let mut tier_config = original_tier_config;
+    tier_config.claim_window_slots = 77;
+
+    schedule
+        .schedule(&policy, &admin, &quorum_patch(3), 100, 150)
+        .unwrap();
+    schedule
+        .schedule(
+            &policy,
+            &admin,
+            &ConfigPolicyV2Patch::TierConfig {
+                tier: RequestTier::Pro,
+                tier_config,
+            },
+            100,
+            400,
+        )
+        .unwrap();
+
+    assert_eq!(schedule.activate_due(&mut policy, 149), Ok(0));
+    assert_eq!(schedule.activate_due(&mut policy, 150), Ok(1));
+    assert_eq!(policy.v2_verifier_quorum, 3);
+    assert_eq!(schedule.entry_count, 1);
+    assert_eq!(schedule.pending_patches()[0].activation_slot, 400);
+
+    assert_eq!(
+        schedule.cancel(&policy, &admin, 9),
+        Err(AuctionError::ScheduledConfigPolicyV2PatchNotFound)
+    );
+    schedule.cancel(&policy, &admin, 1).unwrap();
+    assert!(schedule.pending_patches().is_empty());
+    assert_eq!(policy.tier_config(RequestTier::Pro), &original_tier_config);
+}
+
+#[test]
+fn schedule_rejects_patches_invalid_at_activation() {
+    let policy = admin_policy();
+    let mut schedule = schedule_with_delay(1);
+    let admin = test_pubkey(1);
+
+    schedule
+        .schedule(
+            &policy,
+            &admin,
+            &ConfigPolicyV2Patch::VerifierSettings {
+                v2_verifiers_per_auction: 1,
+                v2_verifier_quorum: 1,
+            },
+            0,
+            10,
+        )
+        .unwrap();
+    assert_eq!(
+        schedule.schedule(&policy, &admin, &quorum_patch(0), 0, 20),
+        Err(AuctionError::InvalidVerifierQuorum)
+    );
+}
+
+#[test]
+fn schedule_account_bytes_round_trip() {
+    let mut schedule = schedule_with_delay(5);
+    schedule
+        .schedule(&admin_policy(), &test_pubkey(1), &quorum_patch(3), 0, 5)
+        .unwrap();
+
+    let mut bytes = vec![0; RawConfigPolicyV2ScheduleData::LEN];
+    assert!(schedule.write_bytes(&mut bytes));
+    let parsed = RawConfigPolicyV2ScheduleData::from_bytes(&bytes).unwrap();
+    assert_eq!(parsed.as_raw(), &schedule);
+    assert_eq!(
+        parsed.pending_patches()[0].decoded_patch(),
+        Ok(quorum_patch(3))
+    );
+}
+
+#[test]
+fn v2_escrow_keeps_policy_snapshot_across_policy_updates() {
+    let mut policy = admin_policy();
+    let snapshot = BundleEscrowV2PolicySnapshot::capture(&policy, RequestTier::Standard);
+
+    let mut v2_bytes = vec![0; RawBundleEscrowV2Data::account_len(AccountLayoutVersion::V2)];
+    assert!(BundleEscrowV2::default().write_v2_bytes(&mut v2_bytes));
+    {
+        let mut escrow = RawBundleEscrowV2Data::from_bytes_mut(&mut v2_bytes).unwrap();
+        assert_eq!(escrow.policy_snapshot(), None);
+        assert!(escrow.set_policy_snapshot(&snapshot));
+    }
+
+    assert_eq!(
+        policy.apply(&quorum_patch(1), &test_pubkey(1)),
+        Err(AuctionError::ConfigPolicyV2PatchMustBeScheduled)
+    );
+    let mut schedule = schedule_with_delay(10);
+    schedule
+        .schedule(&policy, &test_pubkey(1), &quorum_patch(1), 100, 110)
+        .unwrap();
+    assert_eq!(schedule.activate_due(&mut policy, 110), Ok(1));
+    assert_eq!(policy.v2_verifier_quorum, 1);
+    let escrow = RawBundleEscrowV2Data::from_bytes(&v2_bytes).unwrap();
+    assert_eq!(escrow.policy_snapshot(), Some(snapshot));
+    assert_eq!(escrow.policy_snapshot().unwrap().v2_verifier_quorum, 2);
+
+    let mut v1_bytes = vec![0; RawBundleEscrowV2Data::account_len(AccountLayoutVersion::V1)];
+    assert!(BundleEscrowV2::default().write_v1_bytes(&mut v1_bytes));
+    let mut escrow = RawBundleEscrowV2Data::from_bytes_mut(&mut v1_bytes).unwrap();
+    assert!(!escrow.set_policy_snapshot(&snapshot));
+    assert_eq!(escrow.policy_snapshot(), None);
+}
+
+#[test]
+fn multisig_policies_schedule_through_approved_proposals() {
+    let mut policy = admin_policy();
+    policy.admin_authorities[1] = test_pubkey(2);
+    policy.admin_approval_threshold = 2;
+    let mut schedule = schedule_with_delay(10);
+
+    assert_eq!(
+        schedule.schedule(&policy, &test_pubkey(1), &quorum_patch(1), 100, 200),
+        Err(AuctionError::ConfigPolicyV2ProposalRequired)
+    );
+
+    let mut proposal = ConfigPolicyV2Proposal::new(
+        test_pubkey(60),
+        &policy,
+        test_pubkey(1),
+        7,
+        255,
+        &quorum_patch(1),
+        100,
+        300,
+    )
+    .unwrap();
+    assert_eq!(
+        proposal.execute_scheduled(&policy, &mut schedule, 101),
+        Err(AuctionError::ConfigPolicyV2ProposalThresholdNotMet)
+    );
+    proposal.approve(&policy, &test_pubkey(2), 101).unwrap();
+    assert_eq!(
+        proposal.clone().execute(&mut policy, 102),
+        Err(AuctionError::ConfigPolicyV2PatchMustBeScheduled)
+    );
+    assert_eq!(
+        proposal.execute_scheduled(&policy, &mut schedule, 102),
+        Ok(0)
+    );
+    assert_eq!(proposal.status, ConfigPolicyV2ProposalStatus::EXECUTED);
+    assert_eq!(schedule.pending_patches()[0].activation_slot, 112);
+
+    // A single admin can still veto a scheduled patch.
+    assert_eq!(
+        schedule.cancel(&policy, &test_pubkey(3), 0),
+        Err(AuctionError::UnauthorizedConfigPolicyAuthority)
+    );
+    assert!(schedule.cancel(&policy, &test_pubkey(2), 0).is_ok());
+    assert!(schedule.pending_patches().is_empty());
+}
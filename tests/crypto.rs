@@ -0,0 +1,97 @@
+#![cfg(feature = "crypto")]
+
+use ambient_auction_api::{
+    check_commitment_ivs, error::AuctionError, x25519_public_key, JobRequest, SubmitJobOutputArgs,
+    VerificationState,
+};
+
+// RFC 7748, section 6.1.
+const ALICE_SECRET: &str = "77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a";
+const ALICE_PUBLIC: &str = "8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a";
+const BOB_SECRET: &str = "5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb";
+const BOB_PUBLIC: &str = "de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f";
+
+fn bytes<const N: usize>(hex: &str) -> [u8; N] {
+    let mut out = [0; N];
+    for (index, byte) in out.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[index * 2..index * 2 + 2], 16).unwrap();
+    }
+    out
+}
+
+fn test_iv() -> [u8; 16] {
+    std::array::from_fn(|index| index as u8 + 1)
+}
+
+#[test]
+fn public_keys_match_the_x25519_test_vectors() {
+    assert_eq!(x25519_public_key(&bytes(ALICE_SECRET)), bytes(ALICE_PUBLIC));
+    assert_eq!(x25519_public_key(&bytes(BOB_SECRET)), bytes(BOB_PUBLIC));
+}
+
+#[test]
+fn output_hash_and_merkle_root_cannot_share_an_iv() {
+    let mut other_iv = test_iv();
+    other_iv[0] ^= 1;
+    assert_eq!(check_commitment_ivs(&test_iv(), &other_iv), Ok(()));
+    let mut two_apart = test_iv();
+    two_apart[15] += 2;
+    assert_eq!(check_commitment_ivs(&test_iv(), &two_apart), Ok(()));
+    assert_eq!(check_commitment_ivs(&test_iv(), &[0; 16]), Ok(()));
+    assert_eq!(check_commitment_ivs(&[0; 16], &[0; 16]), Ok(()));
+    assert_eq!(
+        check_commitment_ivs(&test_iv(), &test_iv()),
+        Err(AuctionError::InvalidEncryptionIv)
+    );
+}
+
+#[test]
+fn output_hash_and_merkle_root_ivs_cannot_share_a_counter_block() {
+    let mut next_iv = test_iv();
+    next_iv[15] += 1;
+    assert_eq!(
+        check_commitment_ivs(&test_iv(), &next_iv),
+        Err(AuctionError::InvalidEncryptionIv)
+    );
+    assert_eq!(
+        check_commitment_ivs(&next_iv, &test_iv()),
+        Err(AuctionError::InvalidEncryptionIv)
+    );
+
+    // `u128::MAX` takes the blocks `u128::MAX` and `0`, so `1` is 2 apart across the wrap.
+    let max = u128::MAX.to_be_bytes();
+    let one = 1u128.to_be_bytes();
+    assert_eq!(check_commitment_ivs(&max, &one), Ok(()));
+    assert_eq!(check_commitment_ivs(&one, &max), Ok(()));
+    assert_eq!(
+        check_commitment_ivs(&max, &(u128::MAX - 1).to_be_bytes()),
+        Err(AuctionError::InvalidEncryptionIv)
+    );
+    assert_eq!(
+        check_commitment_ivs(&(u128::MAX - 1).to_be_bytes(), &max),
+        Err(AuctionError::InvalidEncryptionIv)
+    );
+    assert_eq!(check_commitment_ivs(&max, &[0; 16]), Ok(()));
+}
+
+#[test]
+fn is_encrypted_follows_the_stored_ivs() {
+    let mut verification = VerificationState::default();
+    assert!(!verification.is_output_hash_encrypted());
+    verification.merkle_root_iv = test_iv();
+    assert!(verification.is_merkle_root_encrypted());
+    assert!(!verification.is_output_hash_encrypted());
+
+    let request = JobRequest {
+        input_hash_iv: test_iv(),
+        ..Default::default()
+    };
+    assert!(request.is_input_hash_encrypted());
+
+    let args = SubmitJobOutputArgs {
+        output_hash_iv: test_iv(),
+        ..Default::default()
+    };
+    assert!(args.is_output_hash_encrypted());
+    assert!(!args.is_merkle_root_encrypted());
+}
@@ -0,0 +1,444 @@
+use ambient_auction_api::{
+    error::AuctionError, metadata_payload_offset, parse_metadata_layout, read_data_account_chain,
+    AccountDiscriminator, AccountHeaderV1, AccountLayoutVersion, AppendDataArgs, AppendDataV1Args,
+    DataAccountChainReader, DataAccountReader, DataAccountWriter, Metadata, MetadataV1,
+    ParsedAccountLayout, Pubkey, UploadPlan, APPEND_DATA_MAX_CHUNK_LEN,
+    APPEND_DATA_V1_MAX_CHUNK_LEN,
+};
+use bytemuck::Zeroable;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::num::NonZeroU64;
+
+fn payload(len: usize) -> Vec<u8> {
+    (0..len).map(|index| (index % 251) as u8).collect()
+}
+
+/// Applies `instructions` to `account` the way `AppendData` does.
+fn append(account: &mut [u8], instructions: &[(AppendDataArgs, &[u8])], authority: Pubkey) {
+    for (args, chunk) in instructions {
+        let mut metadata: Metadata = bytemuck::pod_read_unaligned(&account[..Metadata::LEN]);
+        metadata.authority = authority;
+        metadata.seed = args.seed;
+        metadata.seed_len = args.seed_len;
+        metadata.decompressed_len = args.decompressed_data_length;
+        let offset = args.offset as usize;
+        account[offset..offset + chunk.len()].copy_from_slice(chunk);
+        metadata.payload_len = (offset + chunk.len() - Metadata::LEN) as u64;
+        account[..Metadata::LEN].copy_from_slice(bytemuck::bytes_of(&metadata));
+    }
+}
+
+/// Applies `instructions` to `account` the way `AppendDataV1` does.
+fn append_v1(account: &mut [u8], instructions: &[(AppendDataV1Args, &[u8])], authority: Pubkey) {
+    for (args, chunk) in instructions {
+        let mut metadata: MetadataV1 = bytemuck::pod_read_unaligned(&account[..MetadataV1::LEN]);
+        metadata.header = AccountHeaderV1::new(AccountDiscriminator::Metadata);
+        metadata.authority = authority;
+        metadata.seed = args.seed;
+        metadata.seed_len = args.seed_len;
+        metadata.decompressed_len = args.decompressed_data_length;
+        metadata.next_data_account = args.next_data_account;
+        let offset = args.offset as usize;
+        account[offset..offset + chunk.len()].copy_from_slice(chunk);
+        metadata.payload_len = (offset + chunk.len() - MetadataV1::LEN) as u64;
+        account[..MetadataV1::LEN].copy_from_slice(bytemuck::bytes_of(&metadata));
+    }
+}
+
+#[test]
+fn upload_plan_splits_the_payload_after_the_metadata() {
+    let data = payload(APPEND_DATA_MAX_CHUNK_LEN * 2 + 17);
+    let plan = UploadPlan::new(&data, b"prompt-1", None).unwrap();
+    assert_eq!(plan.data_account_len(), Metadata::LEN + data.len());
+
+    let instructions = plan.instructions();
+    assert_eq!(instructions.len(), 3);
+    assert_eq!(instructions[0].0.offset, Metadata::LEN as u64);
+    assert_eq!(
+        instructions[2].0.offset,
+        (Metadata::LEN + APPEND_DATA_MAX_CHUNK_LEN * 2) as u64
+    );
+    assert_eq!(instructions[2].1.len(), 17);
+    assert!(instructions.iter().all(|(args, _)| args.seed_len == 8
+        && &args.seed[..8] == b"prompt-1"
+        && args.decompressed_data_length.is_none()));
+
+    let mut account = vec![0; plan.data_account_len()];
+    append(&mut account, &instructions, Pubkey::default());
+    assert_eq!(&account[Metadata::LEN..], &data[..]);
+}
+
+#[test]
+fn upload_plan_resumes_a_partially_filled_account() {
+    let data = payload(1_000);
+    let compression = NonZeroU64::new(4_000);
+    let plan = UploadPlan::new_with_chunk_len(&data, b"seed", compression, 300).unwrap();
+    let instructions = plan.instructions();
+    assert_eq!(instructions.len(), 4);
+
+    let mut account = vec![0; plan.data_account_len()];
+    assert_eq!(plan.resume(&account), Ok(instructions.clone()));
+
+    append(&mut account, &instructions[..2], [9; 32].into());
+    let remaining = plan.resume(&account).unwrap();
+    assert_eq!(remaining, instructions[2..]);
+    append(&mut account, &remaining, [9; 32].into());
+    assert_eq!(plan.resume(&account), Ok(Vec::new()));
+    assert_eq!(&account[Metadata::LEN..], &data[..]);
+
+    let other_seed = UploadPlan::new_with_chunk_len(&data, b"other", compression, 300).unwrap();
+    assert_eq!(
+        other_seed.resume(&account),
+        Err(AuctionError::InvalidMetadata)
+    );
+    let mut changed = data.clone();
+    changed[10] ^= 1;
+    let changed_plan = UploadPlan::new_with_chunk_len(&changed, b"seed", compression, 300).unwrap();
+    assert_eq!(
+        changed_plan.resume(&account),
+        Err(AuctionError::InvalidMetadata)
+    );
+    assert_eq!(
+        plan.resume(&account[..Metadata::LEN - 1]),
+        Err(AuctionError::InvalidMetadata)
+    );
+}
+
+#[test]
+fn upload_plan_rejects_bad_inputs() {
+    assert_eq!(
+        UploadPlan::new(&[], b"seed", None),
+        Err(AuctionError::InvalidDataUpload)
+    );
+    assert_eq!(
+        UploadPlan::new(&[1], &[0; 33], None),
+        Err(AuctionError::InvalidDataUpload)
+    );
+    assert_eq!(
+        UploadPlan::new_with_chunk_len(&[1], b"seed", None, 0),
+        Err(AuctionError::InvalidDataUpload)
+    );
+
+    let mut account = vec![0; Metadata::LEN + 4];
+    let metadata = Metadata {
+        payload_len: 5,
+        ..Metadata::zeroed()
+    };
+    account[..Metadata::LEN].copy_from_slice(bytemuck::bytes_of(&metadata));
+    assert_eq!(
+        UploadPlan::new(&[1, 2, 3, 4], b"seed", None)
+            .unwrap()
+            .resume(&account),
+        Err(AuctionError::InvalidMetadata)
+    );
+}
+
+#[test]
+fn reader_returns_uncompressed_payload_borrowed() {
+    let authority = Pubkey::from([7; 32]);
+    let data = payload(500);
+    let writer = DataAccountWriter::uncompressed(&data);
+    let mut account = writer.account_data(authority, b"seed").unwrap();
+    account.extend_from_slice(&[0xaa; 16]);
+
+    let reader = DataAccountReader::new(&account, b"seed").unwrap();
+    assert!(!reader.is_compressed());
+    assert_eq!(reader.metadata().authority, authority);
+    assert_eq!(reader.stored_payload(), data.as_slice());
+    assert!(matches!(reader.payload(), Ok(Cow::Borrowed(bytes)) if bytes == data.as_slice()));
+}
+
+#[test]
+fn reader_validates_seed_and_payload_len() {
+    let account = DataAccountWriter::uncompressed(&payload(10))
+        .account_data(Pubkey::from([1; 32]), b"seed")
+        .unwrap();
+
+    assert_eq!(
+        DataAccountReader::new(&account, b"other"),
+        Err(AuctionError::InvalidMetadata)
+    );
+    assert_eq!(
+        DataAccountReader::new(&account[..account.len() - 1], b"seed"),
+        Err(AuctionError::InvalidMetadata)
+    );
+    assert_eq!(
+        DataAccountReader::new(&account[..Metadata::LEN - 1], b"seed"),
+        Err(AuctionError::InvalidMetadata)
+    );
+}
+
+#[test]
+fn reader_rejects_unknown_and_lz4_frames() {
+    let writer = DataAccountWriter::uncompressed(&[0x04, 0x22, 0x4d, 0x18, 0, 0]);
+    let mut metadata = writer.metadata(Pubkey::from([1; 32]), b"seed").unwrap();
+    metadata.decompressed_len = NonZeroU64::new(64);
+    let account = [bytemuck::bytes_of(&metadata), writer.stored_payload()].concat();
+    assert_eq!(
+        DataAccountReader::new(&account, b"seed").unwrap().payload(),
+        Err(AuctionError::CompressionNotSupported)
+    );
+
+    let writer = DataAccountWriter::uncompressed(&payload(6));
+    let mut metadata = writer.metadata(Pubkey::from([1; 32]), b"seed").unwrap();
+    metadata.decompressed_len = NonZeroU64::new(6);
+    let account = [bytemuck::bytes_of(&metadata), writer.stored_payload()].concat();
+    assert_eq!(
+        DataAccountReader::new(&account, b"seed").unwrap().payload(),
+        Err(AuctionError::InvalidCompressedPayload)
+    );
+}
+
+#[cfg(feature = "compression")]
+#[test]
+fn compressed_payload_round_trips_through_upload() {
+    let authority = Pubkey::from([3; 32]);
+    let data = vec![42; 4096];
+    let writer = DataAccountWriter::compressed(&data).unwrap();
+    assert_eq!(writer.decompressed_len(), NonZeroU64::new(4096));
+    assert!(writer.stored_payload().len() < data.len());
+
+    let plan = writer.upload_plan(b"seed").unwrap();
+    let mut account = vec![0; plan.data_account_len()];
+    append(&mut account, &plan.instructions(), authority);
+    assert_eq!(account, writer.account_data(authority, b"seed").unwrap());
+
+    let reader = DataAccountReader::new(&account, b"seed").unwrap();
+    assert!(reader.is_compressed());
+    assert_eq!(reader.payload().unwrap().as_ref(), data.as_slice());
+}
+
+#[cfg(feature = "compression")]
+#[test]
+fn compressed_payload_must_match_decompressed_len() {
+    let writer = DataAccountWriter::compressed(&payload(300)).unwrap();
+    for decompressed_len in [299, 301] {
+        let mut metadata = writer.metadata(Pubkey::from([1; 32]), b"seed").unwrap();
+        metadata.decompressed_len = NonZeroU64::new(decompressed_len);
+        let account = [bytemuck::bytes_of(&metadata), writer.stored_payload()].concat();
+        assert_eq!(
+            DataAccountReader::new(&account, b"seed").unwrap().payload(),
+            Err(AuctionError::InvalidCompressedPayload)
+        );
+    }
+    assert_eq!(
+        DataAccountWriter::compressed(&[]),
+        Err(AuctionError::InvalidDataUpload)
+    );
+}
+
+#[cfg(not(feature = "compression"))]
+#[test]
+fn zstd_payload_needs_compression_feature() {
+    let writer = DataAccountWriter::uncompressed(&[0x28, 0xb5, 0x2f, 0xfd, 0, 0]);
+    let mut metadata = writer.metadata(Pubkey::from([1; 32]), b"seed").unwrap();
+    metadata.decompressed_len = NonZeroU64::new(16);
+    let account = [bytemuck::bytes_of(&metadata), writer.stored_payload()].concat();
+    assert_eq!(
+        DataAccountReader::new(&account, b"seed").unwrap().payload(),
+        Err(AuctionError::CompressionNotSupported)
+    );
+}
+
+fn linked_accounts(
+    writer: &DataAccountWriter,
+    authority: Pubkey,
+    max_segment_len: usize,
+) -> HashMap<[u8; 32], Vec<u8>> {
+    let count = writer.linked_account_count(max_segment_len);
+    let seeds: Vec<Vec<u8>> = (0..count)
+        .map(|index| format!("part-{index}").into_bytes())
+        .collect();
+    let accounts: Vec<(Pubkey, &[u8])> = seeds
+        .iter()
+        .enumerate()
+        .map(|(index, seed)| (Pubkey::from([index as u8 + 1; 32]), seed.as_slice()))
+        .collect();
+
+    writer
+        .linked_accounts(&accounts, max_segment_len)
+        .unwrap()
+        .into_iter()
+        .map(|linked| {
+            let plan = linked.upload_plan().unwrap();
+            let mut account = vec![0; plan.data_account_len()];
+            let instructions = plan.instructions();
+            assert!(instructions
+                .iter()
+                .all(|(_, chunk)| chunk.len() <= APPEND_DATA_V1_MAX_CHUNK_LEN));
+            append_v1(&mut account, &instructions, authority);
+            assert_eq!(account, linked.account_data(authority).unwrap());
+            (linked.key().inner(), account)
+        })
+        .collect()
+}
+
+#[test]
+fn linked_accounts_round_trip_through_append_data() {
+    let authority = Pubkey::from([9; 32]);
+    let data = payload(2500);
+    let writer = DataAccountWriter::uncompressed(&data);
+    assert_eq!(writer.linked_account_count(1000), 3);
+    let accounts = linked_accounts(&writer, authority, 1000);
+    let head = Pubkey::from([1; 32]);
+
+    let head_reader = DataAccountReader::new(&accounts[&head.inner()], b"part-0").unwrap();
+    assert_eq!(
+        head_reader.layout(),
+        ParsedAccountLayout::new(AccountDiscriminator::Metadata, AccountLayoutVersion::V1)
+    );
+    assert_eq!(head_reader.next_data_account(), Some(Pubkey::from([2; 32])));
+    assert_eq!(head_reader.metadata().seed_len, 6);
+    assert_eq!(head_reader.metadata().payload_len, 1000);
+    assert_eq!(head_reader.stored_payload(), &data[..1000]);
+    assert_eq!(
+        head_reader.payload(),
+        Err(AuctionError::InvalidDataAccountChain)
+    );
+
+    let mut chain = DataAccountChainReader::new(head, &accounts[&head.inner()]).unwrap();
+    assert_eq!(chain.payload(), Err(AuctionError::InvalidDataAccountChain));
+    while let Some(next) = chain.next_data_account() {
+        chain.push(next, &accounts[&next.inner()]).unwrap();
+    }
+    assert_eq!(chain.accounts().len(), 3);
+    assert_eq!(chain.payload().unwrap().as_ref(), data.as_slice());
+    assert_eq!(
+        read_data_account_chain(head, |key| accounts.get(&key.inner())),
+        Ok(data)
+    );
+}
+
+#[test]
+fn legacy_accounts_read_as_single_segment_chains() {
+    let authority = Pubkey::from([9; 32]);
+    let data = payload(64);
+    let account = DataAccountWriter::uncompressed(&data)
+        .account_data(authority, b"seed")
+        .unwrap();
+    assert_eq!(
+        parse_metadata_layout(&account),
+        Some(ParsedAccountLayout::legacy_v0(
+            AccountDiscriminator::Metadata
+        ))
+    );
+    assert_eq!(
+        read_data_account_chain(authority, |_| Some(&account)),
+        Ok(data)
+    );
+}
+
+#[test]
+fn legacy_payload_starting_like_a_header_stays_legacy() {
+    let authority = Pubkey::from([9; 32]);
+    let data = [
+        bytemuck::bytes_of(&AccountHeaderV1::new(AccountDiscriminator::Metadata)),
+        &payload(8),
+    ]
+    .concat();
+    let account = DataAccountWriter::uncompressed(&data)
+        .account_data(authority, b"seed")
+        .unwrap();
+
+    let reader = DataAccountReader::new(&account, b"seed").unwrap();
+    assert!(reader.layout().is_legacy());
+    assert_eq!(reader.next_data_account(), None);
+    assert_eq!(reader.metadata().seed_len, 4);
+    assert_eq!(reader.stored_payload(), data.as_slice());
+}
+
+#[test]
+fn metadata_layout_comes_from_the_account_header() {
+    let writer = DataAccountWriter::uncompressed(&payload(20));
+    let accounts = linked_accounts(&writer, Pubkey::from([9; 32]), 10);
+    let mut account = accounts[&[1; 32]].clone();
+    let v1 = ParsedAccountLayout::new(AccountDiscriminator::Metadata, AccountLayoutVersion::V1);
+    assert_eq!(parse_metadata_layout(&account), Some(v1));
+    assert_eq!(parse_metadata_layout(&account[..MetadataV1::LEN - 1]), None);
+    assert_eq!(
+        metadata_payload_offset(AccountLayoutVersion::V1),
+        Some(MetadataV1::LEN)
+    );
+    assert_eq!(metadata_payload_offset(AccountLayoutVersion::V2), None);
+
+    account[1] = AccountLayoutVersion::V2 as u8;
+    assert_eq!(parse_metadata_layout(&account), None);
+    assert_eq!(
+        DataAccountReader::from_account_data(&account),
+        Err(AuctionError::InvalidMetadata)
+    );
+}
+
+#[test]
+fn chain_reader_rejects_cycles_and_authority_mismatches() {
+    let authority = Pubkey::from([9; 32]);
+    let head = Pubkey::from([1; 32]);
+    let second = Pubkey::from([2; 32]);
+    let writer = DataAccountWriter::uncompressed(&payload(20));
+    let mut accounts = linked_accounts(&writer, authority, 10);
+
+    let mut other_authority = accounts[&second.inner()].clone();
+    other_authority[AccountHeaderV1::LEN..AccountHeaderV1::LEN + 32].fill(4);
+    let mut chain = DataAccountChainReader::new(head, &accounts[&head.inner()]).unwrap();
+    assert_eq!(
+        chain.push(second, &other_authority),
+        Err(AuctionError::DataAccountAuthorityMismatch)
+    );
+    assert_eq!(
+        chain.push(head, &accounts[&second.inner()]),
+        Err(AuctionError::InvalidDataAccountChain)
+    );
+
+    let second_account = accounts.get_mut(&second.inner()).unwrap();
+    let mut metadata: MetadataV1 = bytemuck::pod_read_unaligned(&second_account[..MetadataV1::LEN]);
+    metadata.next_data_account = Some(head).into();
+    second_account[..MetadataV1::LEN].copy_from_slice(bytemuck::bytes_of(&metadata));
+    assert_eq!(
+        read_data_account_chain(head, |key| accounts.get(&key.inner())),
+        Err(AuctionError::DataAccountChainCycle)
+    );
+    accounts.remove(&second.inner());
+    assert_eq!(
+        read_data_account_chain(head, |key| accounts.get(&key.inner())),
+        Err(AuctionError::InvalidDataAccountChain)
+    );
+}
+
+#[test]
+fn linked_accounts_reject_bad_account_lists() {
+    let writer = DataAccountWriter::uncompressed(&payload(20));
+    let key = Pubkey::from([1; 32]);
+    assert_eq!(
+        writer.linked_accounts(&[(key, b"a")], 10),
+        Err(AuctionError::InvalidDataUpload)
+    );
+    assert_eq!(
+        writer.linked_accounts(&[(key, b"a"), (key, b"b")], 10),
+        Err(AuctionError::DataAccountChainCycle)
+    );
+    assert_eq!(
+        writer.linked_accounts(&[], 0),
+        Err(AuctionError::InvalidDataUpload)
+    );
+}
+
+#[cfg(feature = "compression")]
+#[test]
+fn compressed_payload_spans_linked_accounts() {
+    let authority = Pubkey::from([9; 32]);
+    let data: Vec<u8> = (0..20_000u32)
+        .flat_map(|index| (index % 97).to_le_bytes())
+        .collect();
+    let writer = DataAccountWriter::compressed(&data).unwrap();
+    let max_segment_len = writer.stored_payload().len() / 3 + 1;
+    let accounts = linked_accounts(&writer, authority, max_segment_len);
+    assert_eq!(accounts.len(), 3);
+
+    let second = DataAccountReader::from_account_data(&accounts[&[2; 32]]).unwrap();
+    assert_eq!(second.metadata().decompressed_len, None);
+    assert_eq!(
+        read_data_account_chain(Pubkey::from([1; 32]), |key| accounts.get(&key.inner())),
+        Ok(data)
+    );
+}
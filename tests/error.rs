@@ -33,6 +33,59 @@ fn new_error_codes_decode_strictly() {
         (73, AuctionError::InvalidVerificationVerdict),
         (74, AuctionError::InvalidPostedResultV2),
         (75, AuctionError::InvalidVerifierPagesSummary),
+        (76, AuctionError::InvalidTierRegistryV2Data),
+        (77, AuctionError::TierNotFound),
+        (78, AuctionError::TierRegistryFull),
+        (79, AuctionError::InvalidConfigPolicyV2Patch),
+        (80, AuctionError::InvalidConfigPolicyAuthority),
+        (81, AuctionError::DuplicateConfigPolicyAuthority),
+        (82, AuctionError::ConfigPolicyAuthoritySlotsFull),
+        (83, AuctionError::ConfigPolicyAuthorityNotFound),
+        (84, AuctionError::LastConfigPolicyAdmin),
+        (85, AuctionError::InvalidConfigPolicyV2ProposalData),
+        (86, AuctionError::ConfigPolicyV2ProposalRequired),
+        (87, AuctionError::InvalidAdminApprovalThreshold),
+        (88, AuctionError::ConfigPolicyV2ProposalNotPending),
+        (89, AuctionError::ConfigPolicyV2ProposalExpired),
+        (90, AuctionError::ConfigPolicyV2ProposalAlreadyApproved),
+        (91, AuctionError::ConfigPolicyV2ProposalThresholdNotMet),
+        (92, AuctionError::InvalidConfigPolicyV2ScheduleData),
+        (93, AuctionError::ConfigPolicyV2ScheduleFull),
+        (94, AuctionError::ActivationSlotTooEarly),
+        (95, AuctionError::ScheduledConfigPolicyV2PatchNotFound),
+        (96, AuctionError::ProtocolPaused),
+        (97, AuctionError::InvalidProtocolFee),
+        (98, AuctionError::InvalidProtocolFeeRecipient),
+        (99, AuctionError::InvalidTokenEscrowAccounts),
+        (100, AuctionError::InvalidRefundPageV2Input),
+        (101, AuctionError::RequesterRefundAlreadyClaimed),
+        (102, AuctionError::InvalidRunnerUpsV2),
+        (103, AuctionError::NoRunnerUpAvailable),
+        (104, AuctionError::InvalidNodePenalty),
+        (105, AuctionError::NodeNotPenalizable),
+        (106, AuctionError::NodePenaltyAlreadyApplied),
+        (107, AuctionError::InvalidNodePenaltyRecord),
+        (108, AuctionError::InvalidNodeReputationV2),
+        (109, AuctionError::InvalidVerifierCandidates),
+        (110, AuctionError::VerifierSelectionMismatch),
+        (111, AuctionError::InvalidTokenRanges),
+        (112, AuctionError::InvalidMerkleProof),
+        (113, AuctionError::InvalidEncryptionIv),
+        (114, AuctionError::InvalidEncryptionKey),
+        (115, AuctionError::InvalidDataUpload),
+        (116, AuctionError::InvalidCompressedPayload),
+        (117, AuctionError::CompressionNotSupported),
+        (118, AuctionError::DataAccountChainCycle),
+        (119, AuctionError::DataAccountAuthorityMismatch),
+        (120, AuctionError::InvalidDataAccountChain),
+        (121, AuctionError::InvalidJobInputAccount),
+        (122, AuctionError::JobInputHashMismatch),
+        (123, AuctionError::JobInputHashEncrypted),
+        (124, AuctionError::InvalidCid),
+        (125, AuctionError::UnsupportedCid),
+        (126, AuctionError::InvalidNodeRegistration),
+        (127, AuctionError::ConfigPolicyV2PatchMustBeScheduled),
+        (128, AuctionError::InvalidActivationDelay),
     ];
 
     for (code, error) in new_errors {
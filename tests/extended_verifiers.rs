@@ -0,0 +1,245 @@
+use ambient_auction_api::{
+    error::AuctionError, AccountLayoutVersion, BundleEscrowV2, BundleEscrowV2Status,
+    BundleEscrowV2VerifierSet, BundleVerifierPageV2, BundleVerifierPageV2EntryVerifiers,
+    ConfigPolicyV2, ConfigPolicyV2Patch, FinalizeBundleVerificationV2Accounts, InstructionAccounts,
+    NodePenaltyKind, NodeReputationV2, Pubkey, RawConfigPolicyV2ScheduleData,
+    MAX_EXTENDED_VERIFIERS_PER_AUCTION, MAX_VERIFIERS_PER_AUCTION,
+};
+
+fn test_pubkey(byte: u8) -> Pubkey {
+    [byte; 32].into()
+}
+
+/// Schedules `patch` with the shortest activation delay and activates it once due.
+fn apply_scheduled(
+    policy: &mut ConfigPolicyV2,
+    patch: &ConfigPolicyV2Patch,
+    signer: &Pubkey,
+) -> Result<(), AuctionError> {
+    let mut schedule = RawConfigPolicyV2ScheduleData::new(test_pubkey(50), 255, 1);
+    schedule.schedule(policy, signer, patch, 0, 1)?;
+    schedule.activate_due(policy, 1).map(|_| ())
+}
+
+fn extended_verifier_set() -> BundleEscrowV2VerifierSet {
+    let verifiers: Vec<_> = (10..20).map(test_pubkey).collect();
+    let mut set = BundleEscrowV2VerifierSet::new(&verifiers).unwrap();
+    set.verifier_reward_remaining[..10].fill(100);
+    set.quorum_verifier_bitmap = 0b11_0000_0111;
+    set
+}
+
+fn escrow_bytes(version: AccountLayoutVersion) -> Vec<u8> {
+    let escrow = BundleEscrowV2 {
+        status: BundleEscrowV2Status::FinalizedVerified,
+        escrow_lamports: 10_000,
+        winner_node_pubkey: test_pubkey(1),
+        winner_payout_lamports: 5_000,
+        winner_reward_claimed: 1,
+        ..Default::default()
+    };
+    let mut bytes = vec![0; BundleEscrowV2::account_len(version)];
+    assert!(escrow.write_bytes_with_layout(&mut bytes, version));
+    bytes
+}
+
+#[test]
+fn v6_escrow_stores_up_to_sixteen_verifiers() {
+    let mut bytes = escrow_bytes(AccountLayoutVersion::V6);
+    assert_eq!(
+        bytes.len(),
+        BundleEscrowV2::LEN_V5 + BundleEscrowV2VerifierSet::LEN
+    );
+    let mut account = BundleEscrowV2::from_bytes_mut(&mut bytes).unwrap();
+    assert_eq!(
+        account.verifier_capacity(),
+        MAX_EXTENDED_VERIFIERS_PER_AUCTION
+    );
+    account.set_verifier_set(&extended_verifier_set()).unwrap();
+    assert_eq!(account.selected_verifiers, [Pubkey::default(); 3]);
+
+    assert_eq!(
+        account.mark_penalized(NodePenaltyKind::MISSED_VERIFICATION, 9),
+        Err(AuctionError::NodeNotPenalizable)
+    );
+    assert_eq!(
+        account.mark_penalized(NodePenaltyKind::MISSED_VERIFICATION, 7),
+        Ok(test_pubkey(17))
+    );
+    assert_eq!(
+        account.mark_penalized(NodePenaltyKind::MISSED_VERIFICATION, 7),
+        Err(AuctionError::NodePenaltyAlreadyApplied)
+    );
+
+    let policy = ConfigPolicyV2::production_default();
+    assert_eq!(
+        account.payout(&policy).unwrap().verifier_reward_amount,
+        1_000
+    );
+    for index in [0, 1, 2, 8] {
+        assert!(account.claim_verifier_reward(index, 100));
+    }
+    assert!(!account.all_quorum_verifier_rewards_claimed());
+    assert!(account.claim_verifier_reward(9, 100));
+    assert!(account.final_reward_claims_complete());
+
+    let parsed = BundleEscrowV2::from_bytes(&bytes).unwrap();
+    let set = parsed.verifier_set();
+    assert_eq!(set.len(), 10);
+    assert_eq!(set.penalized_verifier_bitmap, 1 << 7);
+    assert_eq!(set.verifier_reward_claimed_bitmap, 0b11_0000_0111);
+    assert_eq!(parsed.penalized_node_bitmap, 0);
+
+    let mut reputation = NodeReputationV2::new(test_pubkey(19));
+    reputation.record_escrow(parsed.as_raw(), &set, 50).unwrap();
+    assert_eq!(reputation.verifications_participated, 1);
+}
+
+#[test]
+fn v6_finalize_takes_a_reputation_account_per_verifier() {
+    let mut bytes = escrow_bytes(AccountLayoutVersion::V6);
+    let mut account = BundleEscrowV2::from_bytes_mut(&mut bytes).unwrap();
+    account.set_verifier_set(&extended_verifier_set()).unwrap();
+    let escrow = BundleEscrowV2::from_bytes(&bytes).unwrap();
+    let set = escrow.verifier_set();
+
+    // Eight fixed accounts, a reputation per verifier and two verifier pages.
+    let accounts: Vec<u8> = (1..=20).collect();
+    let finalize = FinalizeBundleVerificationV2Accounts::try_from(&accounts[..])
+        .unwrap()
+        .with_verifier_count(set.len())
+        .unwrap();
+    assert_eq!(finalize.verifier_reputations, &accounts[8..18]);
+    assert_eq!(finalize.bundle_verifier_pages, &[19, 20]);
+    assert_eq!(finalize.iter().copied().collect::<Vec<_>>(), accounts);
+
+    for (verifier, reputation_account) in set.selected_verifiers[..set.len()]
+        .iter()
+        .zip(finalize.verifier_reputations)
+    {
+        let mut reputation = NodeReputationV2::new(*verifier);
+        reputation.record_escrow(escrow.as_raw(), &set, 50).unwrap();
+        assert_eq!(reputation.verifications_assigned, 1, "{reputation_account}");
+    }
+
+    assert!(matches!(
+        FinalizeBundleVerificationV2Accounts::try_from(&accounts[..17])
+            .unwrap()
+            .with_verifier_count(set.len()),
+        Err(AuctionError::NotEnoughAccounts)
+    ));
+}
+
+#[test]
+fn earlier_escrow_layouts_expose_the_payload_verifiers() {
+    let mut bytes = escrow_bytes(AccountLayoutVersion::V5);
+    let mut account = BundleEscrowV2::from_bytes_mut(&mut bytes).unwrap();
+    assert_eq!(account.verifier_capacity(), MAX_VERIFIERS_PER_AUCTION);
+    assert_eq!(
+        account.set_verifier_set(&extended_verifier_set()),
+        Err(AuctionError::InvalidVerifierCount)
+    );
+
+    let mut set = BundleEscrowV2VerifierSet::new(&[test_pubkey(10), test_pubkey(11)]).unwrap();
+    set.verifier_reward_remaining[..2].copy_from_slice(&[40, 60]);
+    set.quorum_verifier_bitmap = 0b01;
+    account.set_verifier_set(&set).unwrap();
+    assert_eq!(
+        account.selected_verifiers,
+        [test_pubkey(10), test_pubkey(11), Pubkey::default()]
+    );
+    assert_eq!(account.quorum_verifier_bitmap, 0b01);
+
+    assert_eq!(
+        account.mark_penalized(NodePenaltyKind::MISSED_VERIFICATION, 1),
+        Ok(test_pubkey(11))
+    );
+    assert_eq!(account.penalized_node_bitmap, 0b10);
+    assert!(account.claim_verifier_reward(0, 40));
+    assert_eq!(account.verifier_reward_claimed_bitmap, 0b01);
+    assert_eq!(account.verifier_set(), {
+        set.penalized_verifier_bitmap = 0b10;
+        set.verifier_reward_remaining[0] = 0;
+        set.verifier_reward_claimed_bitmap = 0b01;
+        set
+    });
+}
+
+#[test]
+fn v6_verifier_pages_store_sixteen_verifier_columns() {
+    let page = BundleVerifierPageV2 {
+        entry_count: 1,
+        ..Default::default()
+    };
+    let mut bytes = vec![0; BundleVerifierPageV2::LEN_V6];
+    assert!(page.write_v6_bytes(&mut bytes));
+
+    let mut verifiers = BundleVerifierPageV2EntryVerifiers::default();
+    verifiers.assigned_verifiers_token_ranges[30..].copy_from_slice(&[0, 12]);
+    verifiers.verifier_reward_tokens[15] = 7;
+    verifiers.verifier_claimed_bitmap = 1 << 15;
+
+    let mut account = BundleVerifierPageV2::from_bytes_mut(&mut bytes).unwrap();
+    assert_eq!(
+        account.verifier_capacity(),
+        MAX_EXTENDED_VERIFIERS_PER_AUCTION
+    );
+    assert!(account.set_entry_verifiers(5, &verifiers));
+    assert!(!account.set_entry_verifiers(6, &verifiers));
+    let parsed = BundleVerifierPageV2::from_bytes(&bytes).unwrap();
+    assert_eq!(parsed.entry_verifiers(5), Some(verifiers));
+    assert_eq!(parsed.entries[5].verifier_reward_tokens, [0; 3]);
+
+    let mut bytes = vec![0; BundleVerifierPageV2::LEN_V2];
+    assert!(page.write_v2_bytes(&mut bytes));
+    let mut account = BundleVerifierPageV2::from_bytes_mut(&mut bytes).unwrap();
+    assert!(!account.set_entry_verifiers(0, &verifiers));
+    let mut legacy = BundleVerifierPageV2EntryVerifiers::default();
+    legacy.verifier_reward_tokens[2] = 9;
+    assert!(account.set_entry_verifiers(0, &legacy));
+    assert_eq!(account.entries[0].verifier_reward_tokens, [0, 0, 9]);
+    assert_eq!(account.entry_verifiers(0), Some(legacy));
+}
+
+#[test]
+fn verifier_count_is_checked_against_the_layout_capacity() {
+    let mut policy = ConfigPolicyV2::production_default();
+    policy.admin_authorities[0] = test_pubkey(99);
+    let extended = ConfigPolicyV2Patch::VerifierSettings {
+        v2_verifiers_per_auction: 9,
+        v2_verifier_quorum: 6,
+    };
+    assert_eq!(policy.verifier_capacity(), MAX_VERIFIERS_PER_AUCTION);
+    assert_eq!(
+        policy.apply(&extended, &test_pubkey(99)),
+        Err(AuctionError::ConfigPolicyV2PatchMustBeScheduled)
+    );
+    assert_eq!(
+        apply_scheduled(&mut policy, &extended, &test_pubkey(99)),
+        Err(AuctionError::InvalidVerifierCount)
+    );
+
+    policy.v2_account_layout_version = AccountLayoutVersion::V6 as u8;
+    assert_eq!(
+        policy.configured_bundle_escrow_v2_layout_version(),
+        Err(AccountLayoutVersion::V5 as u8)
+    );
+    policy.bundle_escrow_v2_layout_version = AccountLayoutVersion::V6 as u8;
+    assert_eq!(
+        policy.configured_bundle_escrow_v2_layout_version(),
+        Ok(AccountLayoutVersion::V6)
+    );
+    apply_scheduled(&mut policy, &extended, &test_pubkey(99)).unwrap();
+    assert_eq!(policy.v2_verifiers_per_auction, 9);
+    assert_eq!(
+        apply_scheduled(
+            &mut policy,
+            &ConfigPolicyV2Patch::VerifierSettings {
+                v2_verifiers_per_auction: 17,
+                v2_verifier_quorum: 6,
+            },
+            &test_pubkey(99),
+        ),
+        Err(AuctionError::InvalidVerifierCount)
+    );
+}
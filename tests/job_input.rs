@@ -0,0 +1,165 @@
+use ambient_auction_api::{
+    data_account_content_hash, error::AuctionError, parse_metadata_layout, verify_job_input,
+    verify_job_input_chain, AccountLayoutVersion, DataAccountWriter, JobRequest, Metadata,
+    MetadataV1, Pubkey,
+};
+
+const REQUESTER: [u8; 32] = [9; 32];
+const JOB_REQUEST_KEY: [u8; 32] = [5; 32];
+
+fn prompt() -> Vec<u8> {
+    b"Summarize the following document in three sentences.".repeat(40)
+}
+
+/// Sets the `job_request_key` back-reference the program records on the data account.
+fn link_to_job_request(account: &mut [u8], job_request_key: Pubkey) {
+    if parse_metadata_layout(account).unwrap().version == AccountLayoutVersion::V1 {
+        let mut metadata: MetadataV1 = bytemuck::pod_read_unaligned(&account[..MetadataV1::LEN]);
+        metadata.job_request_key = job_request_key.into();
+        account[..MetadataV1::LEN].copy_from_slice(bytemuck::bytes_of(&metadata));
+    } else {
+        let mut metadata: Metadata = bytemuck::pod_read_unaligned(&account[..Metadata::LEN]);
+        metadata.job_request_key = job_request_key.into();
+        account[..Metadata::LEN].copy_from_slice(bytemuck::bytes_of(&metadata));
+    }
+}
+
+fn job_request(input: &[u8], input_data_account: Pubkey) -> JobRequest {
+    JobRequest {
+        authority: REQUESTER.into(),
+        input_hash: data_account_content_hash(input).into(),
+        input_data_account: input_data_account.into(),
+        ..Default::default()
+    }
+}
+
+fn input_account(input: &[u8]) -> Vec<u8> {
+    let mut account = DataAccountWriter::uncompressed(input)
+        .account_data(REQUESTER.into(), b"input")
+        .unwrap();
+    link_to_job_request(&mut account, JOB_REQUEST_KEY.into());
+    account
+}
+
+#[test]
+fn content_hash_is_sha256_of_the_payload() {
+    assert_eq!(
+        data_account_content_hash(b"abc"),
+        [
+            0xba, 0x78, 0x16, 0xbf, 0x8f, 0x01, 0xcf, 0xea, 0x41, 0x41, 0x40, 0xde, 0x5d, 0xae,
+            0x22, 0x23, 0xb0, 0x03, 0x61, 0xa3, 0x96, 0x17, 0x7a, 0x9c, 0xb4, 0x10, 0xff, 0x61,
+            0xf2, 0x00, 0x15, 0xad,
+        ]
+    );
+}
+
+#[test]
+fn verify_job_input_accepts_the_committed_input() {
+    let input = prompt();
+    let request = job_request(&input, [1; 32].into());
+    assert_eq!(
+        verify_job_input(JOB_REQUEST_KEY.into(), &request, &input_account(&input)),
+        Ok(())
+    );
+}
+
+#[test]
+fn verify_job_input_rejects_tampered_or_foreign_accounts() {
+    let input = prompt();
+    let request = job_request(&input, [1; 32].into());
+
+    let mut tampered = input.clone();
+    tampered[3] ^= 1;
+    assert_eq!(
+        verify_job_input(JOB_REQUEST_KEY.into(), &request, &input_account(&tampered)),
+        Err(AuctionError::JobInputHashMismatch)
+    );
+    assert_eq!(
+        verify_job_input([6; 32].into(), &request, &input_account(&input)),
+        Err(AuctionError::InvalidJobInputAccount)
+    );
+
+    let mut other_authority = input_account(&input);
+    other_authority[..32].fill(4);
+    assert_eq!(
+        verify_job_input(JOB_REQUEST_KEY.into(), &request, &other_authority),
+        Err(AuctionError::InvalidJobInputAccount)
+    );
+
+    let encrypted = JobRequest {
+        input_hash_iv: [1; 16],
+        ..request
+    };
+    assert_eq!(
+        verify_job_input(JOB_REQUEST_KEY.into(), &encrypted, &input_account(&input)),
+        Err(AuctionError::JobInputHashEncrypted)
+    );
+}
+
+#[test]
+fn verify_job_input_chain_follows_linked_accounts() {
+    let input = prompt();
+    let writer = DataAccountWriter::uncompressed(&input);
+    let keys: Vec<Pubkey> = (1..=3).map(|index| [index; 32].into()).collect();
+    let seeds: [&[u8]; 3] = [b"input-0", b"input-1", b"input-2"];
+    let accounts: Vec<(Pubkey, &[u8])> = keys.iter().copied().zip(seeds).collect();
+    let mut stored: Vec<(Pubkey, Vec<u8>)> = writer
+        .linked_accounts(&accounts, input.len().div_ceil(3))
+        .unwrap()
+        .iter()
+        .map(|linked| (linked.key(), linked.account_data(REQUESTER.into()).unwrap()))
+        .collect();
+    link_to_job_request(&mut stored[0].1, JOB_REQUEST_KEY.into());
+    let fetch = |key: &Pubkey| {
+        stored
+            .iter()
+            .find(|(stored_key, _)| stored_key == key)
+            .map(|(_, account)| account.clone())
+    };
+
+    assert_eq!(
+        verify_job_input_chain(JOB_REQUEST_KEY.into(), &job_request(&input, keys[0]), fetch),
+        Ok(())
+    );
+    assert_eq!(
+        verify_job_input_chain(
+            JOB_REQUEST_KEY.into(),
+            &job_request(&input[1..], keys[0]),
+            fetch
+        ),
+        Err(AuctionError::JobInputHashMismatch)
+    );
+    assert_eq!(
+        verify_job_input_chain(
+            JOB_REQUEST_KEY.into(),
+            &job_request(&input, Pubkey::default()),
+            fetch
+        ),
+        Err(AuctionError::InvalidJobInputAccount)
+    );
+}
+
+#[cfg(feature = "unverified-commitment-cipher")]
+#[test]
+fn encrypted_input_hash_verifies_after_decryption() {
+    use ambient_auction_api::{decrypt_job_input_hash, encrypt_commitment, CommitmentKey};
+
+    let input = prompt();
+    let key = CommitmentKey::from_shared_secret(&[7; 32]);
+    let iv = [3; 16];
+    let request = job_request(&input, [1; 32].into());
+    let encrypted = JobRequest {
+        input_hash: encrypt_commitment(&key, &iv, &request.input_hash.inner())
+            .unwrap()
+            .into(),
+        input_hash_iv: iv,
+        ..request
+    };
+
+    let decrypted = decrypt_job_input_hash(&key, &encrypted);
+    assert_eq!(decrypted, request);
+    assert_eq!(
+        verify_job_input(JOB_REQUEST_KEY.into(), &decrypted, &input_account(&input)),
+        Ok(())
+    );
+}
@@ -0,0 +1,122 @@
+use ambient_auction_api::{
+    assign_token_ranges, error::AuctionError, output_merkle_root, OutputMerkleTree, OutputToken,
+    TokenRangeStrategy,
+};
+
+fn tokens(count: u32) -> Vec<OutputToken> {
+    (0..count)
+        .map(|index| OutputToken {
+            token_id: 1_000 + index,
+            logprob: -(index as f32) / 8.0,
+        })
+        .collect()
+}
+
+#[test]
+fn root_commits_to_token_order_values_and_length() {
+    let output = tokens(7);
+    let root = output_merkle_root(&output).unwrap();
+    assert_eq!(output_merkle_root(&output), Ok(root));
+
+    let mut swapped = output.clone();
+    swapped.swap(2, 3);
+    assert_ne!(output_merkle_root(&swapped), Ok(root));
+
+    let mut changed_logprob = output.clone();
+    changed_logprob[6].logprob = 0.5;
+    assert_ne!(output_merkle_root(&changed_logprob), Ok(root));
+
+    assert_ne!(output_merkle_root(&output[..6]), Ok(root));
+    assert_eq!(output_merkle_root(&[output[0]]), Ok(output[0].leaf_hash(0)));
+    assert_eq!(
+        output_merkle_root(&[]),
+        Err(AuctionError::InvalidTokenRanges)
+    );
+}
+
+#[test]
+fn every_range_proof_verifies_against_the_root() {
+    for count in 1..=13 {
+        let output = tokens(count);
+        let tree = OutputMerkleTree::new(&output).unwrap();
+        for start in 0..count {
+            for end in start + 1..=count {
+                let proof = tree.prove_range(u64::from(start), u64::from(end)).unwrap();
+                let range = &output[start as usize..end as usize];
+                assert_eq!(proof.verify(&tree.root(), u64::from(count), range), Ok(()));
+            }
+        }
+    }
+}
+
+#[test]
+fn proofs_reject_tampered_tokens_and_siblings() {
+    let output = tokens(10);
+    let tree = OutputMerkleTree::new(&output).unwrap();
+    let root = tree.root();
+
+    let proof = tree.prove(4).unwrap();
+    assert_eq!(proof.verify(&root, 10, &output[4..5]), Ok(()));
+    assert_eq!(
+        proof.verify(&root, 10, &output[5..6]),
+        Err(AuctionError::InvalidMerkleProof)
+    );
+
+    let mut tampered = proof.clone();
+    tampered.siblings[0][0] ^= 1;
+    assert_eq!(
+        tampered.verify(&root, 10, &output[4..5]),
+        Err(AuctionError::InvalidMerkleProof)
+    );
+    let mut extended = proof.clone();
+    extended.siblings.push([0; 32]);
+    assert_eq!(
+        extended.verify(&root, 10, &output[4..5]),
+        Err(AuctionError::InvalidMerkleProof)
+    );
+    assert_eq!(
+        proof.verify(&root, 11, &output[4..5]),
+        Err(AuctionError::InvalidMerkleProof)
+    );
+    let mut recounted = proof.clone();
+    recounted.leaf_count = 11;
+    assert_eq!(
+        recounted.verify(&root, 10, &output[4..5]),
+        Err(AuctionError::InvalidMerkleProof)
+    );
+    let mut moved = proof;
+    moved.start = 5;
+    moved.end = 6;
+    assert_eq!(
+        moved.verify(&root, 10, &output[4..5]),
+        Err(AuctionError::InvalidMerkleProof)
+    );
+
+    assert_eq!(
+        tree.prove_range(3, 11),
+        Err(AuctionError::InvalidTokenRanges)
+    );
+    assert_eq!(
+        tree.prove_range(3, 3),
+        Err(AuctionError::InvalidTokenRanges)
+    );
+}
+
+#[test]
+fn verifiers_prove_their_assigned_ranges() {
+    let output = tokens(34);
+    let tree = OutputMerkleTree::new(&output).unwrap();
+    let ranges =
+        assign_token_ranges(34, 3, TokenRangeStrategy::Overlapping { redundancy: 2 }).unwrap();
+    for pair in ranges[..6].chunks_exact(2) {
+        let proof = tree.prove_range(pair[0], pair[1]).unwrap();
+        assert_eq!(
+            proof.verify(
+                &tree.root(),
+                34,
+                &output[pair[0] as usize..pair[1] as usize]
+            ),
+            Ok(())
+        );
+    }
+}
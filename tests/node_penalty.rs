@@ -0,0 +1,249 @@
+use ambient_auction_api::{
+    error::AuctionError, AccountLayoutVersion, ApplyNodePenaltyV2Accounts, AuctionInstruction,
+    BundleEscrowV2, BundleEscrowV2RunnerUp, BundleEscrowV2RunnerUps, BundleEscrowV2Status,
+    BundleEscrowV2TokenDenomination, ConfigPolicyV2, ConfigPolicyV2Patch, ConfigPolicyV2PauseGroup,
+    InstructionAccounts, NodePenaltyKind, NodePenaltyRecord, Pubkey,
+};
+
+fn test_pubkey(byte: u8) -> Pubkey {
+    [byte; 32].into()
+}
+
+fn policy_with_penalties() -> ConfigPolicyV2 {
+    let mut policy = ConfigPolicyV2::production_default();
+    policy.admin_authorities[0] = test_pubkey(99);
+    policy
+        .apply(
+            &ConfigPolicyV2Patch::NodePenalties {
+                rejected_result_penalty_bps: 1_000,
+                missed_result_penalty_bps: 500,
+                missed_verification_penalty_bps: 100,
+            },
+            &test_pubkey(99),
+        )
+        .unwrap();
+    policy
+}
+
+fn escrow_bytes(escrow: &BundleEscrowV2) -> Vec<u8> {
+    let mut bytes = vec![0; BundleEscrowV2::LEN_V5];
+    assert!(escrow.write_v5_bytes(&mut bytes));
+    bytes
+}
+
+fn awarded_escrow() -> BundleEscrowV2 {
+    BundleEscrowV2 {
+        status: BundleEscrowV2Status::Awarded,
+        escrow_lamports: 1_000_000,
+        max_output_tokens: 1_000,
+        winner_node_pubkey: test_pubkey(1),
+        clearing_price_per_output_token: 500,
+        selected_verifiers: [test_pubkey(10), test_pubkey(11), test_pubkey(12)],
+        result_deadline_slot: 100,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn node_penalties_scale_with_the_award() {
+    let policy = policy_with_penalties();
+    let escrow = BundleEscrowV2 {
+        status: BundleEscrowV2Status::FinalizedRejected,
+        ..awarded_escrow()
+    };
+    assert_eq!(escrow.award_amount(), 500_000);
+
+    let mut bytes = escrow_bytes(&escrow);
+    let mut account = BundleEscrowV2::from_bytes_mut(&mut bytes).unwrap();
+    assert_eq!(
+        account.node_penalty_lamports(&policy, NodePenaltyKind::REJECTED_RESULT),
+        50_000
+    );
+    assert_eq!(
+        account.node_penalty_lamports(&policy, NodePenaltyKind::MISSED_VERIFICATION),
+        5_000
+    );
+
+    assert_eq!(
+        account.mark_penalized(NodePenaltyKind::REJECTED_RESULT, 0),
+        Ok(test_pubkey(1))
+    );
+    assert_eq!(
+        account.mark_penalized(NodePenaltyKind::REJECTED_RESULT, 0),
+        Err(AuctionError::NodePenaltyAlreadyApplied)
+    );
+    assert_eq!(
+        account.mark_penalized(NodePenaltyKind::MISSED_RESULT, 0),
+        Err(AuctionError::NodeNotPenalizable)
+    );
+
+    let mut invalid = policy;
+    assert_eq!(
+        invalid.apply(
+            &ConfigPolicyV2Patch::NodePenalties {
+                rejected_result_penalty_bps: 10_001,
+                missed_result_penalty_bps: 0,
+                missed_verification_penalty_bps: 0,
+            },
+            &test_pubkey(99),
+        ),
+        Err(AuctionError::InvalidNodePenalty)
+    );
+}
+
+#[test]
+fn token_escrow_penalties_count_in_the_escrow_token() {
+    let policy = policy_with_penalties();
+    let escrow = BundleEscrowV2 {
+        status: BundleEscrowV2Status::FinalizedRejected,
+        ..awarded_escrow()
+    };
+    let mut bytes = escrow_bytes(&escrow);
+    let mut account = BundleEscrowV2::from_bytes_mut(&mut bytes).unwrap();
+    let mint = test_pubkey(30);
+    assert!(
+        account.set_token_denomination(&BundleEscrowV2TokenDenomination {
+            mint,
+            ..Default::default()
+        })
+    );
+    assert_eq!(account.denomination().mint(), mint);
+    assert_eq!(
+        account.node_penalty_lamports(&policy, NodePenaltyKind::REJECTED_RESULT),
+        50_000
+    );
+
+    let record = NodePenaltyRecord::new(test_pubkey(1), mint);
+    assert_eq!(record.check_node(&test_pubkey(1), &mint), Ok(()));
+    assert_eq!(
+        record.check_node(&test_pubkey(1), &Pubkey::default()),
+        Err(AuctionError::InvalidNodePenaltyRecord)
+    );
+    assert_eq!(
+        record.check_node(&test_pubkey(2), &mint),
+        Err(AuctionError::InvalidNodePenaltyRecord)
+    );
+}
+
+#[test]
+fn missed_verification_penalises_only_verifiers_that_did_not_vote() {
+    let escrow = BundleEscrowV2 {
+        status: BundleEscrowV2Status::FinalizedVerified,
+        quorum_verifier_bitmap: 0b001,
+        ..awarded_escrow()
+    };
+    let mut bytes = escrow_bytes(&escrow);
+    let mut account = BundleEscrowV2::from_bytes_mut(&mut bytes).unwrap();
+    for invalid in [0b001, 0b1000] {
+        assert_eq!(
+            account.set_dissenting_verifiers(invalid),
+            Err(AuctionError::InvalidVerifierQuorum)
+        );
+    }
+    account.set_dissenting_verifiers(0b100).unwrap();
+    assert_eq!(account.dissenting_verifier_bitmap, 0b100);
+
+    assert_eq!(
+        account.mark_penalized(NodePenaltyKind::MISSED_VERIFICATION, 0),
+        Err(AuctionError::NodeNotPenalizable)
+    );
+    assert_eq!(
+        account.mark_penalized(NodePenaltyKind::MISSED_VERIFICATION, 1),
+        Ok(test_pubkey(11))
+    );
+    assert_eq!(
+        account.mark_penalized(NodePenaltyKind::MISSED_VERIFICATION, 2),
+        Err(AuctionError::NodeNotPenalizable)
+    );
+    assert_eq!(
+        account.mark_penalized(NodePenaltyKind::MISSED_VERIFICATION, 3),
+        Err(AuctionError::NodeNotPenalizable)
+    );
+    assert_eq!(account.penalized_node_bitmap, 0b010);
+}
+
+#[test]
+fn missed_result_penalises_defaulted_and_expired_winners() {
+    let escrow = awarded_escrow();
+    let mut bytes = escrow_bytes(&escrow);
+    let mut account = BundleEscrowV2::from_bytes_mut(&mut bytes).unwrap();
+    let runner_ups = BundleEscrowV2RunnerUps::new(
+        &escrow,
+        &account.verifier_set(),
+        &[BundleEscrowV2RunnerUp {
+            node_pubkey: test_pubkey(2),
+            vote_account: test_pubkey(3),
+            clearing_price_per_output_token: 600,
+        }],
+    )
+    .unwrap();
+    assert!(account.set_runner_ups(&runner_ups));
+    assert_eq!(
+        account.reassign_winner(101, &ConfigPolicyV2::production_default()),
+        Ok(test_pubkey(1))
+    );
+
+    assert_eq!(
+        account.mark_penalized(NodePenaltyKind::MISSED_RESULT, 0),
+        Ok(test_pubkey(1))
+    );
+    assert_eq!(
+        account.mark_penalized(NodePenaltyKind::MISSED_RESULT, 1),
+        Err(AuctionError::NodeNotPenalizable)
+    );
+
+    account.expire().unwrap();
+    assert_eq!(
+        account.mark_penalized(NodePenaltyKind::MISSED_RESULT, 1),
+        Ok(test_pubkey(2))
+    );
+}
+
+#[test]
+fn node_penalty_record_withholds_outstanding_penalties() {
+    let mut record = NodePenaltyRecord::new(test_pubkey(1), Pubkey::default());
+    record
+        .apply_penalty(NodePenaltyKind::REJECTED_RESULT, test_pubkey(9), 700, 42)
+        .unwrap();
+    record
+        .apply_penalty(
+            NodePenaltyKind::MISSED_VERIFICATION,
+            test_pubkey(8),
+            300,
+            43,
+        )
+        .unwrap();
+    assert_eq!(
+        record.apply_penalty(NodePenaltyKind(9), test_pubkey(8), 300, 43),
+        Err(AuctionError::NodeNotPenalizable)
+    );
+    assert_eq!(record.rejected_result_count, 1);
+    assert_eq!(record.missed_verification_count, 1);
+    assert_eq!(record.outstanding_penalty_lamports(), 1_000);
+
+    assert_eq!(record.withhold(600), 600);
+    assert_eq!(record.withhold(600), 400);
+    assert_eq!(record.outstanding_penalty_lamports(), 0);
+
+    let mut bytes = vec![0; NodePenaltyRecord::LEN];
+    assert!(record.write_bytes(&mut bytes));
+    let parsed = NodePenaltyRecord::from_bytes(&bytes).unwrap();
+    assert_eq!(parsed.layout().version, AccountLayoutVersion::V1);
+    assert_eq!(parsed.slashed_lamports, 1_000);
+    assert_eq!(parsed.last_bundle_escrow, test_pubkey(8));
+}
+
+#[test]
+fn apply_node_penalty_v2_accounts_and_pause_group() {
+    let keys = [1u8, 2, 3, 4];
+    let accounts = ApplyNodePenaltyV2Accounts::try_from(&keys[..]).unwrap();
+    assert_eq!(accounts.iter().copied().collect::<Vec<_>>(), keys);
+    assert_eq!(
+        ApplyNodePenaltyV2Accounts::try_from(&keys[..3]).err(),
+        Some(AuctionError::NotEnoughAccounts)
+    );
+    assert_eq!(
+        AuctionInstruction::ApplyNodePenaltyV2.pause_group(),
+        Some(ConfigPolicyV2PauseGroup::Settlement)
+    );
+}
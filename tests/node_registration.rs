@@ -0,0 +1,192 @@
+use ambient_auction_api::{
+    error::AuctionError, AccountDiscriminator, AccountLayoutVersion, AuctionInstruction, Bid,
+    ConfigPolicyV2PauseGroup, DeregisterNodeArgs, InstructionBytes, IpAddr, NodeRegistration,
+    NodeRegistrationInfo, ParsedAccountLayout, PlaceBidAccounts, PlaceBidArgs, RegisterNodeArgs,
+    RequestTier, UpdateNodeRegistrationArgs, MAX_NODE_HOSTNAME_LEN,
+};
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+
+fn info(ip: std::net::IpAddr, hostname: Option<&str>) -> NodeRegistrationInfo {
+    NodeRegistrationInfo::new(
+        ip,
+        8443,
+        hostname,
+        Some([7; 32]),
+        &[RequestTier::Eco, RequestTier::Large],
+    )
+    .unwrap()
+}
+
+#[test]
+fn node_registration_info_holds_the_endpoint() {
+    let ipv4 = info(
+        Ipv4Addr::new(10, 0, 0, 1).into(),
+        Some("node-1.example.com"),
+    );
+    assert_eq!(
+        ipv4.socket_addr(),
+        "10.0.0.1:8443".parse::<SocketAddr>().unwrap()
+    );
+    assert_eq!(ipv4.hostname(), Some("node-1.example.com"));
+    assert_eq!(ipv4.encryption_public_key(), Some([7; 32]));
+    assert!(ipv4.supports_tier(RequestTier::Eco));
+    assert!(ipv4.supports_tier(RequestTier::Large));
+    assert!(!ipv4.supports_tier(RequestTier::Pro));
+
+    let ipv6 = NodeRegistrationInfo::new(
+        Ipv6Addr::LOCALHOST.into(),
+        9000,
+        None,
+        None,
+        &[RequestTier::Standard],
+    )
+    .unwrap();
+    assert_eq!(
+        ipv6.socket_addr(),
+        "[::1]:9000".parse::<SocketAddr>().unwrap()
+    );
+    assert_eq!(ipv6.hostname(), None);
+    assert_eq!(ipv6.encryption_public_key(), None);
+}
+
+#[test]
+fn node_registration_info_rejects_invalid_input() {
+    let ip = Ipv4Addr::LOCALHOST.into();
+    let long_hostname = "a".repeat(MAX_NODE_HOSTNAME_LEN + 1);
+    for (port, hostname, tiers) in [
+        (0, None, &[RequestTier::Eco][..]),
+        (80, None, &[][..]),
+        (80, Some("bad host"), &[RequestTier::Eco][..]),
+        (80, Some(long_hostname.as_str()), &[RequestTier::Eco][..]),
+    ] {
+        assert_eq!(
+            NodeRegistrationInfo::new(ip, port, hostname, None, tiers),
+            Err(AuctionError::InvalidNodeRegistration)
+        );
+    }
+
+    let mut info = info(ip, Some("node"));
+    info.hostname[10] = b'x';
+    assert_eq!(info.validate(), Err(AuctionError::InvalidNodeRegistration));
+    info.hostname[10] = 0;
+    info.supported_tiers_bitmap |= 0b1000_0000;
+    assert_eq!(info.validate(), Err(AuctionError::InvalidNodeRegistration));
+}
+
+#[test]
+fn node_registration_round_trips_through_account_bytes() {
+    let registration = NodeRegistration::new(
+        [3; 32].into(),
+        info(Ipv4Addr::new(10, 0, 0, 1).into(), None),
+        100,
+    )
+    .unwrap();
+    let mut bytes = vec![0; NodeRegistration::LEN];
+    assert!(registration.write_bytes(&mut bytes));
+    assert!(!registration.write_bytes(&mut bytes[1..]));
+
+    let account = NodeRegistration::from_bytes(&bytes).unwrap();
+    assert_eq!(
+        account.layout(),
+        ParsedAccountLayout::new(
+            AccountDiscriminator::NodeRegistration,
+            AccountLayoutVersion::V1
+        )
+    );
+    assert_eq!(*account.as_raw(), registration);
+    assert!(NodeRegistration::from_bytes(&bytes[..bytes.len() - 1]).is_none());
+
+    bytes[0] = AccountDiscriminator::NodeReputationV2 as u8;
+    assert_eq!(NodeRegistration::read(&bytes), None);
+}
+
+#[test]
+fn updating_a_registration_rotates_the_endpoint_and_records_a_heartbeat() {
+    let mut bytes = vec![0; NodeRegistration::LEN];
+    NodeRegistration::new(
+        [3; 32].into(),
+        info(Ipv4Addr::new(10, 0, 0, 1).into(), None),
+        100,
+    )
+    .unwrap()
+    .write_bytes(&mut bytes);
+
+    let mut account = NodeRegistration::from_bytes_mut(&mut bytes).unwrap();
+    let rotated = info(Ipv4Addr::new(10, 0, 0, 2).into(), Some("node-2"));
+    account.update(rotated, 150).unwrap();
+    assert_eq!(
+        account.update(rotated, 149),
+        Err(AuctionError::InvalidNodeRegistration)
+    );
+
+    let registration = NodeRegistration::read(&bytes).unwrap();
+    assert_eq!(registration.info, rotated);
+    assert_eq!(registration.registered_slot, 100);
+    assert_eq!(registration.heartbeat_slot, 150);
+    assert!(registration.is_live(200, 50));
+    assert!(!registration.is_live(201, 50));
+}
+
+#[test]
+fn node_registration_instructions_encode_their_tags() {
+    let info = info(Ipv4Addr::new(10, 0, 0, 1).into(), None);
+    let register = RegisterNodeArgs {
+        info,
+        node_registration_lamports: 1_000_000,
+    };
+    let bytes = register.to_bytes();
+    assert_eq!(bytes[0], AuctionInstruction::RegisterNode as u8);
+    assert_eq!(RegisterNodeArgs::try_from(&bytes[1..]), Ok(register));
+    assert_eq!(
+        UpdateNodeRegistrationArgs { info }.to_bytes()[0],
+        AuctionInstruction::UpdateNodeRegistration as u8
+    );
+    assert_eq!(
+        DeregisterNodeArgs {}.to_bytes(),
+        vec![AuctionInstruction::DeregisterNode as u8]
+    );
+
+    assert_eq!(
+        AuctionInstruction::RegisterNode.pause_group(),
+        Some(ConfigPolicyV2PauseGroup::Intake)
+    );
+    assert_eq!(
+        AuctionInstruction::DeregisterNode.pause_group(),
+        Some(ConfigPolicyV2PauseGroup::Payout)
+    );
+}
+
+#[test]
+fn place_bid_can_reference_a_node_registration() {
+    let args = PlaceBidArgs::new_with_registration([1; 32], [2; 32]);
+    assert!(args.uses_node_registration());
+    assert!(
+        !PlaceBidArgs::new([1; 32], [2; 32], IpAddr::V4([10, 0, 0, 1]), 80, None)
+            .uses_node_registration()
+    );
+
+    let accounts = [1_u8, 2, 3, 4, 5];
+    assert_eq!(
+        PlaceBidAccounts::try_from(&accounts[..])
+            .unwrap()
+            .node_registration,
+        Some(&5)
+    );
+    assert_eq!(
+        PlaceBidAccounts::try_from(&accounts[..4])
+            .unwrap()
+            .node_registration,
+        None
+    );
+
+    let bid = Bid::new(
+        [2; 32],
+        [1; 32],
+        [3; 32],
+        255,
+        IpAddr::default(),
+        0,
+        [0; 32],
+    );
+    assert!(bid.uses_node_registration());
+}
@@ -0,0 +1,124 @@
+use ambient_auction_api::{
+    error::AuctionError, AccountLayoutVersion, BundleEscrowV2, BundleEscrowV2Status,
+    BundleEscrowV2VerifierSet, ExpireBundleEscrowV2Accounts, FinalizeBundleVerificationV2Accounts,
+    InstructionAccounts, NodeReputationV2, Pubkey,
+};
+
+fn test_pubkey(byte: u8) -> Pubkey {
+    [byte; 32].into()
+}
+
+fn finalized_escrow(status: BundleEscrowV2Status) -> BundleEscrowV2 {
+    BundleEscrowV2 {
+        status,
+        winner_node_pubkey: test_pubkey(1),
+        selected_verifiers: [test_pubkey(10), test_pubkey(11), Pubkey::default()],
+        quorum_verifier_bitmap: 0b001,
+        accepted_output_tokens: 900,
+        ..Default::default()
+    }
+}
+
+fn record(
+    reputation: &mut NodeReputationV2,
+    escrow: &BundleEscrowV2,
+    current_slot: u64,
+) -> Result<(), AuctionError> {
+    reputation.record_escrow(
+        escrow,
+        &BundleEscrowV2VerifierSet::from(escrow),
+        current_slot,
+    )
+}
+
+#[test]
+fn node_reputation_v2_records_winner_outcomes() {
+    let mut reputation = NodeReputationV2::new(test_pubkey(1));
+    assert_eq!(reputation.result_reliability_bps(), None);
+    assert_eq!(reputation.reliability_score_bps(), 5_000);
+
+    for status in [
+        BundleEscrowV2Status::FinalizedVerified,
+        BundleEscrowV2Status::FinalizedVerified,
+        BundleEscrowV2Status::FinalizedRejected,
+        BundleEscrowV2Status::Expired,
+    ] {
+        record(&mut reputation, &finalized_escrow(status), 77).unwrap();
+    }
+    assert_eq!(
+        record(
+            &mut reputation,
+            &finalized_escrow(BundleEscrowV2Status::Awarded),
+            78
+        ),
+        Err(AuctionError::InvalidNodeReputationV2)
+    );
+
+    assert_eq!(reputation.wins, 4);
+    assert_eq!(reputation.verified_results, 2);
+    assert_eq!(reputation.rejected_results, 1);
+    assert_eq!(reputation.expiries, 1);
+    assert_eq!(reputation.accepted_output_tokens, 1_800);
+    assert_eq!(reputation.last_updated_slot, 77);
+    assert_eq!(reputation.result_reliability_bps(), Some(5_000));
+    assert_eq!(reputation.reliability_score_bps(), 5_000);
+}
+
+#[test]
+fn node_reputation_v2_records_verifier_participation() {
+    let mut in_quorum = NodeReputationV2::new(test_pubkey(10));
+    let mut outside_quorum = NodeReputationV2::new(test_pubkey(11));
+    let escrow = finalized_escrow(BundleEscrowV2Status::FinalizedVerified);
+    record(&mut in_quorum, &escrow, 5).unwrap();
+    record(&mut outside_quorum, &escrow, 5).unwrap();
+
+    assert_eq!(in_quorum.verification_participation_bps(), Some(10_000));
+    assert_eq!(outside_quorum.verification_participation_bps(), Some(0));
+    assert!(in_quorum.reliability_score_bps() > outside_quorum.reliability_score_bps());
+
+    assert_eq!(
+        record(
+            &mut outside_quorum,
+            &finalized_escrow(BundleEscrowV2Status::Expired),
+            6
+        ),
+        Err(AuctionError::InvalidNodeReputationV2)
+    );
+    assert_eq!(
+        record(&mut NodeReputationV2::new(test_pubkey(42)), &escrow, 6),
+        Err(AuctionError::InvalidNodeReputationV2)
+    );
+
+    let mut bytes = vec![0; NodeReputationV2::LEN];
+    assert!(in_quorum.write_bytes(&mut bytes));
+    let parsed = NodeReputationV2::from_bytes(&bytes).unwrap();
+    assert_eq!(parsed.layout().version, AccountLayoutVersion::V1);
+    assert_eq!(parsed.verifications_assigned, 1);
+}
+
+#[test]
+fn finalize_and_expire_take_reputation_accounts() {
+    let accounts = [1_u8, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
+    let finalize = FinalizeBundleVerificationV2Accounts::try_from(&accounts[..]).unwrap();
+    assert_eq!(*finalize.winner_reputation, 8);
+    assert_eq!(finalize.verifier_reputations, &[] as &[u8]);
+    assert_eq!(finalize.bundle_verifier_pages, &[9, 10, 11, 12]);
+    let finalize = finalize.with_verifier_count(3).unwrap();
+    assert_eq!(finalize.verifier_reputations, &[9, 10, 11]);
+    assert_eq!(finalize.bundle_verifier_pages, &[12]);
+    assert_eq!(finalize.iter().copied().collect::<Vec<_>>(), accounts);
+    assert!(matches!(
+        FinalizeBundleVerificationV2Accounts::try_from(&accounts[..10])
+            .unwrap()
+            .with_verifier_count(3),
+        Err(AuctionError::NotEnoughAccounts)
+    ));
+
+    let expire = ExpireBundleEscrowV2Accounts::try_from(&accounts[..]).unwrap();
+    assert_eq!(*expire.winner_reputation, 4);
+    assert_eq!(expire.iter().count(), 4);
+    assert!(matches!(
+        ExpireBundleEscrowV2Accounts::try_from(&accounts[..3]),
+        Err(AuctionError::NotEnoughAccounts)
+    ));
+}
@@ -0,0 +1,232 @@
+use ambient_auction_api::{
+    error::AuctionError, AccountLayoutVersion, BundleEscrowV2, BundleEscrowV2Denomination,
+    BundleEscrowV2Payout, BundleEscrowV2PolicySnapshot, BundleEscrowV2Status, ConfigPolicyV2,
+    ConfigPolicyV2Patch, FinalizeBundleVerificationV2Accounts, InstructionAccounts, Pubkey,
+    RequestTier,
+};
+
+fn test_pubkey(byte: u8) -> Pubkey {
+    [byte; 32].into()
+}
+
+fn policy_with_fee(protocol_fee_bps: u16, protocol_min_fee_lamports: u64) -> ConfigPolicyV2 {
+    let mut policy = ConfigPolicyV2::production_default();
+    policy.protocol_fee_bps = protocol_fee_bps;
+    policy.protocol_min_fee_lamports = protocol_min_fee_lamports;
+    policy.protocol_fee_recipient = test_pubkey(7);
+    policy
+}
+
+#[test]
+fn protocol_fee_applies_bps_minimum_and_payout_cap() {
+    assert_eq!(
+        ConfigPolicyV2::production_default().protocol_fee_lamports(1_000_000),
+        0
+    );
+
+    let policy = policy_with_fee(250, 1_000);
+    assert_eq!(policy.protocol_fee_lamports(1_000_000), 25_000);
+    assert_eq!(policy.protocol_fee_lamports(10_000), 1_000);
+    assert_eq!(policy.protocol_fee_lamports(600), 600);
+    assert_eq!(policy.protocol_fee_lamports(0), 0);
+
+    let policy = policy_with_fee(10_000, 0);
+    assert_eq!(policy.protocol_fee_lamports(u64::MAX), u64::MAX);
+}
+
+#[test]
+fn protocol_fee_patch_is_validated() {
+    let admin = test_pubkey(1);
+    let mut policy = ConfigPolicyV2::production_default();
+    policy.admin_authorities[0] = admin;
+
+    let too_high = ConfigPolicyV2Patch::ProtocolFee {
+        protocol_fee_bps: 10_001,
+        protocol_min_fee_lamports: 0,
+        protocol_fee_recipient: test_pubkey(7),
+    };
+    assert_eq!(
+        policy.apply(&too_high, &admin),
+        Err(AuctionError::InvalidProtocolFee)
+    );
+
+    let no_recipient = ConfigPolicyV2Patch::ProtocolFee {
+        protocol_fee_bps: 0,
+        protocol_min_fee_lamports: 1,
+        protocol_fee_recipient: Pubkey::default(),
+    };
+    assert_eq!(
+        policy.apply(&no_recipient, &admin),
+        Err(AuctionError::InvalidProtocolFee)
+    );
+
+    let fee = ConfigPolicyV2Patch::ProtocolFee {
+        protocol_fee_bps: 100,
+        protocol_min_fee_lamports: 0,
+        protocol_fee_recipient: test_pubkey(7),
+    };
+    policy.apply(&fee, &admin).unwrap();
+    assert_eq!(policy.protocol_fee_bps, 100);
+    assert_eq!(policy.protocol_fee_recipient, test_pubkey(7));
+    assert_eq!(policy.check_protocol_fee_recipient(&test_pubkey(7)), Ok(()));
+    assert_eq!(
+        policy.check_protocol_fee_recipient(&test_pubkey(8)),
+        Err(AuctionError::InvalidProtocolFeeRecipient)
+    );
+
+    let disabled = ConfigPolicyV2Patch::ProtocolFee {
+        protocol_fee_bps: 0,
+        protocol_min_fee_lamports: 0,
+        protocol_fee_recipient: Pubkey::default(),
+    };
+    policy.apply(&disabled, &admin).unwrap();
+    assert_eq!(policy.check_protocol_fee_recipient(&test_pubkey(8)), Ok(()));
+}
+
+#[test]
+fn payout_split_takes_fee_from_winner_and_refunds_the_rest() {
+    let policy = policy_with_fee(500, 0);
+
+    assert_eq!(
+        BundleEscrowV2Payout::split(
+            &policy,
+            &BundleEscrowV2Denomination::Lamports,
+            10_000,
+            6_000,
+            1_500
+        ),
+        Ok(BundleEscrowV2Payout {
+            winner_amount: 5_700,
+            protocol_fee_amount: 300,
+            verifier_reward_amount: 1_500,
+            requester_refund_amount: 2_500,
+        })
+    );
+    assert_eq!(
+        BundleEscrowV2Payout::split(
+            &policy,
+            &BundleEscrowV2Denomination::Lamports,
+            10_000,
+            9_000,
+            1_500
+        ),
+        Err(AuctionError::InsufficientEscrowBalance)
+    );
+
+    let mut escrow = BundleEscrowV2 {
+        status: BundleEscrowV2Status::FinalizedVerified,
+        escrow_lamports: 10_000,
+        winner_payout_lamports: 6_000,
+        ..Default::default()
+    };
+    escrow.verifier_reward_remaining[0] = 1_000;
+    escrow.verifier_reward_remaining[2] = 500;
+    let mut bytes = vec![0; BundleEscrowV2::LEN_V2];
+    assert!(escrow.write_v2_bytes(&mut bytes));
+    assert_eq!(
+        BundleEscrowV2::from_bytes(&bytes).unwrap().payout(&policy),
+        BundleEscrowV2Payout::split(
+            &policy,
+            &BundleEscrowV2Denomination::Lamports,
+            10_000,
+            6_000,
+            1_500
+        )
+    );
+}
+
+#[test]
+fn v3_escrow_records_protocol_fee_next_to_policy_snapshot() {
+    let policy = policy_with_fee(500, 0);
+    assert_eq!(
+        policy.configured_bundle_escrow_v2_layout_version(),
+        Ok(AccountLayoutVersion::V5)
+    );
+
+    let escrow = BundleEscrowV2 {
+        status: BundleEscrowV2Status::ResultPosted,
+        bundle_version: 3,
+        ..Default::default()
+    };
+    let snapshot = BundleEscrowV2PolicySnapshot::capture(&policy, RequestTier::Standard);
+
+    let mut v3_bytes = vec![0; BundleEscrowV2::account_len(AccountLayoutVersion::V3)];
+    assert!(escrow.write_v3_bytes(&mut v3_bytes));
+    let mut account = BundleEscrowV2::from_bytes_mut(&mut v3_bytes).unwrap();
+    assert_eq!(account.protocol_fee_lamports(), Some(0));
+    assert!(account.set_policy_snapshot(&snapshot));
+    assert!(account.set_protocol_fee_lamports(300));
+
+    let account = BundleEscrowV2::from_bytes(&v3_bytes).unwrap();
+    assert_eq!(account.layout().version, AccountLayoutVersion::V3);
+    assert_eq!(account.bundle_version, 3);
+    assert_eq!(account.policy_snapshot(), Some(snapshot));
+    assert_eq!(account.protocol_fee_lamports(), Some(300));
+
+    let mut v2_bytes = vec![0; BundleEscrowV2::LEN_V2];
+    assert!(escrow.write_v2_bytes(&mut v2_bytes));
+    let mut account = BundleEscrowV2::from_bytes_mut(&mut v2_bytes).unwrap();
+    assert!(!account.set_protocol_fee_lamports(300));
+    assert_eq!(account.protocol_fee_lamports(), None);
+}
+
+#[test]
+fn v3_payout_pays_the_fee_recorded_at_finalize() {
+    let policy = policy_with_fee(500, 0);
+    let mut escrow = BundleEscrowV2 {
+        status: BundleEscrowV2Status::FinalizedVerified,
+        escrow_lamports: 10_000,
+        winner_payout_lamports: 6_000,
+        ..Default::default()
+    };
+    escrow.verifier_reward_remaining[0] = 1_500;
+
+    let mut bytes = vec![0; BundleEscrowV2::account_len(AccountLayoutVersion::V3)];
+    assert!(escrow.write_v3_bytes(&mut bytes));
+    let mut account = BundleEscrowV2::from_bytes_mut(&mut bytes).unwrap();
+    let at_finalize = account.finalize_payout(&policy).unwrap();
+    assert_eq!(at_finalize.protocol_fee_amount, 300);
+    assert!(account.set_protocol_fee_lamports(at_finalize.protocol_fee_amount));
+
+    let raised = policy_with_fee(2_000, 0);
+    let account = BundleEscrowV2::from_bytes(&bytes).unwrap();
+    assert_eq!(account.payout(&raised), Ok(at_finalize));
+    assert_eq!(
+        account
+            .finalize_payout(&raised)
+            .unwrap()
+            .protocol_fee_amount,
+        1_200
+    );
+
+    let mut account = BundleEscrowV2::from_bytes_mut(&mut bytes).unwrap();
+    assert!(account.set_protocol_fee_lamports(6_001));
+    assert_eq!(
+        account.payout(&policy),
+        Err(AuctionError::InsufficientEscrowBalance)
+    );
+
+    let mut v2_bytes = vec![0; BundleEscrowV2::LEN_V2];
+    assert!(escrow.write_v2_bytes(&mut v2_bytes));
+    let account = BundleEscrowV2::from_bytes(&v2_bytes).unwrap();
+    assert_eq!(account.payout(&raised).unwrap().protocol_fee_amount, 1_200);
+}
+
+#[test]
+fn finalize_v2_accounts_take_fee_recipient_before_verifier_pages() {
+    let accounts = [1_u8, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13];
+    let parsed = FinalizeBundleVerificationV2Accounts::try_from(&accounts[..])
+        .unwrap()
+        .with_verifier_count(3)
+        .unwrap();
+
+    assert_eq!(*parsed.config_policy, 6);
+    assert_eq!(*parsed.protocol_fee_recipient, 7);
+    assert_eq!(parsed.bundle_verifier_pages, &[12, 13]);
+    assert_eq!(parsed.iter().copied().collect::<Vec<_>>(), accounts);
+
+    assert!(matches!(
+        FinalizeBundleVerificationV2Accounts::try_from(&accounts[..6]),
+        Err(AuctionError::NotEnoughAccounts)
+    ));
+}
@@ -0,0 +1,233 @@
+use ambient_auction_api::{
+    error::AuctionError, AccountLayoutVersion, AuctionInstruction, BundleEscrowV2,
+    BundleEscrowV2PolicySnapshot, BundleEscrowV2RunnerUp, BundleEscrowV2RunnerUps,
+    BundleEscrowV2Status, BundleEscrowV2TokenDenomination, BundleEscrowV2VerifierSet,
+    ConfigPolicyV2, ConfigPolicyV2PauseGroup, Pubkey, RequestTier, MAX_BUNDLE_ESCROW_V2_RUNNER_UPS,
+};
+
+fn test_pubkey(byte: u8) -> Pubkey {
+    [byte; 32].into()
+}
+
+fn awarded_escrow() -> BundleEscrowV2 {
+    BundleEscrowV2 {
+        status: BundleEscrowV2Status::Awarded,
+        escrow_lamports: 1_000_000,
+        max_output_tokens: 1_000,
+        winner_node_pubkey: test_pubkey(1),
+        winner_vote_account: test_pubkey(2),
+        clearing_price_per_output_token: 100,
+        result_deadline_slot: 200,
+        verification_deadline_slot: 300,
+        claim_deadline_slot: 400,
+        ..Default::default()
+    }
+}
+
+/// Snapshot of a 50-slot result window, shorter than any production default.
+fn snapshot() -> BundleEscrowV2PolicySnapshot {
+    BundleEscrowV2PolicySnapshot {
+        result_window_slots: 50,
+        captured: 1,
+        ..Default::default()
+    }
+}
+
+fn runner_up(byte: u8, clearing_price_per_output_token: u64) -> BundleEscrowV2RunnerUp {
+    BundleEscrowV2RunnerUp {
+        node_pubkey: test_pubkey(byte),
+        vote_account: test_pubkey(byte + 100),
+        clearing_price_per_output_token,
+    }
+}
+
+fn no_verifiers() -> BundleEscrowV2VerifierSet {
+    BundleEscrowV2VerifierSet::default()
+}
+
+#[test]
+fn v5_escrow_stores_runner_ups_after_token_denomination() {
+    let escrow = awarded_escrow();
+    let mut bytes = vec![0; BundleEscrowV2::account_len(AccountLayoutVersion::V5)];
+    assert_eq!(
+        bytes.len(),
+        BundleEscrowV2::LEN_V4 + BundleEscrowV2RunnerUps::LEN
+    );
+    assert!(escrow.write_v5_bytes(&mut bytes));
+
+    let runner_ups =
+        BundleEscrowV2RunnerUps::new(&escrow, &no_verifiers(), &[runner_up(3, 120)]).unwrap();
+    let mut account = BundleEscrowV2::from_bytes_mut(&mut bytes).unwrap();
+    assert!(account.set_runner_ups(&runner_ups));
+    assert!(account.set_token_denomination(&BundleEscrowV2TokenDenomination::default()));
+
+    let account = BundleEscrowV2::from_bytes(&bytes).unwrap();
+    assert_eq!(account.layout().version, AccountLayoutVersion::V5);
+    assert_eq!(account.runner_ups(), Some(runner_ups));
+
+    let mut v4_bytes = vec![0; BundleEscrowV2::LEN_V4];
+    assert!(escrow.write_v4_bytes(&mut v4_bytes));
+    let mut account = BundleEscrowV2::from_bytes_mut(&mut v4_bytes).unwrap();
+    assert!(!account.set_runner_ups(&runner_ups));
+    assert_eq!(account.runner_ups(), None);
+}
+
+#[test]
+fn runner_ups_must_be_ranked_and_payable() {
+    let escrow = awarded_escrow();
+    let verifiers = BundleEscrowV2VerifierSet::new(&[test_pubkey(5), test_pubkey(6)]).unwrap();
+    for runner_ups in [
+        vec![runner_up(3, 90)],
+        vec![runner_up(3, 120), runner_up(4, 110)],
+        vec![runner_up(3, 1_001)],
+        vec![runner_up(1, 120)],
+        vec![runner_up(3, 120); MAX_BUNDLE_ESCROW_V2_RUNNER_UPS + 1],
+        vec![runner_up(3, 120), runner_up(3, 150)],
+        vec![runner_up(3, 120), runner_up(6, 150)],
+    ] {
+        assert_eq!(
+            BundleEscrowV2RunnerUps::new(&escrow, &verifiers, &runner_ups),
+            Err(AuctionError::InvalidRunnerUpsV2)
+        );
+    }
+    assert!(BundleEscrowV2RunnerUps::new(
+        &escrow,
+        &verifiers,
+        &[runner_up(3, 120), runner_up(4, 150)]
+    )
+    .is_ok());
+}
+
+#[test]
+fn reassign_winner_rejects_runner_ups_selected_as_verifiers() {
+    let escrow = BundleEscrowV2 {
+        selected_verifiers: [test_pubkey(3), test_pubkey(5), test_pubkey(6)],
+        ..awarded_escrow()
+    };
+    let mut bytes = vec![0; BundleEscrowV2::LEN_V5];
+    assert!(escrow.write_v5_bytes(&mut bytes));
+    // Ranked before the verifiers were selected.
+    let runner_ups =
+        BundleEscrowV2RunnerUps::new(&escrow, &no_verifiers(), &[runner_up(3, 120)]).unwrap();
+    let mut account = BundleEscrowV2::from_bytes_mut(&mut bytes).unwrap();
+    assert!(account.set_runner_ups(&runner_ups));
+
+    let policy = ConfigPolicyV2::production_default();
+    assert_eq!(
+        account.reassign_winner(250, &policy),
+        Err(AuctionError::InvalidRunnerUpsV2)
+    );
+    assert_eq!(account.winner_node_pubkey, test_pubkey(1));
+}
+
+#[test]
+fn reassign_winner_promotes_runner_ups_in_order() {
+    let escrow = awarded_escrow();
+    let mut bytes = vec![0; BundleEscrowV2::LEN_V5];
+    assert!(escrow.write_v5_bytes(&mut bytes));
+    let runner_ups = BundleEscrowV2RunnerUps::new(
+        &escrow,
+        &no_verifiers(),
+        &[runner_up(3, 120), runner_up(4, 150)],
+    )
+    .unwrap();
+    let mut account = BundleEscrowV2::from_bytes_mut(&mut bytes).unwrap();
+    assert!(account.set_runner_ups(&runner_ups));
+    assert!(account.set_policy_snapshot(&snapshot()));
+
+    let policy = ConfigPolicyV2::production_default();
+    assert_eq!(
+        account.reassign_winner(200, &policy),
+        Err(AuctionError::DeadlineNotReached)
+    );
+    assert_eq!(account.reassign_winner(250, &policy), Ok(test_pubkey(1)));
+    assert_eq!(account.winner_node_pubkey, test_pubkey(3));
+    assert_eq!(account.winner_vote_account, test_pubkey(103));
+    assert_eq!(account.clearing_price_per_output_token, 120);
+    assert_eq!(account.result_deadline_slot, 300);
+    assert_eq!(account.verification_deadline_slot, 400);
+    assert_eq!(account.claim_deadline_slot, 500);
+
+    assert_eq!(account.reassign_winner(301, &policy), Ok(test_pubkey(3)));
+    assert_eq!(account.winner_node_pubkey, test_pubkey(4));
+    assert_eq!(
+        account.runner_ups().unwrap().defaulted_winners(),
+        &[test_pubkey(1), test_pubkey(3)]
+    );
+    assert_eq!(
+        account.reassign_winner(400, &policy),
+        Err(AuctionError::NoRunnerUpAvailable)
+    );
+
+    account.status = BundleEscrowV2Status::ResultPosted;
+    assert_eq!(
+        account.reassign_winner(400, &policy),
+        Err(AuctionError::InvalidBundleEscrowV2Status)
+    );
+}
+
+#[test]
+fn reassign_winner_falls_back_to_the_live_policy_without_a_snapshot() {
+    let escrow = awarded_escrow();
+    let mut bytes = vec![0; BundleEscrowV2::LEN_V5];
+    assert!(escrow.write_v5_bytes(&mut bytes));
+    let runner_ups =
+        BundleEscrowV2RunnerUps::new(&escrow, &no_verifiers(), &[runner_up(3, 120)]).unwrap();
+    let mut account = BundleEscrowV2::from_bytes_mut(&mut bytes).unwrap();
+    assert!(account.set_runner_ups(&runner_ups));
+
+    let policy = ConfigPolicyV2::production_default();
+    let result_window_slots = policy.tier_config(RequestTier::Eco).result_window_slots;
+    assert_eq!(account.reassign_winner(250, &policy), Ok(test_pubkey(1)));
+    assert_eq!(account.result_deadline_slot, 250 + result_window_slots);
+}
+
+#[test]
+fn rewriting_an_escrow_keeps_its_reserved_tail() {
+    let mut escrow = awarded_escrow();
+    let mut bytes = vec![0; BundleEscrowV2::LEN_V5];
+    assert!(escrow.write_v5_bytes(&mut bytes));
+    let runner_ups =
+        BundleEscrowV2RunnerUps::new(&escrow, &no_verifiers(), &[runner_up(3, 120)]).unwrap();
+    let mut account = BundleEscrowV2::from_bytes_mut(&mut bytes).unwrap();
+    assert!(account.set_policy_snapshot(&snapshot()));
+    assert!(account.set_runner_ups(&runner_ups));
+
+    escrow.status = BundleEscrowV2Status::ResultPosted;
+    assert!(escrow.write_v5_bytes(&mut bytes));
+    let account = BundleEscrowV2::from_bytes(&bytes).unwrap();
+    assert_eq!(account.status, BundleEscrowV2Status::ResultPosted);
+    assert_eq!(account.policy_snapshot(), Some(snapshot()));
+    assert_eq!(account.runner_ups(), Some(runner_ups));
+
+    // A different layout starts from a zeroed tail.
+    let mut v4_bytes = bytes[..BundleEscrowV2::LEN_V4].to_vec();
+    assert!(escrow.write_v4_bytes(&mut v4_bytes));
+    let account = BundleEscrowV2::from_bytes(&v4_bytes).unwrap();
+    assert_eq!(account.policy_snapshot(), None);
+}
+
+#[test]
+fn escrows_expire_after_the_deadline_of_their_stage() {
+    let mut escrow = awarded_escrow();
+    assert!(!escrow.is_expired(200));
+    assert!(escrow.is_expired(201));
+    escrow.status = BundleEscrowV2Status::ResultPosted;
+    assert!(!escrow.is_expired(300));
+    assert!(escrow.is_expired(301));
+    escrow.status = BundleEscrowV2Status::Expired;
+    assert!(!escrow.is_expired(u64::MAX));
+}
+
+#[test]
+fn runner_up_instructions_are_settlement_instructions() {
+    for instruction in [
+        AuctionInstruction::CommitRunnerUpsV2,
+        AuctionInstruction::ReassignWinnerV2,
+    ] {
+        assert_eq!(
+            instruction.pause_group(),
+            Some(ConfigPolicyV2PauseGroup::Settlement)
+        );
+    }
+}
@@ -0,0 +1,508 @@
+use ambient_auction_api::{
+    error::AuctionError, AuctionInstruction, ConfigPolicyV2, ConfigPolicyV2Action,
+    ConfigPolicyV2AuthorityKind, ConfigPolicyV2AuthoritySlot, ConfigPolicyV2Flag,
+    ConfigPolicyV2Flags, ConfigPolicyV2Patch, ConfigPolicyV2PatchKind, ConfigPolicyV2PauseGroup,
+    ConfigPolicyV2PauseGroups, Pubkey, RawConfigPolicyV2ScheduleData, RequestTier,
+    RequestTierConfigV2, SetConfigPolicyV2Args,
+};
+
+fn test_pubkey(byte: u8) -> Pubkey {
+    [byte; 32].into()
+}
+
+fn policy_with_admin(admin: Pubkey) -> ConfigPolicyV2 {
+    let mut policy = ConfigPolicyV2::production_default();
+    policy.admin_authorities[0] = admin;
+    policy
+}
+
+/// Schedules `patch` with the shortest activation delay and activates it once due.
+fn apply_scheduled(
+    policy: &mut ConfigPolicyV2,
+    patch: &ConfigPolicyV2Patch,
+    signer: &Pubkey,
+) -> Result<(), AuctionError> {
+    let mut schedule = RawConfigPolicyV2ScheduleData::new(test_pubkey(50), 255, 1);
+    schedule.schedule(policy, signer, patch, 0, 1)?;
+    schedule.activate_due(policy, 1).map(|_| ())
+}
+
+fn sample_patches() -> Vec<ConfigPolicyV2Patch> {
+    vec![
+        ConfigPolicyV2Patch::Flags(ConfigPolicyV2Flag::AllowServiceCommitOverride.mask()),
+        ConfigPolicyV2Patch::Authority {
+            kind: ConfigPolicyV2AuthorityKind::SERVICE,
+            index: 3,
+            authority: test_pubkey(9),
+        },
+        ConfigPolicyV2Patch::VerifierSettings {
+            v2_verifiers_per_auction: 3,
+            v2_verifier_quorum: 3,
+        },
+        ConfigPolicyV2Patch::TierConfig {
+            tier: RequestTier::Pro,
+            tier_config: RequestTierConfigV2 {
+                result_window_slots: 64,
+                ..RequestTierConfigV2::production_default_for_tier(RequestTier::Pro)
+            },
+        },
+        ConfigPolicyV2Patch::MaxAuctionCreditsPerUpdate(7),
+        ConfigPolicyV2Patch::PausedInstructionGroups(ConfigPolicyV2PauseGroup::Payout.mask()),
+        ConfigPolicyV2Patch::ProtocolFee {
+            protocol_fee_bps: 250,
+            protocol_min_fee_lamports: 5_000,
+            protocol_fee_recipient: test_pubkey(8),
+        },
+        ConfigPolicyV2Patch::NodePenalties {
+            rejected_result_penalty_bps: 1_000,
+            missed_result_penalty_bps: 500,
+            missed_verification_penalty_bps: 100,
+        },
+    ]
+}
+
+#[test]
+fn config_policy_v2_args_keep_their_original_size() {
+    assert_eq!(std::mem::size_of::<SetConfigPolicyV2Args>(), 160);
+
+    let args = SetConfigPolicyV2Args::from(ConfigPolicyV2Patch::ProtocolFee {
+        protocol_fee_bps: 250,
+        protocol_min_fee_lamports: 5_000,
+        protocol_fee_recipient: test_pubkey(8),
+    });
+    let data = bytemuck::bytes_of(&args);
+    assert_eq!(SetConfigPolicyV2Args::try_from(data), Ok(args));
+    assert_eq!(args.fee_args().protocol_fee_recipient, test_pubkey(8));
+    assert_eq!(args.authority, Pubkey::default());
+}
+
+#[test]
+fn config_policy_v2_patch_round_trips_through_args() {
+    for patch in sample_patches() {
+        let args = SetConfigPolicyV2Args::from(patch);
+        assert_eq!(args.patch_kind, patch.kind());
+        assert_eq!(ConfigPolicyV2Patch::try_from(&args), Ok(patch));
+    }
+}
+
+#[test]
+fn config_policy_v2_patch_rejects_garbage_in_unused_fields() {
+    let mut args = ConfigPolicyV2Patch::Flags(ConfigPolicyV2Flags::empty()).to_args();
+    args.max_auction_credits_per_update = 1;
+    assert_eq!(
+        ConfigPolicyV2Patch::try_from(&args),
+        Err(AuctionError::InvalidConfigPolicyV2Patch)
+    );
+
+    let mut args = ConfigPolicyV2Patch::MaxAuctionCreditsPerUpdate(1).to_args();
+    args.authority = test_pubkey(1);
+    assert_eq!(
+        ConfigPolicyV2Patch::try_from(&args),
+        Err(AuctionError::InvalidConfigPolicyV2Patch)
+    );
+
+    let mut args = sample_patches()[3].to_args();
+    args.tier = 99;
+    assert_eq!(
+        ConfigPolicyV2Patch::try_from(&args),
+        Err(AuctionError::InvalidConfigPolicyV2Patch)
+    );
+
+    let mut args = sample_patches()[0].to_args();
+    args.patch_kind = ConfigPolicyV2PatchKind(42);
+    assert_eq!(
+        ConfigPolicyV2Patch::try_from(&args),
+        Err(AuctionError::InvalidConfigPolicyV2Patch)
+    );
+}
+
+#[test]
+fn config_policy_v2_apply_requires_admin_signer() {
+    let mut policy = policy_with_admin(test_pubkey(1));
+    let patch = ConfigPolicyV2Patch::MaxAuctionCreditsPerUpdate(5);
+
+    assert_eq!(
+        policy.apply(&patch, &test_pubkey(2)),
+        Err(AuctionError::UnauthorizedConfigPolicyAuthority)
+    );
+    assert_eq!(
+        policy.apply(&patch, &Pubkey::default()),
+        Err(AuctionError::UnauthorizedConfigPolicyAuthority)
+    );
+    policy.apply(&patch, &test_pubkey(1)).unwrap();
+    assert_eq!(policy.max_auction_credits_per_update, 5);
+}
+
+#[test]
+fn config_policy_v2_apply_validates_patch_values() {
+    let admin = test_pubkey(1);
+    let mut policy = policy_with_admin(admin);
+
+    assert_eq!(
+        apply_scheduled(
+            &mut policy,
+            &ConfigPolicyV2Patch::VerifierSettings {
+                v2_verifiers_per_auction: 4,
+                v2_verifier_quorum: 2,
+            },
+            &admin,
+        ),
+        Err(AuctionError::InvalidVerifierCount)
+    );
+    assert_eq!(
+        apply_scheduled(
+            &mut policy,
+            &ConfigPolicyV2Patch::VerifierSettings {
+                v2_verifiers_per_auction: 2,
+                v2_verifier_quorum: 3,
+            },
+            &admin,
+        ),
+        Err(AuctionError::InvalidVerifierQuorum)
+    );
+    assert_eq!(
+        apply_scheduled(
+            &mut policy,
+            &ConfigPolicyV2Patch::TierConfig {
+                tier: RequestTier::Eco,
+                tier_config: RequestTierConfigV2 {
+                    claim_window_slots: 0,
+                    ..RequestTierConfigV2::production_default_for_tier(RequestTier::Eco)
+                },
+            },
+            &admin,
+        ),
+        Err(AuctionError::InvalidTierConfig)
+    );
+    assert_eq!(
+        policy.apply(
+            &ConfigPolicyV2Patch::Authority {
+                kind: ConfigPolicyV2AuthorityKind::ADMIN,
+                index: 8,
+                authority: test_pubkey(2),
+            },
+            &admin,
+        ),
+        Err(AuctionError::InvalidConfigPolicyV2Patch)
+    );
+    assert_eq!(policy, policy_with_admin(admin));
+}
+
+#[test]
+fn config_policy_v2_diff_patches_reproduce_target_policy() {
+    let admin = test_pubkey(1);
+    let old = policy_with_admin(admin);
+    let mut new = old;
+    for patch in sample_patches() {
+        if patch.affects_in_flight_escrows() {
+            assert_eq!(
+                new.apply(&patch, &admin),
+                Err(AuctionError::ConfigPolicyV2PatchMustBeScheduled)
+            );
+            apply_scheduled(&mut new, &patch, &admin).unwrap();
+        } else {
+            new.apply(&patch, &admin).unwrap();
+        }
+    }
+    new.admin_authorities[1] = test_pubkey(2);
+
+    let diff = ConfigPolicyV2::diff(&old, &new);
+    assert_eq!(
+        diff.direct.len() + diff.scheduled.len(),
+        sample_patches().len() + 1
+    );
+    assert!(diff
+        .scheduled
+        .iter()
+        .all(ConfigPolicyV2Patch::affects_in_flight_escrows));
+
+    let mut migrated = old;
+    for patch in &diff.direct {
+        let decoded = ConfigPolicyV2Patch::try_from(&patch.to_args()).unwrap();
+        migrated.apply(&decoded, &admin).unwrap();
+    }
+    let mut schedule = RawConfigPolicyV2ScheduleData::new(test_pubkey(50), 255, 1);
+    for patch in &diff.scheduled {
+        let decoded = ConfigPolicyV2Patch::try_from(&patch.to_args()).unwrap();
+        schedule
+            .schedule(&migrated, &admin, &decoded, 10, 11)
+            .unwrap();
+    }
+    assert_eq!(
+        schedule.activate_due(&mut migrated, 11),
+        Ok(diff.scheduled.len())
+    );
+    assert_eq!(migrated, new);
+    assert!(ConfigPolicyV2::diff(&migrated, &new).is_empty());
+}
+
+#[test]
+fn config_policy_v2_diff_lets_the_signing_admin_rotate_itself_out() {
+    let admin = test_pubkey(1);
+    let old = policy_with_admin(admin);
+    let mut new = old;
+    new.admin_authorities[0] = test_pubkey(2);
+    new.admin_authorities[1] = test_pubkey(3);
+    new.policy_flags = ConfigPolicyV2Flag::AllowServiceCommitOverride.mask();
+    new.max_auction_credits_per_update = 7;
+
+    let patches = ConfigPolicyV2::diff(&old, &new).direct;
+    assert_eq!(
+        patches.last(),
+        Some(&ConfigPolicyV2Patch::Authority {
+            kind: ConfigPolicyV2AuthorityKind::ADMIN,
+            index: 0,
+            authority: test_pubkey(2),
+        })
+    );
+
+    let mut migrated = old;
+    for patch in &patches {
+        migrated.apply(patch, &admin).unwrap();
+    }
+    assert_eq!(migrated, new);
+    assert!(!migrated.is_admin(&admin));
+}
+
+#[test]
+fn config_policy_v2_add_and_remove_authorities_pick_slots() {
+    let admin = test_pubkey(1);
+    let mut policy = policy_with_admin(admin);
+
+    assert_eq!(
+        policy.add_authority(ConfigPolicyV2AuthorityKind::ADMIN, test_pubkey(2)),
+        Ok(1)
+    );
+    assert_eq!(
+        policy.add_authority(ConfigPolicyV2AuthorityKind::SERVICE, test_pubkey(3)),
+        Ok(0)
+    );
+    assert_eq!(
+        policy.add_authority(ConfigPolicyV2AuthorityKind::ADMIN, test_pubkey(2)),
+        Err(AuctionError::DuplicateConfigPolicyAuthority)
+    );
+    assert_eq!(
+        policy.add_authority(ConfigPolicyV2AuthorityKind::ADMIN, Pubkey::default()),
+        Err(AuctionError::InvalidConfigPolicyAuthority)
+    );
+    assert!(policy.is_admin(&test_pubkey(2)));
+    assert!(policy.is_service(&test_pubkey(3)));
+    assert!(!policy.is_service(&Pubkey::default()));
+
+    assert_eq!(
+        policy.authority_slots().collect::<Vec<_>>(),
+        vec![
+            ConfigPolicyV2AuthoritySlot {
+                kind: ConfigPolicyV2AuthorityKind::ADMIN,
+                index: 0,
+                authority: admin,
+            },
+            ConfigPolicyV2AuthoritySlot {
+                kind: ConfigPolicyV2AuthorityKind::ADMIN,
+                index: 1,
+                authority: test_pubkey(2),
+            },
+            ConfigPolicyV2AuthoritySlot {
+                kind: ConfigPolicyV2AuthorityKind::SERVICE,
+                index: 0,
+                authority: test_pubkey(3),
+            },
+        ]
+    );
+
+    assert_eq!(
+        policy.remove_authority(ConfigPolicyV2AuthorityKind::ADMIN, &admin),
+        Ok(0)
+    );
+    assert_eq!(
+        policy.add_authority(ConfigPolicyV2AuthorityKind::ADMIN, test_pubkey(4)),
+        Ok(0)
+    );
+    assert_eq!(
+        policy.remove_authority(ConfigPolicyV2AuthorityKind::SERVICE, &test_pubkey(9)),
+        Err(AuctionError::ConfigPolicyAuthorityNotFound)
+    );
+
+    for byte in 10..16 {
+        policy
+            .add_authority(ConfigPolicyV2AuthorityKind::ADMIN, test_pubkey(byte))
+            .unwrap();
+    }
+    assert_eq!(
+        policy.add_authority(ConfigPolicyV2AuthorityKind::ADMIN, test_pubkey(16)),
+        Err(AuctionError::ConfigPolicyAuthoritySlotsFull)
+    );
+}
+
+#[test]
+fn config_policy_v2_authority_keys_hold_one_slot() {
+    let admin = test_pubkey(1);
+    let mut policy = policy_with_admin(admin);
+    policy.service_authorities[0] = test_pubkey(2);
+
+    assert_eq!(
+        policy.add_authority(ConfigPolicyV2AuthorityKind::SERVICE, admin),
+        Err(AuctionError::DuplicateConfigPolicyAuthority)
+    );
+    for (kind, index, authority) in [
+        (ConfigPolicyV2AuthorityKind::ADMIN, 1, admin),
+        (ConfigPolicyV2AuthorityKind::SERVICE, 1, admin),
+        (ConfigPolicyV2AuthorityKind::ADMIN, 1, test_pubkey(2)),
+        (ConfigPolicyV2AuthorityKind::SERVICE, 1, test_pubkey(2)),
+    ] {
+        let patch = ConfigPolicyV2Patch::Authority {
+            kind,
+            index,
+            authority,
+        };
+        assert_eq!(
+            policy.apply(&patch, &admin),
+            Err(AuctionError::DuplicateConfigPolicyAuthority)
+        );
+    }
+    assert_eq!(policy.admin_count(), 1);
+    assert_eq!(policy.service_authorities[1], Pubkey::default());
+
+    // Rewriting a key into the slot it already holds is not a duplicate.
+    let rewrite = ConfigPolicyV2Patch::Authority {
+        kind: ConfigPolicyV2AuthorityKind::SERVICE,
+        index: 0,
+        authority: test_pubkey(2),
+    };
+    assert_eq!(policy.apply(&rewrite, &admin), Ok(()));
+}
+
+#[test]
+fn config_policy_v2_keeps_last_admin() {
+    let admin = test_pubkey(1);
+    let mut policy = policy_with_admin(admin);
+
+    assert_eq!(
+        policy.remove_authority(ConfigPolicyV2AuthorityKind::ADMIN, &admin),
+        Err(AuctionError::LastConfigPolicyAdmin)
+    );
+    assert_eq!(
+        policy.apply(
+            &ConfigPolicyV2Patch::Authority {
+                kind: ConfigPolicyV2AuthorityKind::ADMIN,
+                index: 0,
+                authority: Pubkey::default(),
+            },
+            &admin,
+        ),
+        Err(AuctionError::LastConfigPolicyAdmin)
+    );
+    assert_eq!(policy, policy_with_admin(admin));
+
+    policy
+        .apply(
+            &ConfigPolicyV2Patch::Authority {
+                kind: ConfigPolicyV2AuthorityKind::ADMIN,
+                index: 0,
+                authority: test_pubkey(2),
+            },
+            &admin,
+        )
+        .unwrap();
+    assert!(policy.is_admin(&test_pubkey(2)));
+    assert!(!policy.is_admin(&admin));
+}
+
+#[test]
+fn config_policy_v2_can_combines_authority_kind_and_flags() {
+    let admin = test_pubkey(1);
+    let service = test_pubkey(2);
+    let mut policy = policy_with_admin(admin);
+    policy.service_authorities[0] = service;
+
+    assert!(policy.can(&admin, ConfigPolicyV2Action::UpdatePolicy));
+    assert!(!policy.can(&service, ConfigPolicyV2Action::UpdatePolicy));
+    assert!(!policy.can(&service, ConfigPolicyV2Action::CommitOverride));
+
+    policy.policy_flags = ConfigPolicyV2Flag::AllowServiceCommitOverride.mask();
+    assert!(policy.can(&service, ConfigPolicyV2Action::CommitOverride));
+    assert!(!policy.can(&admin, ConfigPolicyV2Action::CommitOverride));
+    assert!(!policy.can(&service, ConfigPolicyV2Action::FinalizeOverride));
+}
+
+#[test]
+fn config_policy_v2_pause_groups_gate_instructions() {
+    let admin = test_pubkey(1);
+    let mut policy = policy_with_admin(admin);
+    assert!(!policy.is_instruction_paused(AuctionInstruction::RequestJob));
+
+    let intake = ConfigPolicyV2PauseGroup::Intake.mask();
+    policy
+        .apply(
+            &ConfigPolicyV2Patch::PausedInstructionGroups(intake),
+            &admin,
+        )
+        .unwrap();
+
+    for instruction in [
+        AuctionInstruction::RequestJob,
+        AuctionInstruction::PlaceBid,
+        AuctionInstruction::OpenBundleEscrowV2,
+    ] {
+        assert!(policy.is_instruction_paused(instruction));
+        assert_eq!(
+            policy.check_instruction_not_paused(instruction),
+            Err(AuctionError::ProtocolPaused)
+        );
+    }
+    for instruction in [
+        AuctionInstruction::ClaimWinnerLstakeV2,
+        AuctionInstruction::ExpireBundleEscrowV2,
+        AuctionInstruction::FinalizeBundleVerificationV2,
+        AuctionInstruction::SetConfigPolicyV2,
+    ] {
+        assert_eq!(policy.check_instruction_not_paused(instruction), Ok(()));
+    }
+
+    assert_eq!(
+        policy.apply(
+            &ConfigPolicyV2Patch::PausedInstructionGroups(ConfigPolicyV2PauseGroups::from_bits(
+                0x80
+            )),
+            &admin,
+        ),
+        Err(AuctionError::InvalidConfigPolicyV2Patch)
+    );
+    assert_eq!(
+        ConfigPolicyV2::diff(&policy_with_admin(admin), &policy).direct,
+        vec![ConfigPolicyV2Patch::PausedInstructionGroups(intake)]
+    );
+}
+
+#[test]
+fn config_policy_v2_single_admin_can_pause_but_not_unpause_under_threshold() {
+    let mut policy = policy_with_admin(test_pubkey(1));
+    policy.admin_authorities[1] = test_pubkey(2);
+    policy.admin_approval_threshold = 2;
+
+    let all = ConfigPolicyV2PauseGroup::Intake
+        .mask()
+        .union(ConfigPolicyV2PauseGroup::Settlement.mask());
+    policy
+        .apply(
+            &ConfigPolicyV2Patch::PausedInstructionGroups(all),
+            &test_pubkey(2),
+        )
+        .unwrap();
+    assert_eq!(policy.paused_instruction_groups, all);
+
+    assert_eq!(
+        policy.apply(
+            &ConfigPolicyV2Patch::PausedInstructionGroups(ConfigPolicyV2PauseGroups::empty()),
+            &test_pubkey(1),
+        ),
+        Err(AuctionError::ConfigPolicyV2ProposalRequired)
+    );
+    assert_eq!(
+        policy.apply(
+            &ConfigPolicyV2Patch::PausedInstructionGroups(all),
+            &test_pubkey(9),
+        ),
+        Err(AuctionError::UnauthorizedConfigPolicyAuthority)
+    );
+}
@@ -0,0 +1,159 @@
+use ambient_auction_api::{
+    error::AuctionError, AccountDiscriminator, AccountHeaderV1, ConfigPolicyV2, Pubkey,
+    RequestTier, RequestTierConfigV2, TierRegistryV2, TierRegistryV2Entry,
+};
+
+const XL_TIER_ID: u64 = 100;
+const LOW_LATENCY_TIER_ID: u64 = 101;
+
+fn tier_config(max_context_length_tokens: u64) -> RequestTierConfigV2 {
+    RequestTierConfigV2 {
+        max_context_length_tokens,
+        ..RequestTierConfigV2::production_default_for_tier(RequestTier::Eco)
+    }
+}
+
+fn seeded_registry(capacity: u16) -> Vec<u8> {
+    let mut bytes = vec![0u8; TierRegistryV2::account_len(capacity)];
+    let mut registry =
+        TierRegistryV2::init_bytes(&mut bytes, Pubkey::from([1; 32]), 254, capacity).unwrap();
+    registry
+        .import_policy_tiers(&ConfigPolicyV2::production_default())
+        .unwrap();
+    bytes
+}
+
+#[test]
+fn tier_registry_v2_round_trips_through_bytes() {
+    let bytes = seeded_registry(8);
+
+    let registry = TierRegistryV2::from_bytes(&bytes).unwrap();
+    assert_eq!(
+        registry.header(),
+        &AccountHeaderV1::new(AccountDiscriminator::TierRegistryV2)
+    );
+    assert_eq!(registry.config_policy, Pubkey::from([1; 32]));
+    assert_eq!(registry.bump, 254);
+    assert_eq!(registry.capacity, 8);
+    assert_eq!(registry.tier_count, 5);
+    for tier in RequestTier::ALL {
+        assert_eq!(
+            registry.tier_config(u64::from(tier)),
+            Some(&RequestTierConfigV2::production_default_for_tier(tier))
+        );
+    }
+}
+
+#[test]
+fn tier_registry_v2_rejects_malformed_bytes() {
+    let mut bytes = seeded_registry(8);
+    assert!(TierRegistryV2::from_bytes(&bytes[..bytes.len() - 1]).is_none());
+
+    bytes[0] = AccountDiscriminator::BundleEscrowV2 as u8;
+    assert!(TierRegistryV2::from_bytes(&bytes).is_none());
+}
+
+#[test]
+fn tier_registry_v2_context_tier_matches_builtin_walk() {
+    let bytes = seeded_registry(8);
+    let registry = TierRegistryV2::from_bytes(&bytes).unwrap();
+
+    for tokens in [0, 2_000, 2_001, 16_000, 32_001, 64_001, 202_752, 202_753] {
+        assert_eq!(
+            registry.context_tier_for_tokens(tokens),
+            RequestTier::context_tier_for_tokens(tokens).map(u64::from)
+        );
+    }
+}
+
+#[test]
+fn tier_registry_v2_walks_added_tiers_in_context_length_order() {
+    let mut bytes = seeded_registry(8);
+    {
+        let mut registry = TierRegistryV2::from_bytes_mut(&mut bytes).unwrap();
+        registry.upsert(XL_TIER_ID, tier_config(1_000_000)).unwrap();
+        registry
+            .upsert(LOW_LATENCY_TIER_ID, tier_config(8_000))
+            .unwrap();
+    }
+
+    let registry = TierRegistryV2::from_bytes(&bytes).unwrap();
+    assert_eq!(registry.context_tier_for_tokens(300_000), Some(XL_TIER_ID));
+    assert_eq!(
+        registry.context_tier_for_tokens(4_000),
+        Some(LOW_LATENCY_TIER_ID)
+    );
+    assert_eq!(
+        registry.context_tier_for_tokens(10_000),
+        Some(u64::from(RequestTier::Small))
+    );
+    assert_eq!(registry.context_tier_for_tokens(1_000_001), None);
+
+    let order = registry
+        .entries_by_context_length()
+        .iter()
+        .map(|entry| entry.tier_id)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        order,
+        vec![
+            u64::from(RequestTier::Eco),
+            LOW_LATENCY_TIER_ID,
+            u64::from(RequestTier::Small),
+            u64::from(RequestTier::Standard),
+            u64::from(RequestTier::Pro),
+            u64::from(RequestTier::Large),
+            XL_TIER_ID,
+        ]
+    );
+}
+
+#[test]
+fn tier_registry_v2_upsert_and_remove_keep_entries_compact() {
+    let mut bytes = seeded_registry(6);
+    let mut registry = TierRegistryV2::from_bytes_mut(&mut bytes).unwrap();
+
+    registry.upsert(XL_TIER_ID, tier_config(1_000_000)).unwrap();
+    assert_eq!(
+        registry.upsert(LOW_LATENCY_TIER_ID, tier_config(8_000)),
+        Err(AuctionError::TierRegistryFull)
+    );
+    registry
+        .upsert(u64::from(RequestTier::Eco), tier_config(3_000))
+        .unwrap();
+    assert_eq!(
+        registry
+            .tier_config(u64::from(RequestTier::Eco))
+            .unwrap()
+            .max_context_length_tokens,
+        3_000
+    );
+
+    let removed = registry.remove(u64::from(RequestTier::Small)).unwrap();
+    assert_eq!(removed.max_context_length_tokens, 16_000);
+    assert_eq!(registry.tier_count, 5);
+    assert!(registry
+        .tier_config(u64::from(RequestTier::Small))
+        .is_none());
+    assert!(registry.tier_config(XL_TIER_ID).is_some());
+    assert_eq!(
+        registry.remove(u64::from(RequestTier::Small)),
+        Err(AuctionError::TierNotFound)
+    );
+    assert_eq!(
+        registry.upsert(
+            XL_TIER_ID,
+            RequestTierConfigV2 {
+                bundle_duration: 0,
+                ..tier_config(1)
+            }
+        ),
+        Err(AuctionError::InvalidTierConfig)
+    );
+}
+
+#[test]
+fn tier_registry_v2_entry_layout_stays_stable() {
+    assert_eq!(TierRegistryV2Entry::LEN, 104);
+    assert_eq!(TierRegistryV2::PREFIX_LEN, 56);
+}
@@ -0,0 +1,182 @@
+use ambient_auction_api::{
+    error::AuctionError, AccountLayoutVersion, BundleEscrowV2, BundleEscrowV2Denomination,
+    BundleEscrowV2Payout, BundleEscrowV2Status, BundleEscrowV2TokenDenomination,
+    ClaimVerifierLstakeV2Accounts, ClaimWinnerLstakeV2Accounts, ConfigPolicyV2,
+    ExpireBundleEscrowV2Accounts, FinalizeBundleVerificationV2Accounts, InstructionAccounts,
+    OpenBundleEscrowV2Accounts, Pubkey,
+};
+
+fn test_pubkey(byte: u8) -> Pubkey {
+    [byte; 32].into()
+}
+
+fn usdc() -> BundleEscrowV2TokenDenomination {
+    BundleEscrowV2TokenDenomination {
+        mint: test_pubkey(1),
+        vault: test_pubkey(2),
+        token_program: test_pubkey(3),
+        decimals: 6,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn v4_escrow_records_token_denomination() {
+    let escrow = BundleEscrowV2 {
+        status: BundleEscrowV2Status::Open,
+        escrow_lamports: 25_000_000,
+        ..Default::default()
+    };
+    let mut bytes = vec![0; BundleEscrowV2::account_len(AccountLayoutVersion::V4)];
+    assert_eq!(
+        bytes.len(),
+        BundleEscrowV2::LEN_V3 + BundleEscrowV2TokenDenomination::LEN
+    );
+    assert!(escrow.write_v4_bytes(&mut bytes));
+
+    let mut account = BundleEscrowV2::from_bytes_mut(&mut bytes).unwrap();
+    assert_eq!(account.denomination(), BundleEscrowV2Denomination::Lamports);
+    assert!(account.set_token_denomination(&usdc()));
+    assert!(account.set_protocol_fee_lamports(12));
+
+    let account = BundleEscrowV2::from_bytes(&bytes).unwrap();
+    assert_eq!(account.layout().version, AccountLayoutVersion::V4);
+    assert_eq!(account.token_denomination(), Some(usdc()));
+    assert_eq!(
+        account.denomination(),
+        BundleEscrowV2Denomination::Token(usdc())
+    );
+    assert_eq!(account.protocol_fee_lamports(), Some(12));
+    assert_eq!(account.escrow_lamports, 25_000_000);
+
+    let mut v3_bytes = vec![0; BundleEscrowV2::LEN_V3];
+    assert!(escrow.write_v3_bytes(&mut v3_bytes));
+    let mut account = BundleEscrowV2::from_bytes_mut(&mut v3_bytes).unwrap();
+    assert!(!account.set_token_denomination(&usdc()));
+    assert_eq!(account.token_denomination(), None);
+}
+
+#[test]
+fn token_denomination_checks_vault_and_token_program() {
+    let denomination = usdc();
+
+    assert_eq!(
+        denomination.check_accounts(&test_pubkey(2), &test_pubkey(3)),
+        Ok(())
+    );
+    assert_eq!(
+        denomination.check_accounts(&test_pubkey(9), &test_pubkey(3)),
+        Err(AuctionError::InvalidTokenEscrowAccounts)
+    );
+    assert_eq!(
+        denomination.check_accounts(&test_pubkey(2), &test_pubkey(9)),
+        Err(AuctionError::InvalidTokenEscrowAccounts)
+    );
+}
+
+#[test]
+fn token_payout_skips_the_lamport_minimum_fee() {
+    let mut policy = ConfigPolicyV2::production_default();
+    policy.protocol_fee_bps = 100;
+    policy.protocol_min_fee_lamports = 5_000;
+    policy.protocol_fee_recipient = test_pubkey(7);
+
+    let token = BundleEscrowV2Denomination::Token(usdc());
+    assert_eq!(
+        BundleEscrowV2Payout::split(&policy, &token, 1_000_000, 200_000, 50_000),
+        Ok(BundleEscrowV2Payout {
+            winner_amount: 198_000,
+            protocol_fee_amount: 2_000,
+            verifier_reward_amount: 50_000,
+            requester_refund_amount: 750_000,
+        })
+    );
+    assert_eq!(
+        BundleEscrowV2Payout::split(&policy, &token, 1_000, 100, 0)
+            .unwrap()
+            .protocol_fee_amount,
+        1
+    );
+    assert_eq!(
+        BundleEscrowV2Payout::split(
+            &policy,
+            &BundleEscrowV2Denomination::Lamports,
+            1_000_000,
+            200_000,
+            50_000
+        )
+        .unwrap()
+        .protocol_fee_amount,
+        5_000
+    );
+}
+
+#[test]
+fn token_escrow_accounts_follow_the_native_accounts() {
+    let accounts = [1_u8, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16];
+
+    let open = OpenBundleEscrowV2Accounts::try_from(&accounts[..]).unwrap();
+    assert_eq!(open.token_program, None);
+    assert_eq!(open.iter().count(), 4);
+    let open = OpenBundleEscrowV2Accounts::try_from_token_escrow(&accounts[..]).unwrap();
+    assert_eq!(open.token_mint, Some(&6));
+    assert_eq!(open.payer_token_account, Some(&8));
+    assert_eq!(open.iter().copied().collect::<Vec<_>>(), accounts[..8]);
+
+    let finalize = FinalizeBundleVerificationV2Accounts::try_from_token_escrow(&accounts[..])
+        .unwrap()
+        .with_verifier_count(3)
+        .unwrap();
+    assert_eq!(finalize.protocol_fee_recipient, &7);
+    assert_eq!(finalize.token_program, Some(&9));
+    assert_eq!(finalize.protocol_fee_token_account, Some(&12));
+    assert_eq!(finalize.verifier_reputations, &[13, 14, 15]);
+    assert_eq!(finalize.bundle_verifier_pages, &[16]);
+    assert_eq!(finalize.iter().copied().collect::<Vec<_>>(), accounts);
+
+    let claim = ClaimWinnerLstakeV2Accounts::try_from(&accounts[..5]).unwrap();
+    assert_eq!(claim.node_penalty_record, None);
+    assert_eq!(claim.iter().count(), 5);
+    let claim = ClaimWinnerLstakeV2Accounts::try_from(&accounts[..]).unwrap();
+    assert_eq!(claim.node_penalty_record, Some(&6));
+    assert_eq!(claim.protocol_fee_recipient, Some(&7));
+    assert_eq!(claim.iter().copied().collect::<Vec<_>>(), accounts[..7]);
+    let claim = ClaimWinnerLstakeV2Accounts::try_from_token_escrow(&accounts[..8]).unwrap();
+    assert_eq!(claim.winner_token_account, Some(&8));
+    assert_eq!(claim.node_penalty_record, None);
+    let claim = ClaimWinnerLstakeV2Accounts::try_from_token_escrow(&accounts[..]).unwrap();
+    assert_eq!(claim.node_penalty_record, Some(&9));
+    assert_eq!(claim.protocol_fee_token_account, Some(&11));
+    assert_eq!(claim.iter().copied().collect::<Vec<_>>(), accounts[..11]);
+
+    let claim = ClaimVerifierLstakeV2Accounts::try_from_token_escrow(&accounts[..]).unwrap();
+    assert_eq!(claim.verifier_token_account, Some(&8));
+    assert_eq!(claim.bundle_verifier_pages, &accounts[8..]);
+    let with_penalty = claim.clone().with_verifier_page_count(5).unwrap();
+    assert_eq!(with_penalty.bundle_verifier_pages, &accounts[8..13]);
+    assert_eq!(with_penalty.node_penalty_record, Some(&14));
+    assert_eq!(with_penalty.protocol_fee_token_account, Some(&16));
+    assert_eq!(with_penalty.iter().copied().collect::<Vec<_>>(), accounts);
+    let without_penalty = claim.clone().with_verifier_page_count(8).unwrap();
+    assert_eq!(without_penalty.node_penalty_record, None);
+    assert!(matches!(
+        claim.with_verifier_page_count(6),
+        Err(AuctionError::NotEnoughAccounts)
+    ));
+    let claim = ClaimVerifierLstakeV2Accounts::try_from(&accounts[..])
+        .unwrap()
+        .with_verifier_page_count(9)
+        .unwrap();
+    assert_eq!(claim.node_penalty_record, Some(&15));
+    assert_eq!(claim.protocol_fee_recipient, Some(&16));
+    assert_eq!(claim.protocol_fee_token_account, None);
+
+    let expire = ExpireBundleEscrowV2Accounts::try_from_token_escrow(&accounts[..]).unwrap();
+    assert_eq!(expire.winner_reputation, &4);
+    assert_eq!(expire.escrow_vault, Some(&6));
+    assert_eq!(expire.requester_refund_token_account, Some(&7));
+    assert!(matches!(
+        ExpireBundleEscrowV2Accounts::try_from_token_escrow(&accounts[..6]),
+        Err(AuctionError::NotEnoughAccounts)
+    ));
+}
@@ -0,0 +1,101 @@
+use ambient_auction_api::{
+    assign_token_ranges, error::AuctionError, validate_token_ranges, verifier_reward_tokens,
+    TokenRangeStrategy, MAX_EXTENDED_VERIFIERS_PER_AUCTION, MAX_VERIFIERS_PER_AUCTION,
+};
+
+fn coverage(ranges: &[u64], output_tokens: u64) -> Vec<u32> {
+    let mut coverage = vec![0; output_tokens as usize];
+    for pair in ranges.chunks_exact(2) {
+        for token in pair[0]..pair[1] {
+            coverage[token as usize] += 1;
+        }
+    }
+    coverage
+}
+
+#[test]
+fn equal_split_covers_every_token_once() {
+    let ranges = assign_token_ranges(34, 3, TokenRangeStrategy::EqualSplit).unwrap();
+    assert_eq!(ranges[..6], [0, 12, 12, 23, 23, 34]);
+    assert!(ranges[6..].iter().all(|bound| *bound == 0));
+    assert_eq!(
+        verifier_reward_tokens(&ranges[..MAX_VERIFIERS_PER_AUCTION * 2], 34).unwrap()[..3],
+        [12, 11, 11]
+    );
+    assert!(coverage(&ranges, 34).iter().all(|count| *count == 1));
+}
+
+#[test]
+fn overlapping_ranges_check_each_token_redundancy_times() {
+    let ranges =
+        assign_token_ranges(34, 3, TokenRangeStrategy::Overlapping { redundancy: 2 }).unwrap();
+    assert_eq!(ranges[..6], [0, 17, 0, 34, 17, 34]);
+
+    for redundancy in 1..=10 {
+        let ranges =
+            assign_token_ranges(1_001, 10, TokenRangeStrategy::Overlapping { redundancy }).unwrap();
+        validate_token_ranges(&ranges, 1_001).unwrap();
+        assert!(coverage(&ranges, 1_001)
+            .iter()
+            .all(|count| *count == u32::from(redundancy)));
+    }
+    assert_eq!(
+        assign_token_ranges(34, 3, TokenRangeStrategy::Overlapping { redundancy: 4 }),
+        Err(AuctionError::InvalidTokenRanges)
+    );
+}
+
+#[test]
+fn sampled_windows_are_seeded_from_the_auction() {
+    let strategy = |auction_hash| TokenRangeStrategy::SampledWindows {
+        window_tokens: 50,
+        auction_hash,
+    };
+    let ranges = assign_token_ranges(1_000, 5, strategy([1; 32])).unwrap();
+    assert_eq!(assign_token_ranges(1_000, 5, strategy([1; 32])), Ok(ranges));
+    assert_ne!(assign_token_ranges(1_000, 5, strategy([2; 32])), Ok(ranges));
+    validate_token_ranges(&ranges, 1_000).unwrap();
+    assert!(ranges[..10]
+        .chunks_exact(2)
+        .all(|pair| pair[1] - pair[0] == 50));
+
+    let clamped = assign_token_ranges(20, 2, strategy([1; 32])).unwrap();
+    assert_eq!(clamped[..4], [0, 20, 0, 20]);
+}
+
+#[test]
+fn token_ranges_reject_bad_inputs() {
+    assert_eq!(
+        assign_token_ranges(10, 0, TokenRangeStrategy::EqualSplit),
+        Err(AuctionError::InvalidVerifierCount)
+    );
+    assert_eq!(
+        assign_token_ranges(
+            10,
+            MAX_EXTENDED_VERIFIERS_PER_AUCTION + 1,
+            TokenRangeStrategy::EqualSplit
+        ),
+        Err(AuctionError::InvalidVerifierCount)
+    );
+    assert_eq!(
+        assign_token_ranges(0, 3, TokenRangeStrategy::EqualSplit),
+        Err(AuctionError::InvalidTokenRanges)
+    );
+
+    assert_eq!(
+        validate_token_ranges(&[0, 8, 6, 17, 15, 35], 34),
+        Err(AuctionError::InvalidTokenRanges)
+    );
+    assert_eq!(
+        validate_token_ranges(&[9, 8], 34),
+        Err(AuctionError::InvalidTokenRanges)
+    );
+    assert_eq!(
+        verifier_reward_tokens(&[0, 8, 6], 34),
+        Err(AuctionError::InvalidTokenRanges)
+    );
+    assert_eq!(
+        verifier_reward_tokens(&[0, 8, 6, 17, 15, 34], 34).unwrap()[..3],
+        [8, 11, 19]
+    );
+}
@@ -0,0 +1,153 @@
+use ambient_auction_api::{
+    derive_verifier_selection_seed, error::AuctionError, select_verifiers,
+    verify_selected_verifiers, AccountLayoutVersion, BundleEscrowV2, BundleEscrowV2PolicySnapshot,
+    ConfigPolicyV2, Pubkey, RequestTier, MAX_EXTENDED_VERIFIERS_PER_AUCTION,
+    MAX_VERIFIERS_PER_AUCTION,
+};
+
+fn test_pubkey(byte: u8) -> Pubkey {
+    [byte; 32].into()
+}
+
+fn candidates() -> Vec<(Pubkey, u64)> {
+    (1..=6)
+        .map(|byte| (test_pubkey(byte), u64::from(byte) * 1_000))
+        .collect()
+}
+
+#[test]
+fn selection_is_deterministic_and_ignores_candidate_order() {
+    let seed = derive_verifier_selection_seed(&[7; 32], 1_234, &[9; 32]);
+    assert_ne!(
+        seed,
+        derive_verifier_selection_seed(&[7; 32], 1_235, &[9; 32])
+    );
+
+    let selected = select_verifiers(&candidates(), seed, 3, &test_pubkey(6)).unwrap();
+    let mut reversed = candidates();
+    reversed.reverse();
+    assert_eq!(
+        select_verifiers(&reversed, seed, 3, &test_pubkey(6)),
+        Ok(selected)
+    );
+
+    let picked = &selected[..3];
+    assert!(!picked.contains(&test_pubkey(6)));
+    assert!(!picked.contains(&Pubkey::default()));
+    for (index, verifier) in picked.iter().enumerate() {
+        assert!(!picked[index + 1..].contains(verifier));
+    }
+
+    assert_eq!(
+        verify_selected_verifiers(&candidates(), seed, 3, &test_pubkey(6), &selected),
+        Ok(())
+    );
+    let mut tampered = selected;
+    tampered.swap(0, 1);
+    assert_eq!(
+        verify_selected_verifiers(&candidates(), seed, 3, &test_pubkey(6), &tampered),
+        Err(AuctionError::VerifierSelectionMismatch)
+    );
+}
+
+#[test]
+fn selection_leaves_unused_slots_empty_and_rejects_bad_inputs() {
+    let seed = [3; 32];
+    let selected = select_verifiers(&candidates(), seed, 1, &test_pubkey(1)).unwrap();
+    assert_ne!(selected[0], Pubkey::default());
+    assert_eq!(
+        selected[1..],
+        [Pubkey::default(); MAX_EXTENDED_VERIFIERS_PER_AUCTION - 1]
+    );
+    assert_eq!(
+        verify_selected_verifiers(
+            &candidates(),
+            seed,
+            1,
+            &test_pubkey(1),
+            &selected[..MAX_VERIFIERS_PER_AUCTION]
+        ),
+        Ok(())
+    );
+
+    let few = [
+        (test_pubkey(1), 10),
+        (test_pubkey(2), 0),
+        (test_pubkey(3), 10),
+    ];
+    assert_eq!(
+        select_verifiers(&few, seed, 2, &test_pubkey(1)),
+        Err(AuctionError::InvalidVerifierCount)
+    );
+    assert_eq!(
+        select_verifiers(&candidates(), seed, 0, &test_pubkey(1)),
+        Err(AuctionError::InvalidVerifierCount)
+    );
+    let many: Vec<_> = (1..=20).map(|byte| (test_pubkey(byte), 10)).collect();
+    assert_eq!(
+        select_verifiers(
+            &many,
+            seed,
+            MAX_EXTENDED_VERIFIERS_PER_AUCTION + 1,
+            &test_pubkey(1)
+        ),
+        Err(AuctionError::InvalidVerifierCount)
+    );
+    let extended = select_verifiers(&many, seed, 12, &test_pubkey(1)).unwrap();
+    assert!(extended[..12].iter().all(|node| *node != Pubkey::default()));
+    assert_eq!(
+        verify_selected_verifiers(&many, seed, 12, &test_pubkey(1), &extended[..3]),
+        Err(AuctionError::VerifierSelectionMismatch)
+    );
+
+    let duplicated = [(test_pubkey(1), 10), (test_pubkey(1), 20)];
+    assert_eq!(
+        select_verifiers(&duplicated, seed, 1, &test_pubkey(9)),
+        Err(AuctionError::InvalidVerifierCandidates)
+    );
+    let overflowing = [(test_pubkey(1), u64::MAX), (test_pubkey(2), 1)];
+    assert_eq!(
+        select_verifiers(&overflowing, seed, 1, &test_pubkey(9)),
+        Err(AuctionError::InvalidVerifierCandidates)
+    );
+}
+
+#[test]
+fn selection_follows_stake_weights() {
+    let weighted = [(test_pubkey(1), 1), (test_pubkey(2), 3)];
+    let heavy_picks = (0..4_000u64)
+        .filter(|slot| {
+            let seed = derive_verifier_selection_seed(&[1; 32], *slot, &[2; 32]);
+            select_verifiers(&weighted, seed, 1, &test_pubkey(9)).unwrap()[0] == test_pubkey(2)
+        })
+        .count();
+    assert!((2_800..3_200).contains(&heavy_picks), "{heavy_picks}");
+}
+
+#[test]
+fn seed_slot_is_the_escrow_open_slot() {
+    let policy = ConfigPolicyV2::production_default();
+    let snapshot = BundleEscrowV2PolicySnapshot::capture(&policy, RequestTier::Standard);
+    let escrow = BundleEscrowV2 {
+        settlement_deadline_slot: 1_000 + snapshot.settlement_window_slots,
+        ..Default::default()
+    };
+
+    let mut bytes = vec![0; BundleEscrowV2::account_len(AccountLayoutVersion::V2)];
+    assert!(escrow.write_v2_bytes(&mut bytes));
+    assert_eq!(
+        BundleEscrowV2::from_bytes(&bytes)
+            .unwrap()
+            .verifier_seed_slot(),
+        None
+    );
+    assert!(BundleEscrowV2::from_bytes_mut(&mut bytes)
+        .unwrap()
+        .set_policy_snapshot(&snapshot));
+    assert_eq!(
+        BundleEscrowV2::from_bytes(&bytes)
+            .unwrap()
+            .verifier_seed_slot(),
+        Some(1_000)
+    );
+}